@@ -0,0 +1,16 @@
+//! Prints the average cost of one `next` call of the core indicators.
+//!
+//! ```text
+//! cargo bench --features bench --bench throughput
+//! ```
+
+use technical_analysis::benches::{throughput, LARGE_PERIOD, SMALL_PERIOD};
+
+const UPDATES: usize = 50_000;
+
+fn main() {
+    println!("{:<6}{:>16}{:>16}", "", format!("period {}", SMALL_PERIOD), format!("period {}", LARGE_PERIOD));
+    for t in throughput(UPDATES) {
+        println!("{:<6}{:>16?}{:>16?}", t.name, t.small, t.large);
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal_macros::dec;
+use ta::{Close, Next, Reset};
+
+use crate::checks::check_range;
+use crate::hilbert::DominantCyclePeriod;
+
+/// Shortest lookback the RSI is allowed to use.
+const MIN_PERIOD: usize = 5;
+/// Longest lookback the RSI is allowed to use.
+const MAX_PERIOD: usize = 25;
+
+/// RSI whose lookback follows the dominant cycle, after Ehlers.
+///
+/// Each bar the lookback is set to half the cycle period estimated by
+/// [DominantCyclePeriod](../hilbert/struct.DominantCyclePeriod.html), so the RSI spans
+/// one swing of the current cycle whether the market is moving in short or long waves.
+///
+/// # Formula
+///
+/// period = round(DCPERIOD / 2), limited to 5..=25
+///
+/// RSI = 100 * Σgains / (Σgains + Σlosses)
+///
+/// The sums run over the last _period_ changes. A lookback that changes every bar rules
+/// out Wilder's recursive smoothing, so the gains and losses are plain sums (Cutler's RSI).
+/// RSI is 50 when the input has not moved at all. Returns `None` until the cycle estimate
+/// is available, i.e. for the first 32 inputs.
+#[derive(Debug, Clone)]
+pub struct AdaptiveRsi {
+    cycle: DominantCyclePeriod,
+    /// The last `MAX_PERIOD + 1` inputs, newest last.
+    prices: VecDeque<Decimal>,
+    period: Option<usize>,
+}
+
+impl AdaptiveRsi {
+    pub fn new() -> Self {
+        Self {
+            cycle: DominantCyclePeriod::new(),
+            prices: VecDeque::with_capacity(MAX_PERIOD + 1),
+            period: None,
+        }
+    }
+
+    /// Lookback used by the last `next`. `None` until the cycle estimate is available.
+    pub fn effective_period(&self) -> Option<usize> {
+        self.period
+    }
+}
+
+impl Next<Decimal> for AdaptiveRsi {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        if self.prices.len() > MAX_PERIOD {
+            self.prices.pop_front();
+        }
+        self.prices.push_back(input);
+
+        let cycle = self.cycle.next(input)?;
+        let period = (cycle / dec!(2)).round().to_usize().unwrap().clamp(MIN_PERIOD, MAX_PERIOD);
+        self.period = Some(period);
+
+        let (gains, losses) = self
+            .prices
+            .iter()
+            .skip(self.prices.len() - period - 1)
+            .zip(self.prices.iter().skip(self.prices.len() - period))
+            .fold((dec!(0), dec!(0)), |(gains, losses), (prev, value)| {
+                let change = value - prev;
+                (gains + change.max(dec!(0)), losses + (-change).max(dec!(0)))
+            });
+
+        if (gains + losses).is_zero() {
+            return Some(dec!(50));
+        }
+        let rsi = dec!(100) * gains / (gains + losses);
+        check_range("ARSI", rsi, dec!(0), dec!(100));
+        Some(rsi)
+    }
+}
+
+impl<T: Close> Next<&T> for AdaptiveRsi {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for AdaptiveRsi {
+    fn reset(&mut self) {
+        self.cycle.reset();
+        self.prices.clear();
+        self.period = None;
+    }
+}
+
+impl Default for AdaptiveRsi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for AdaptiveRsi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ARSI")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use rust_decimal::prelude::FromPrimitive;
+    use crate::math::sin;
+    use crate::test_util::assert_full_reset;
+    use super::*;
+
+    fn sinusoid(period: usize, len: usize) -> Vec<Decimal> {
+        let step = Decimal::from_f64(2.0 * PI).unwrap() / Decimal::from(period);
+        (0..len).map(|i| dec!(100) + dec!(5) * sin(step * Decimal::from(i))).collect()
+    }
+
+    #[test]
+    fn test_warmup() {
+        let mut rsi = AdaptiveRsi::new();
+        let values = sinusoid(20, 33);
+        for value in &values[..32] {
+            assert_eq!(rsi.next(*value), None);
+            assert_eq!(rsi.effective_period(), None);
+        }
+        assert!(rsi.next(values[32]).is_some());
+        assert!(rsi.effective_period().is_some());
+    }
+
+    #[test]
+    fn test_period_adapts() {
+        let mut rsi = AdaptiveRsi::new();
+        let mut series = sinusoid(14, 300);
+        series.extend(sinusoid(40, 300));
+
+        let periods: Vec<usize> = series
+            .into_iter()
+            .filter_map(|value| {
+                let out = rsi.next(value)?;
+                assert!(out >= dec!(0) && out <= dec!(100));
+                rsi.effective_period()
+            })
+            .collect();
+
+        // half of a 14-bar cycle, then half of a 40-bar one
+        let short = &periods[200..268];
+        let long = &periods[468..];
+        assert!(short.iter().all(|period| (6..=8).contains(period)), "{:?}", short);
+        assert!(long.iter().all(|period| (18..=22).contains(period)), "{:?}", long);
+    }
+
+    #[test]
+    fn test_flat() {
+        let mut rsi = AdaptiveRsi::new();
+        let out = (0..40).map(|_| rsi.next(dec!(7))).last().unwrap();
+        assert_eq!(out, Some(dec!(50)));
+        assert!((MIN_PERIOD..=MAX_PERIOD).contains(&rsi.effective_period().unwrap()));
+    }
+
+    #[test]
+    fn test_full_reset() {
+        assert_full_reset(AdaptiveRsi::new(), &sinusoid(20, 60));
+    }
+
+    #[test]
+    fn test_default() {
+        AdaptiveRsi::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", AdaptiveRsi::new()), "ARSI");
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::standard_deviation::StandardDeviation;
+
+/// Simple moving average whose length follows volatility.
+///
+/// The standard deviation of close over _volatility_period_ is compared with the lowest and
+/// highest readings seen over the last _max_period_ bars. The lowest volatility maps to
+/// _min_period_, the highest to _max_period_, with the effective period linearly
+/// interpolated in between. The output is an SMA over that many of the latest closes, so
+/// the average reacts quickly in calm markets and smooths harder in volatile ones.
+///
+/// # Parameters
+///
+/// * _min_period_ - shortest averaging window (integer greater than 0)
+/// * _max_period_ - longest averaging window (not less than _min_period_)
+/// * _volatility_period_ - window of the standard deviation (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct AdaptiveSma {
+    min_period: usize,
+    max_period: usize,
+    sd: StandardDeviation,
+    volatility: VecDeque<Decimal>,
+    closes: VecDeque<Decimal>,
+    effective_period: Option<usize>,
+}
+
+impl AdaptiveSma {
+    pub fn new(min_period: usize, max_period: usize, volatility_period: usize) -> Result<Self> {
+        if min_period == 0 || min_period > max_period {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            min_period,
+            max_period,
+            sd: StandardDeviation::new(volatility_period)?,
+            volatility: VecDeque::with_capacity(max_period),
+            closes: VecDeque::with_capacity(max_period),
+            effective_period: None,
+        })
+    }
+
+    /// Averaging window used for the last output.
+    pub fn effective_period(&self) -> Option<usize> {
+        self.effective_period
+    }
+}
+
+impl<T: Close> Next<&T> for AdaptiveSma {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let close = Decimal::from_f64_retain(input.close()).unwrap();
+        if self.closes.len() == self.max_period {
+            self.closes.pop_front();
+        }
+        self.closes.push_back(close);
+
+        let sd = self.sd.next(close)?;
+        if self.volatility.len() == self.max_period {
+            self.volatility.pop_front();
+        }
+        self.volatility.push_back(sd);
+
+        let lowest = self.volatility.iter().copied().min().unwrap();
+        let highest = self.volatility.iter().copied().max().unwrap();
+        let ratio = if highest > lowest {
+            (sd - lowest) / (highest - lowest)
+        } else {
+            dec!(0)
+        };
+        let span = Decimal::from(self.max_period - self.min_period);
+        let extra = usize::try_from((ratio * span).round()).unwrap_or(0);
+        let period = self.min_period + extra;
+        self.effective_period = Some(period);
+
+        if self.closes.len() < period {
+            return None;
+        }
+        let sum: Decimal = self.closes.iter().rev().take(period).sum();
+        Some(sum / Decimal::from(period))
+    }
+}
+
+impl Reset for AdaptiveSma {
+    fn reset(&mut self) {
+        self.sd.reset();
+        self.volatility.clear();
+        self.closes.clear();
+        self.effective_period = None;
+    }
+}
+
+impl Default for AdaptiveSma {
+    fn default() -> Self {
+        Self::new(5, 20, 10).unwrap()
+    }
+}
+
+impl fmt::Display for AdaptiveSma {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ASMA({}, {}, {})", self.min_period, self.max_period, self.sd.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(close: f64) -> DataItem {
+        DataItem::builder().high(close).low(close).close(close).open(close).volume(0.0).build().unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(AdaptiveSma::new(0, 10, 5).is_err());
+        assert!(AdaptiveSma::new(10, 5, 5).is_err());
+        assert!(AdaptiveSma::new(5, 10, 0).is_err());
+        assert!(AdaptiveSma::new(5, 5, 5).is_ok());
+        assert!(AdaptiveSma::new(5, 10, 5).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut asma = AdaptiveSma::new(2, 4, 2).unwrap();
+        assert_eq!(asma.next(&bar(10.0)), None);
+        // flat volatility history -> shortest window
+        assert_eq!(asma.next(&bar(12.0)), Some(dec!(11)));
+        assert_eq!(asma.effective_period(), Some(2));
+    }
+
+    #[test]
+    fn test_effective_period_follows_volatility() {
+        let mut asma = AdaptiveSma::new(3, 12, 4).unwrap();
+
+        for i in 0..30 {
+            asma.next(&bar(if i % 2 == 0 { 100.0 } else { 101.0 }));
+        }
+        assert_eq!(asma.effective_period(), Some(3));
+
+        for i in 0..6 {
+            asma.next(&bar(if i % 2 == 0 { 90.0 } else { 110.0 }));
+        }
+        assert_eq!(asma.effective_period(), Some(12));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut asma = AdaptiveSma::new(2, 4, 2).unwrap();
+        asma.next(&bar(10.0));
+        assert!(asma.next(&bar(12.0)).is_some());
+
+        asma.reset();
+        assert_eq!(asma.effective_period(), None);
+        assert_eq!(asma.next(&bar(10.0)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        AdaptiveSma::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let asma = AdaptiveSma::new(5, 20, 10).unwrap();
+        assert_eq!(format!("{}", asma), "ASMA(5, 20, 10)");
+    }
+}
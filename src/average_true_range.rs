@@ -1,9 +1,13 @@
 use std::fmt;
 use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
 use ta::errors::Result;
 use crate::true_range::TrueRange;
 use ta::{Close, High, Low, Next, Period, Reset};
 
+use crate::checks::check_non_negative;
+use crate::model::DirectionalAtrOutput;
 use crate::rolling_moving_average::RollingMovingAverage;
 
 /// Average true range (ATR).
@@ -56,7 +60,7 @@ use crate::rolling_moving_average::RollingMovingAverage;
 ///     }
 /// }
 #[doc(alias = "ATR")]
-#[derive(Debug, Clone)]
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub struct AverageTrueRange {
     true_range: TrueRange,
     rma: RollingMovingAverage,
@@ -69,6 +73,31 @@ impl AverageTrueRange {
             rma: RollingMovingAverage::new(period)?,
         })
     }
+
+    /// Feeds the bar like `next` and returns the ATR as a percentage of its close, which
+    /// makes volatility comparable across instruments trading at different prices.
+    /// Returns `None` until the ATR is ready, or when the close is zero.
+    pub fn next_percent<T: High + Low + Close>(&mut self, input: &T) -> Option<Decimal> {
+        let atr = self.next(input)?;
+        let close = Decimal::from_f64(input.close()).unwrap();
+        if close.is_zero() {
+            return None;
+        }
+        Some(dec!(100) * atr / close)
+    }
+
+    /// Records `close` as the previous close without averaging a true range, so the first
+    /// range averaged is measured against it.
+    pub(crate) fn seed(&mut self, close: Decimal) {
+        self.true_range.seed(close);
+    }
+
+    /// `next` for a bar already given as decimals.
+    pub(crate) fn next_hlc(&mut self, high: Decimal, low: Decimal, close: Decimal) -> Option<Decimal> {
+        let atr = self.rma.next(self.true_range.next_hlc(high, low, close))?;
+        check_non_negative("ATR", atr);
+        Some(atr)
+    }
 }
 
 impl Period for AverageTrueRange {
@@ -89,7 +118,11 @@ impl<T: High + Low + Close> Next<&T> for AverageTrueRange {
     type Output = Option<Decimal>;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.rma.next(self.true_range.next(input))
+        self.next_hlc(
+            Decimal::from_f64(input.high()).unwrap(),
+            Decimal::from_f64(input.low()).unwrap(),
+            Decimal::from_f64(input.close()).unwrap(),
+        )
     }
 }
 
@@ -112,10 +145,93 @@ impl fmt::Display for AverageTrueRange {
     }
 }
 
+/// Directional ATR.
+///
+/// [AverageTrueRange](struct.AverageTrueRange.html) split by bar direction: the true range
+/// of a bar closing above the previous close feeds the up average, that of a bar closing
+/// below it feeds the down average, and the other average gets a zero. Unchanged closes,
+/// and the first bar, feed zero to both. Comparing the two shows whether volatility comes
+/// from rallies or from sell-offs.
+///
+/// # Formula
+///
+/// Up ATR = RMA(period) of (TR if close > close<sub>prev</sub>, otherwise 0)
+///
+/// Down ATR = RMA(period) of (TR if close < close<sub>prev</sub>, otherwise 0)
+///
+/// # Parameters
+///
+/// * _period_ - smoothing period (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct DirectionalAtr {
+    true_range: TrueRange,
+    up_rma: RollingMovingAverage,
+    down_rma: RollingMovingAverage,
+    prev_close: Option<Decimal>,
+}
+
+impl DirectionalAtr {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            true_range: TrueRange::new(),
+            up_rma: RollingMovingAverage::new(period)?,
+            down_rma: RollingMovingAverage::new(period)?,
+            prev_close: None,
+        })
+    }
+}
+
+impl Period for DirectionalAtr {
+    fn period(&self) -> usize {
+        self.up_rma.period()
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for DirectionalAtr {
+    type Output = DirectionalAtrOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let tr = self.true_range.next(input);
+        let close = Decimal::from_f64(input.close()).unwrap();
+
+        let (up, down) = match self.prev_close.replace(close) {
+            Some(prev_close) if close > prev_close => (tr, dec!(0)),
+            Some(prev_close) if close < prev_close => (dec!(0), tr),
+            _ => (dec!(0), dec!(0)),
+        };
+        DirectionalAtrOutput {
+            up_atr: self.up_rma.next(up),
+            down_atr: self.down_rma.next(down),
+        }
+    }
+}
+
+impl Reset for DirectionalAtr {
+    fn reset(&mut self) {
+        self.true_range.reset();
+        self.up_rma.reset();
+        self.down_rma.reset();
+        self.prev_close = None;
+    }
+}
+
+impl Default for DirectionalAtr {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for DirectionalAtr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DATR({})", self.up_rma.period())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ta::DataItem;
 
+    use crate::test_util::{self, assert_full_reset};
     use super::*;
 
     #[test]
@@ -123,6 +239,20 @@ mod tests {
         assert!(AverageTrueRange::new(0).is_err());
         assert!(AverageTrueRange::new(1).is_ok());
     }
+
+    #[test]
+    #[cfg(feature = "debug-checks")]
+    #[should_panic(expected = "ATR output")]
+    fn test_debug_checks() {
+        let di = DataItem::builder().open(10.0).high(11.0).low(9.0).close(10.0).volume(1.0).build().unwrap();
+        let mut atr = AverageTrueRange::new(2).unwrap();
+        atr.next(&di);
+        atr.next(&di);
+        // true ranges are never negative
+        atr.rma.next(dec!(-100));
+        atr.next(&di);
+    }
+
     #[test]
     fn test_next() {
         let mut atr = AverageTrueRange::new(3).unwrap();
@@ -198,6 +328,121 @@ mod tests {
         println!("6) {:?}", atr.next(&di6));
         println!("7) {:?}", atr.next(&di7));
     }
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        test_util::bar(high, low, close, 1.0)
+    }
+
+    #[test]
+    fn test_full_reset() {
+        let bars = [bar(10.0, 9.0, 9.5), bar(10.5, 9.5, 10.0), bar(11.0, 9.75, 10.5), bar(10.75, 10.0, 10.25)];
+        let series: Vec<&DataItem> = bars.iter().collect();
+        assert_full_reset(AverageTrueRange::new(2).unwrap(), &series);
+        assert_full_reset(DirectionalAtr::new(2).unwrap(), &series);
+    }
+
+    #[test]
+    fn test_next_percent() {
+        let mut atr = AverageTrueRange::new(2).unwrap();
+
+        assert_eq!(atr.next_percent(&bar(10.0, 9.0, 9.5)), None);
+        // tr = 1.0, 1.0 -> atr = 1.0, close = 10.0
+        assert_eq!(atr.next_percent(&bar(10.5, 9.5, 10.0)), Some(dec!(10)));
+    }
+
+    #[test]
+    fn test_next_percent_scales_with_price() {
+        let cheap = [bar(10.0, 9.0, 9.5), bar(10.5, 9.5, 10.0), bar(11.0, 9.75, 10.5), bar(10.75, 10.0, 10.25)];
+        let pricey = [bar(100.0, 90.0, 95.0), bar(105.0, 95.0, 100.0), bar(110.0, 97.5, 105.0), bar(107.5, 100.0, 102.5)];
+
+        let mut atr_cheap = AverageTrueRange::new(2).unwrap();
+        let mut atr_pricey = AverageTrueRange::new(2).unwrap();
+        for (c, p) in cheap.iter().zip(pricey.iter()) {
+            let pct_cheap = atr_cheap.next_percent(c);
+            let pct_pricey = atr_pricey.next_percent(p);
+            assert_eq!(pct_cheap.map(|v| v.round_dp(10)), pct_pricey.map(|v| v.round_dp(10)));
+        }
+        assert!(atr_pricey.next(&pricey[3]).unwrap() > atr_cheap.next(&cheap[3]).unwrap());
+    }
+
+    #[test]
+    fn test_next_percent_zero_close() {
+        let mut atr = AverageTrueRange::new(1).unwrap();
+        assert_eq!(atr.next_percent(&bar(1.0, 0.0, 0.0)), None);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut atr = AverageTrueRange::new(2).unwrap();
+        atr.next(&bar(10.0, 9.0, 9.5));
+
+        let json = serde_json::to_string(&atr).unwrap();
+        let mut restored: AverageTrueRange = serde_json::from_str(&json).unwrap();
+        for di in [bar(10.5, 9.5, 10.0), bar(11.0, 9.75, 10.5), bar(10.75, 10.0, 10.25)] {
+            assert_eq!(restored.next(&di), atr.next(&di));
+        }
+    }
+
+    #[test]
+    fn test_directional_new() {
+        assert!(DirectionalAtr::new(0).is_err());
+        assert!(DirectionalAtr::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_directional_next() {
+        let mut datr = DirectionalAtr::new(2).unwrap();
+
+        let out = datr.next(&bar(10.0, 9.0, 9.5));
+        assert_eq!(out, DirectionalAtrOutput { up_atr: None, down_atr: None });
+
+        // up close, tr = 1.0
+        let out = datr.next(&bar(10.5, 9.5, 10.0));
+        assert_eq!(out, DirectionalAtrOutput { up_atr: Some(dec!(0.5)), down_atr: Some(dec!(0)) });
+
+        // down close, tr = 10.5 - 7.0 = 3.5
+        let out = datr.next(&bar(10.5, 7.0, 7.5));
+        assert_eq!(out, DirectionalAtrOutput { up_atr: Some(dec!(0.25)), down_atr: Some(dec!(1.75)) });
+    }
+
+    #[test]
+    fn test_directional_asymmetric() {
+        // grinding up in small steps, falling in large ones
+        let mut datr = DirectionalAtr::new(4).unwrap();
+        let mut close = 100.0;
+        let mut out = datr.next(&bar(close + 0.5, close - 0.5, close));
+        for i in 0..30 {
+            if i % 3 == 2 {
+                close -= 3.0;
+                out = datr.next(&bar(close + 3.0, close - 0.5, close));
+            } else {
+                close += 1.0;
+                out = datr.next(&bar(close + 0.2, close - 1.0, close));
+            }
+        }
+        assert!(out.down_atr.unwrap() > out.up_atr.unwrap());
+    }
+
+    #[test]
+    fn test_directional_reset() {
+        let mut datr = DirectionalAtr::new(1).unwrap();
+        datr.next(&bar(10.0, 9.0, 9.5));
+        datr.next(&bar(10.5, 9.5, 10.0));
+
+        datr.reset();
+        let out = datr.next(&bar(10.5, 9.5, 10.0));
+        assert_eq!(out, DirectionalAtrOutput { up_atr: Some(dec!(0)), down_atr: Some(dec!(0)) });
+    }
+
+    #[test]
+    fn test_directional_default() {
+        DirectionalAtr::default();
+    }
+
+    #[test]
+    fn test_directional_display() {
+        assert_eq!(format!("{}", DirectionalAtr::default()), "DATR(14)");
+    }
 }
 //     #[test]
 //     fn test_reset() {
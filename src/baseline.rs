@@ -0,0 +1,167 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, High, Low, Next, Period, Reset};
+
+use crate::model::Cross;
+
+/// Baseline cross (Kijun-sen style).
+///
+/// Midpoint of the range over the last _period_ bars, used as a trend filter, together with
+/// whether the close crossed it on the latest bar.
+///
+/// # Formula
+///
+/// Baseline = (HH + LL) / 2
+///
+/// Where _HH_ and _LL_ are the highest high and lowest low over the last _period_ bars, the
+/// current one included.
+///
+/// The cross is `Up` when the previous close was at or below the previous baseline and the
+/// close is now above the baseline, `Down` for the mirror case and `None` otherwise,
+/// including on the first bar with a full window. Returns `None` until _period_ bars have
+/// been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[doc(alias = "KIJUN")]
+#[derive(Debug, Clone)]
+pub struct BaselineCross {
+    period: usize,
+    window: VecDeque<(Decimal, Decimal)>,
+    prev: Option<(Decimal, Decimal)>,
+}
+
+impl BaselineCross {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                window: VecDeque::with_capacity(period),
+                prev: None,
+            }),
+        }
+    }
+}
+
+impl Period for BaselineCross {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for BaselineCross {
+    type Output = Option<(Decimal, Cross)>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back((
+            Decimal::from_f64(input.high()).unwrap(),
+            Decimal::from_f64(input.low()).unwrap(),
+        ));
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let (highest, lowest) = self.window.iter().fold(
+            (Decimal::MIN, Decimal::MAX),
+            |(hh, ll), (high, low)| (hh.max(*high), ll.min(*low)),
+        );
+        let baseline = (highest + lowest) / dec!(2);
+        let close = Decimal::from_f64_retain(input.close()).unwrap();
+
+        let cross = match self.prev.replace((close, baseline)) {
+            Some((prev_close, prev_baseline)) if prev_close <= prev_baseline && close > baseline => Cross::Up,
+            Some((prev_close, prev_baseline)) if prev_close >= prev_baseline && close < baseline => Cross::Down,
+            _ => Cross::None,
+        };
+        Some((baseline, cross))
+    }
+}
+
+impl Reset for BaselineCross {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.prev = None;
+    }
+}
+
+impl Default for BaselineCross {
+    fn default() -> Self {
+        Self::new(26).unwrap()
+    }
+}
+
+impl fmt::Display for BaselineCross {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BASELINE({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder()
+            .high(high)
+            .low(low)
+            .close(close)
+            .open(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(BaselineCross::new(0).is_err());
+        assert!(BaselineCross::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_cross_up() {
+        let mut baseline = BaselineCross::new(3).unwrap();
+        assert_eq!(baseline.next(&bar(12.0, 8.0, 9.0)), None);
+        assert_eq!(baseline.next(&bar(11.0, 9.0, 9.5)), None);
+        assert_eq!(baseline.next(&bar(10.0, 8.0, 9.0)), Some((dec!(10), Cross::None)));
+        assert_eq!(baseline.next(&bar(11.0, 9.0, 10.5)), Some((dec!(9.5), Cross::Up)));
+        assert_eq!(baseline.next(&bar(12.0, 10.0, 11.5)), Some((dec!(10), Cross::None)));
+    }
+
+    #[test]
+    fn test_cross_down() {
+        let mut baseline = BaselineCross::new(2).unwrap();
+        baseline.next(&bar(12.0, 10.0, 11.5));
+        assert_eq!(baseline.next(&bar(13.0, 11.0, 12.5)), Some((dec!(11.5), Cross::None)));
+        assert_eq!(baseline.next(&bar(12.0, 10.0, 10.5)), Some((dec!(11.5), Cross::Down)));
+        assert_eq!(baseline.next(&bar(11.0, 9.0, 9.5)), Some((dec!(10.5), Cross::None)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut baseline = BaselineCross::new(1).unwrap();
+        baseline.next(&bar(10.0, 8.0, 8.5));
+
+        baseline.reset();
+        assert_eq!(baseline.next(&bar(10.0, 8.0, 9.5)), Some((dec!(9), Cross::None)));
+    }
+
+    #[test]
+    fn test_default() {
+        BaselineCross::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", BaselineCross::new(26).unwrap()), "BASELINE(26)");
+    }
+}
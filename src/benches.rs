@@ -0,0 +1,131 @@
+//! Throughput measurements for the core indicators.
+//!
+//! Only built with the `bench` feature. [throughput](fn.throughput.html) times one `next`
+//! call of each indicator at a short and a long period; if updates are O(1) the two
+//! costs stay close. The `throughput` bench target prints the table:
+//!
+//! ```text
+//! cargo bench --features bench --bench throughput
+//! ```
+
+use std::time::{Duration, Instant};
+use rust_decimal::Decimal;
+use ta::{DataItem, Next};
+
+use crate::average_true_range::AverageTrueRange;
+use crate::directional_movement_index::DirectionalMovementIndex;
+use crate::ema::ExponentialMovingAverage;
+use crate::rolling_moving_average::RollingMovingAverage;
+use crate::simple_moving_average::SimpleMovingAverage;
+
+/// Period of the short-window instance in [throughput](fn.throughput.html).
+pub const SMALL_PERIOD: usize = 10;
+/// Period of the long-window instance in [throughput](fn.throughput.html).
+pub const LARGE_PERIOD: usize = 5_000;
+
+/// Average cost of one update of an indicator at `SMALL_PERIOD` and `LARGE_PERIOD`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Throughput {
+    pub name: &'static str,
+    pub small: Duration,
+    pub large: Duration,
+}
+
+/// Deterministic pseudo-random prices around 100, so runs are comparable.
+pub fn prices(count: usize) -> Vec<Decimal> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..count)
+        .map(|_| {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            Decimal::new(9_000 + (state >> 33) as i64 % 2_000, 2)
+        })
+        .collect()
+}
+
+/// Bars built from `prices`, with a fixed spread around each close.
+pub fn bars(count: usize) -> Vec<DataItem> {
+    prices(count)
+        .into_iter()
+        .map(|price| {
+            let close: f64 = price.try_into().unwrap();
+            DataItem::builder()
+                .open(close)
+                .high(close + 0.5)
+                .low(close - 0.5)
+                .close(close)
+                .volume(1_000.0)
+                .build()
+                .unwrap()
+        })
+        .collect()
+}
+
+/// Average time of one call to `step`, which is handed every input in turn.
+pub fn time_per_update<T>(inputs: &[T], mut step: impl FnMut(&T)) -> Duration {
+    let start = Instant::now();
+    for input in inputs {
+        step(input);
+    }
+    start.elapsed() / inputs.len().max(1) as u32
+}
+
+/// Times SMA, EMA, RMA, ATR and DMI over `updates` inputs each.
+///
+/// Timings are only meaningful in an optimized build.
+pub fn throughput(updates: usize) -> Vec<Throughput> {
+    let prices = prices(updates);
+    let bars = bars(updates);
+
+    let mut small = SimpleMovingAverage::new(SMALL_PERIOD).unwrap();
+    let mut large = SimpleMovingAverage::new(LARGE_PERIOD).unwrap();
+    let sma = Throughput {
+        name: "SMA",
+        small: time_per_update(&prices, |p| { small.next(*p); }),
+        large: time_per_update(&prices, |p| { large.next(*p); }),
+    };
+
+    let mut small = ExponentialMovingAverage::new(SMALL_PERIOD).unwrap();
+    let mut large = ExponentialMovingAverage::new(LARGE_PERIOD).unwrap();
+    let ema = Throughput {
+        name: "EMA",
+        small: time_per_update(&prices, |p| { small.next(*p); }),
+        large: time_per_update(&prices, |p| { large.next(*p); }),
+    };
+
+    let mut small = RollingMovingAverage::new(SMALL_PERIOD).unwrap();
+    let mut large = RollingMovingAverage::new(LARGE_PERIOD).unwrap();
+    let rma = Throughput {
+        name: "RMA",
+        small: time_per_update(&prices, |p| { small.next(*p); }),
+        large: time_per_update(&prices, |p| { large.next(*p); }),
+    };
+
+    let mut small = AverageTrueRange::new(SMALL_PERIOD).unwrap();
+    let mut large = AverageTrueRange::new(LARGE_PERIOD).unwrap();
+    let atr = Throughput {
+        name: "ATR",
+        small: time_per_update(&bars, |di| { small.next(di); }),
+        large: time_per_update(&bars, |di| { large.next(di); }),
+    };
+
+    let mut small = DirectionalMovementIndex::new(SMALL_PERIOD).unwrap();
+    let mut large = DirectionalMovementIndex::new(LARGE_PERIOD).unwrap();
+    let dmi = Throughput {
+        name: "DMI",
+        small: time_per_update(&bars, |di| { small.next(di); }),
+        large: time_per_update(&bars, |di| { large.next(di); }),
+    };
+
+    vec![sma, ema, rma, atr, dmi]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throughput() {
+        let names: Vec<_> = throughput(100).into_iter().map(|t| t.name).collect();
+        assert_eq!(names, ["SMA", "EMA", "RMA", "ATR", "DMI"]);
+    }
+}
@@ -0,0 +1,442 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::model::{BollingerBandsOutput, BollingerPercentBOutput};
+use crate::primitives::percent_rank;
+use crate::standard_deviation::StandardDeviation;
+
+/// Bollinger Bands (BB).
+///
+/// A simple moving average with bands _multiplier_ standard deviations above and below it.
+///
+/// # Formula
+///
+/// Average = SMA(period)
+///
+/// Upper = Average + SD(period) * multiplier
+///
+/// Lower = Average - SD(period) * multiplier
+///
+/// Returns `None` until _period_ values have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+/// * _multiplier_ - number of standard deviations (greater than 0)
+#[doc(alias = "BB")]
+#[derive(Debug, Clone)]
+pub struct BollingerBands {
+    sd: StandardDeviation,
+    multiplier: Decimal,
+}
+
+impl BollingerBands {
+    pub fn new(period: usize, multiplier: Decimal) -> Result<Self> {
+        if multiplier <= dec!(0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            sd: StandardDeviation::new(period)?,
+            multiplier,
+        })
+    }
+
+    pub fn multiplier(&self) -> Decimal {
+        self.multiplier
+    }
+}
+
+impl Period for BollingerBands {
+    fn period(&self) -> usize {
+        self.sd.period()
+    }
+}
+
+impl Next<Decimal> for BollingerBands {
+    type Output = Option<BollingerBandsOutput>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let sd = self.sd.next(input)?;
+        let average = self.sd.mean()?;
+        Some(BollingerBandsOutput {
+            average,
+            upper: average + sd * self.multiplier,
+            lower: average - sd * self.multiplier,
+        })
+    }
+}
+
+impl<T: Close> Next<&T> for BollingerBands {
+    type Output = Option<BollingerBandsOutput>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for BollingerBands {
+    fn reset(&mut self) {
+        self.sd.reset();
+    }
+}
+
+impl Default for BollingerBands {
+    fn default() -> Self {
+        Self::new(20, dec!(2.0)).unwrap()
+    }
+}
+
+impl fmt::Display for BollingerBands {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BB({}, {})", self.sd.period(), self.multiplier)
+    }
+}
+
+/// Width of the bands relative to their average, or 0 when the average is 0.
+fn bandwidth(bands: &BollingerBandsOutput) -> Decimal {
+    if bands.average.is_zero() {
+        dec!(0)
+    } else {
+        (bands.upper - bands.lower) / bands.average
+    }
+}
+
+/// Bollinger %B and Bandwidth.
+///
+/// Normalized readings of [BollingerBands](struct.BollingerBands.html). %B places the
+/// close within the bands and goes above 1 or below 0 when the close breaks out of them.
+/// Bandwidth measures how wide the bands are relative to the average, and its lows mark
+/// squeezes.
+///
+/// # Formula
+///
+/// %B = (close - Lower) / (Upper - Lower), or 0.5 when Upper = Lower
+///
+/// Bandwidth = (Upper - Lower) / Average, or 0 when Average = 0
+///
+/// Returns `None` until _period_ values have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+/// * _multiplier_ - number of standard deviations (greater than 0)
+#[doc(alias = "%B")]
+#[derive(Debug, Clone)]
+pub struct BollingerPercentB {
+    bb: BollingerBands,
+}
+
+impl BollingerPercentB {
+    pub fn new(period: usize, multiplier: Decimal) -> Result<Self> {
+        Ok(Self {
+            bb: BollingerBands::new(period, multiplier)?,
+        })
+    }
+}
+
+impl Period for BollingerPercentB {
+    fn period(&self) -> usize {
+        self.bb.period()
+    }
+}
+
+impl Next<Decimal> for BollingerPercentB {
+    type Output = Option<BollingerPercentBOutput>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let bands = self.bb.next(input)?;
+        let width = bands.upper - bands.lower;
+        let percent_b = if width.is_zero() {
+            dec!(0.5)
+        } else {
+            (input - bands.lower) / width
+        };
+        Some(BollingerPercentBOutput {
+            percent_b,
+            bandwidth: bandwidth(&bands),
+        })
+    }
+}
+
+impl<T: Close> Next<&T> for BollingerPercentB {
+    type Output = Option<BollingerPercentBOutput>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for BollingerPercentB {
+    fn reset(&mut self) {
+        self.bb.reset();
+    }
+}
+
+impl Default for BollingerPercentB {
+    fn default() -> Self {
+        Self::new(20, dec!(2.0)).unwrap()
+    }
+}
+
+impl fmt::Display for BollingerPercentB {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "%B({}, {})", self.bb.period(), self.bb.multiplier())
+    }
+}
+
+/// Bollinger Bandwidth percentile.
+///
+/// Ranks the current [Bandwidth](struct.BollingerPercentB.html) against its recent history,
+/// to spot squeezes without picking an absolute width: a reading near 0 means the bands
+/// are as narrow as they have been in _rank_period_ bars.
+///
+/// # Formula
+///
+/// Percentile = 100 * share of the previous _rank_period_ bandwidths ≤ Bandwidth
+///
+/// Returns `None` until _bb_period_ + _rank_period_ values have been seen.
+///
+/// # Parameters
+///
+/// * _bb_period_ - period of the bands (integer greater than 0)
+/// * _bb_mult_ - number of standard deviations (greater than 0)
+/// * _rank_period_ - number of past bandwidths ranked against (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct BollingerBandwidthPercentile {
+    bb: BollingerBands,
+    rank_period: usize,
+    history: VecDeque<Decimal>,
+}
+
+impl BollingerBandwidthPercentile {
+    pub fn new(bb_period: usize, bb_mult: Decimal, rank_period: usize) -> Result<Self> {
+        if rank_period == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            bb: BollingerBands::new(bb_period, bb_mult)?,
+            rank_period,
+            history: VecDeque::with_capacity(rank_period),
+        })
+    }
+}
+
+impl Period for BollingerBandwidthPercentile {
+    fn period(&self) -> usize {
+        self.rank_period
+    }
+}
+
+impl Next<Decimal> for BollingerBandwidthPercentile {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let bandwidth = bandwidth(&self.bb.next(input)?);
+        let percentile = if self.history.len() == self.rank_period {
+            Some(percent_rank(&self.history, bandwidth) * dec!(100))
+        } else {
+            None
+        };
+        if self.history.len() == self.rank_period {
+            self.history.pop_front();
+        }
+        self.history.push_back(bandwidth);
+        percentile
+    }
+}
+
+impl<T: Close> Next<&T> for BollingerBandwidthPercentile {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for BollingerBandwidthPercentile {
+    fn reset(&mut self) {
+        self.bb.reset();
+        self.history.clear();
+    }
+}
+
+impl Default for BollingerBandwidthPercentile {
+    fn default() -> Self {
+        Self::new(20, dec!(2.0), 125).unwrap()
+    }
+}
+
+impl fmt::Display for BollingerBandwidthPercentile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BBW%({}, {}, {})", self.bb.period(), self.bb.multiplier(), self.rank_period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(BollingerBands::new(0, dec!(2)).is_err());
+        assert!(BollingerBands::new(20, dec!(0)).is_err());
+        assert!(BollingerBands::new(20, dec!(2)).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bb = BollingerBands::new(3, dec!(2)).unwrap();
+        assert_eq!(bb.next(dec!(2)), None);
+        assert_eq!(bb.next(dec!(5)), None);
+
+        // mean 3, SD sqrt(2)
+        let out = bb.next(dec!(2)).unwrap();
+        assert_eq!(out.average, dec!(3));
+        assert_eq!(out.upper.round_dp(8), dec!(5.82842712));
+        assert_eq!(out.lower.round_dp(8), dec!(0.17157288));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bb = BollingerBands::new(2, dec!(2)).unwrap();
+        bb.next(dec!(1));
+        assert!(bb.next(dec!(2)).is_some());
+
+        bb.reset();
+        assert_eq!(bb.next(dec!(1)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        BollingerBands::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", BollingerBands::default()), "BB(20, 2.0)");
+    }
+
+    #[test]
+    fn test_percent_b_new() {
+        assert!(BollingerPercentB::new(0, dec!(2)).is_err());
+        assert!(BollingerPercentB::new(20, dec!(0)).is_err());
+        assert!(BollingerPercentB::new(20, dec!(2)).is_ok());
+    }
+
+    #[test]
+    fn test_percent_b_next() {
+        // over two values, one SD either side of the mean reaches exactly both values
+        let mut percent_b = BollingerPercentB::new(2, dec!(1)).unwrap();
+        assert_eq!(percent_b.next(dec!(1)), None);
+
+        let out = percent_b.next(dec!(3)).unwrap();
+        assert_eq!(out.percent_b, dec!(1));
+        assert_eq!(out.bandwidth, dec!(1));
+
+        let out = percent_b.next(dec!(1)).unwrap();
+        assert_eq!(out.percent_b, dec!(0));
+        assert_eq!(out.bandwidth, dec!(1));
+    }
+
+    #[test]
+    fn test_percent_b_outside_bands() {
+        let mut percent_b = BollingerPercentB::new(3, dec!(1)).unwrap();
+        percent_b.next(dec!(10));
+        percent_b.next(dec!(10));
+        assert!(percent_b.next(dec!(20)).unwrap().percent_b > dec!(1));
+    }
+
+    #[test]
+    fn test_percent_b_flat() {
+        let mut percent_b = BollingerPercentB::new(2, dec!(2)).unwrap();
+        percent_b.next(dec!(5));
+        let out = percent_b.next(dec!(5)).unwrap();
+        assert_eq!(out.percent_b, dec!(0.5));
+        assert_eq!(out.bandwidth, dec!(0));
+    }
+
+    #[test]
+    fn test_percent_b_reset() {
+        let mut percent_b = BollingerPercentB::new(2, dec!(2)).unwrap();
+        percent_b.next(dec!(1));
+        assert!(percent_b.next(dec!(2)).is_some());
+
+        percent_b.reset();
+        assert_eq!(percent_b.next(dec!(1)), None);
+    }
+
+    #[test]
+    fn test_percent_b_default() {
+        BollingerPercentB::default();
+    }
+
+    #[test]
+    fn test_percent_b_display() {
+        assert_eq!(format!("{}", BollingerPercentB::default()), "%B(20, 2.0)");
+    }
+
+    /// Closes swinging around 100 by `amplitude(i)`.
+    fn swings(count: usize, amplitude: impl Fn(usize) -> Decimal) -> Vec<Decimal> {
+        (0..count)
+            .map(|i| if i % 2 == 0 { dec!(100) + amplitude(i) } else { dec!(100) - amplitude(i) })
+            .collect()
+    }
+
+    #[test]
+    fn test_bandwidth_percentile_new() {
+        assert!(BollingerBandwidthPercentile::new(20, dec!(2), 0).is_err());
+        assert!(BollingerBandwidthPercentile::new(0, dec!(2), 125).is_err());
+        assert!(BollingerBandwidthPercentile::new(20, dec!(0), 125).is_err());
+        assert!(BollingerBandwidthPercentile::new(20, dec!(2), 125).is_ok());
+    }
+
+    #[test]
+    fn test_bandwidth_percentile_warmup() {
+        let mut bbwp = BollingerBandwidthPercentile::new(3, dec!(2), 4).unwrap();
+        let outputs: Vec<_> = swings(8, |_| dec!(1)).into_iter().map(|close| bbwp.next(close)).collect();
+        assert!(outputs[..6].iter().all(Option::is_none));
+        assert!(outputs[6].is_some());
+    }
+
+    #[test]
+    fn test_bandwidth_percentile_contracting() {
+        let mut bbwp = BollingerBandwidthPercentile::new(4, dec!(2), 10).unwrap();
+        let closes = swings(30, |i| Decimal::from(30 - i) / dec!(5));
+        let last = closes.into_iter().filter_map(|close| bbwp.next(close)).last().unwrap();
+        assert_eq!(last, dec!(0));
+    }
+
+    #[test]
+    fn test_bandwidth_percentile_expanding() {
+        let mut bbwp = BollingerBandwidthPercentile::new(4, dec!(2), 10).unwrap();
+        let closes = swings(30, |i| Decimal::from(i + 1) / dec!(5));
+        let last = closes.into_iter().filter_map(|close| bbwp.next(close)).last().unwrap();
+        assert_eq!(last, dec!(100));
+    }
+
+    #[test]
+    fn test_bandwidth_percentile_reset() {
+        let mut bbwp = BollingerBandwidthPercentile::new(2, dec!(2), 1).unwrap();
+        bbwp.next(dec!(1));
+        bbwp.next(dec!(2));
+        assert!(bbwp.next(dec!(1)).is_some());
+
+        bbwp.reset();
+        bbwp.next(dec!(1));
+        assert_eq!(bbwp.next(dec!(2)), None);
+    }
+
+    #[test]
+    fn test_bandwidth_percentile_default() {
+        BollingerBandwidthPercentile::default();
+    }
+
+    #[test]
+    fn test_bandwidth_percentile_display() {
+        assert_eq!(format!("{}", BollingerBandwidthPercentile::default()), "BBW%(20, 2.0, 125)");
+    }
+}
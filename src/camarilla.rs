@@ -0,0 +1,118 @@
+//! Camarilla pivot levels and the intraday signals read from them.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::model::{CamarillaLevels, CamarillaSignal};
+
+/// Camarilla levels for a session, from the previous session's `high`, `low` and `close`.
+///
+/// H3 and L3 are where a move away from the close is expected to reverse; H4 and L4 are
+/// where it is instead expected to break out and run.
+///
+/// # Formula
+///
+/// range = high - low
+///
+/// H4 = close + 1.1 * range / 2, H3 = close + 1.1 * range / 4
+///
+/// L3 = close - 1.1 * range / 4, L4 = close - 1.1 * range / 2
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use technical_analysis::camarilla::{camarilla_levels, camarilla_signal};
+/// use technical_analysis::model::CamarillaSignal;
+///
+/// let levels = camarilla_levels(dec!(110), dec!(90), dec!(100));
+/// assert_eq!(levels.h4, dec!(111));
+/// assert_eq!(levels.l3, dec!(94.5));
+/// assert_eq!(camarilla_signal(&levels, dec!(94)), CamarillaSignal::ReversalLong);
+/// ```
+pub fn camarilla_levels(high: Decimal, low: Decimal, close: Decimal) -> CamarillaLevels {
+    let range = dec!(1.1) * (high - low);
+    CamarillaLevels {
+        h4: close + range / dec!(2),
+        h3: close + range / dec!(4),
+        l3: close - range / dec!(4),
+        l4: close - range / dec!(2),
+    }
+}
+
+/// Classifies `close` against `levels`.
+///
+/// * `BreakoutLong` above H4, `BreakoutShort` below L4
+/// * `ReversalShort` above H3 up to H4, `ReversalLong` below L3 down to L4
+/// * `None` from L3 to H3
+pub fn camarilla_signal(levels: &CamarillaLevels, close: Decimal) -> CamarillaSignal {
+    if close > levels.h4 {
+        CamarillaSignal::BreakoutLong
+    } else if close > levels.h3 {
+        CamarillaSignal::ReversalShort
+    } else if close < levels.l4 {
+        CamarillaSignal::BreakoutShort
+    } else if close < levels.l3 {
+        CamarillaSignal::ReversalLong
+    } else {
+        CamarillaSignal::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels() {
+        // range 20, 1.1 * range = 22
+        let levels = camarilla_levels(dec!(110), dec!(90), dec!(100));
+        assert_eq!(
+            levels,
+            CamarillaLevels {
+                h4: dec!(111),
+                h3: dec!(105.5),
+                l3: dec!(94.5),
+                l4: dec!(89),
+            }
+        );
+    }
+
+    #[test]
+    fn test_levels_centred_on_close() {
+        // the levels follow the close, not the middle of the range
+        let levels = camarilla_levels(dec!(50), dec!(40), dec!(49));
+        assert_eq!(levels.h4, dec!(54.5));
+        assert_eq!(levels.h3, dec!(51.75));
+        assert_eq!(levels.l3, dec!(46.25));
+        assert_eq!(levels.l4, dec!(43.5));
+    }
+
+    #[test]
+    fn test_zero_range() {
+        let levels = camarilla_levels(dec!(7), dec!(7), dec!(7));
+        assert_eq!(levels.h4, dec!(7));
+        assert_eq!(levels.l4, dec!(7));
+        assert_eq!(camarilla_signal(&levels, dec!(7)), CamarillaSignal::None);
+        assert_eq!(camarilla_signal(&levels, dec!(7.01)), CamarillaSignal::BreakoutLong);
+    }
+
+    #[test]
+    fn test_signal() {
+        let levels = camarilla_levels(dec!(110), dec!(90), dec!(100));
+        let cases = [
+            (dec!(112), CamarillaSignal::BreakoutLong),
+            (dec!(111), CamarillaSignal::ReversalShort),
+            (dec!(108), CamarillaSignal::ReversalShort),
+            (dec!(105.5), CamarillaSignal::None),
+            (dec!(100), CamarillaSignal::None),
+            (dec!(94.5), CamarillaSignal::None),
+            (dec!(92), CamarillaSignal::ReversalLong),
+            (dec!(89), CamarillaSignal::ReversalLong),
+            (dec!(88), CamarillaSignal::BreakoutShort),
+        ];
+        for (close, expected) in cases {
+            assert_eq!(camarilla_signal(&levels, close), expected, "close {}", close);
+        }
+    }
+}
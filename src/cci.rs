@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, High, Low, Next, Period, Reset};
+
+/// Commodity Channel Index (CCI).
+///
+/// How far the typical price is from its moving average, in units of its mean absolute
+/// deviation. Lambert's 0.015 constant puts most readings between -100 and 100.
+///
+/// # Formula
+///
+/// tp = (high + low + close) / 3
+///
+/// CCI = (tp - SMA(tp, period)) / (0.015 * MD)
+///
+/// Where MD is the mean absolute deviation of the last _period_ typical prices from their
+/// SMA. CCI is 0 when MD is 0. Returns `None` until _period_ bars have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[doc(alias = "CCI")]
+#[derive(Debug, Clone)]
+pub struct CommodityChannelIndex {
+    period: usize,
+    window: VecDeque<Decimal>,
+    sum: Decimal,
+}
+
+impl CommodityChannelIndex {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                window: VecDeque::with_capacity(period),
+                sum: dec!(0),
+            }),
+        }
+    }
+}
+
+impl Period for CommodityChannelIndex {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for CommodityChannelIndex {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let tp = Decimal::from_f64((input.high() + input.low() + input.close()) / 3.0).unwrap();
+        if self.window.len() == self.period {
+            self.sum -= self.window.pop_front().unwrap();
+        }
+        self.window.push_back(tp);
+        self.sum += tp;
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let n = Decimal::from(self.period);
+        let mean = self.sum / n;
+        let deviation = self.window.iter().map(|value| (value - mean).abs()).sum::<Decimal>() / n;
+        if deviation.is_zero() {
+            return Some(dec!(0));
+        }
+        Some((tp - mean) / (dec!(0.015) * deviation))
+    }
+}
+
+impl Reset for CommodityChannelIndex {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.sum = dec!(0);
+    }
+}
+
+impl Default for CommodityChannelIndex {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for CommodityChannelIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CCI({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(close: f64) -> DataItem {
+        DataItem::builder()
+            .high(close)
+            .low(close)
+            .close(close)
+            .open(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(CommodityChannelIndex::new(0).is_err());
+        assert!(CommodityChannelIndex::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut cci = CommodityChannelIndex::new(3).unwrap();
+        assert_eq!(cci.next(&bar(10.0)), None);
+        assert_eq!(cci.next(&bar(12.0)), None);
+        // mean 12, MD (2 + 0 + 2) / 3: (14 - 12) / (0.015 * 4 / 3)
+        assert_eq!(cci.next(&bar(14.0)).unwrap().round_dp(8), dec!(100));
+        // mean 12, MD 4 / 3: (10 - 12) / 0.02
+        assert_eq!(cci.next(&bar(10.0)).unwrap().round_dp(8), dec!(-100));
+    }
+
+    #[test]
+    fn test_typical_price() {
+        let mut cci = CommodityChannelIndex::new(2).unwrap();
+        let item = |high, low, close| {
+            DataItem::builder().high(high).low(low).close(close).open(close).volume(1.0).build().unwrap()
+        };
+        cci.next(&item(12.0, 9.0, 9.0));
+        // typical prices 10 and 12: (12 - 11) / (0.015 * 1)
+        assert_eq!(cci.next(&item(13.0, 11.0, 12.0)).unwrap().round_dp(8), dec!(66.66666667));
+    }
+
+    #[test]
+    fn test_flat() {
+        let mut cci = CommodityChannelIndex::new(2).unwrap();
+        cci.next(&bar(5.0));
+        assert_eq!(cci.next(&bar(5.0)), Some(dec!(0)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cci = CommodityChannelIndex::new(2).unwrap();
+        cci.next(&bar(5.0));
+        assert!(cci.next(&bar(6.0)).is_some());
+
+        cci.reset();
+        assert_eq!(cci.next(&bar(7.0)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        CommodityChannelIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", CommodityChannelIndex::default()), "CCI(20)");
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::errors::Result;
+use ta::{High, Low, Next, Period, Reset};
+
+use crate::ema::ExponentialMovingAverage;
+use crate::roc::RateOfChange;
+
+/// Chaikin Volatility (CV).
+///
+/// The rate of change of the smoothed high-low range. Positive when bars are widening
+/// compared with _roc_period_ bars ago, negative when they are narrowing.
+///
+/// # Formula
+///
+/// CV = ROC(EMA(high - low, ema_period), roc_period)
+///
+/// Returns `None` until _roc_period_ + 1 bars have been seen, and while the smoothed range
+/// _roc_period_ bars ago is zero.
+///
+/// # Parameters
+///
+/// * _ema_period_ - period of the range EMA (integer greater than 0)
+/// * _roc_period_ - lookback of the rate of change (integer greater than 0)
+#[doc(alias = "CV")]
+#[derive(Debug, Clone)]
+pub struct ChaikinVolatility {
+    ema: ExponentialMovingAverage,
+    roc: RateOfChange,
+}
+
+impl ChaikinVolatility {
+    pub fn new(ema_period: usize, roc_period: usize) -> Result<Self> {
+        Ok(Self {
+            ema: ExponentialMovingAverage::new(ema_period)?,
+            roc: RateOfChange::new(roc_period)?,
+        })
+    }
+}
+
+impl<T: High + Low> Next<&T> for ChaikinVolatility {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let range = Decimal::from_f64_retain(input.high() - input.low()).unwrap();
+        self.roc.next(self.ema.next(range))
+    }
+}
+
+impl Reset for ChaikinVolatility {
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.roc.reset();
+    }
+}
+
+impl Default for ChaikinVolatility {
+    fn default() -> Self {
+        Self::new(10, 10).unwrap()
+    }
+}
+
+impl fmt::Display for ChaikinVolatility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CV({}, {})", self.ema.period(), self.roc.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(high: f64, low: f64) -> DataItem {
+        DataItem::builder()
+            .high(high)
+            .low(low)
+            .close(low)
+            .open(low)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(ChaikinVolatility::new(0, 10).is_err());
+        assert!(ChaikinVolatility::new(10, 0).is_err());
+        assert!(ChaikinVolatility::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut cv = ChaikinVolatility::new(3, 1).unwrap();
+        // ranges 2, 4: EMA 2, then 0.5 * 4 + 0.5 * 2 = 3
+        assert_eq!(cv.next(&bar(12.0, 10.0)), None);
+        assert_eq!(cv.next(&bar(14.0, 10.0)), Some(dec!(50)));
+        // EMA 0.5 * 3 + 0.5 * 3 = 3
+        assert_eq!(cv.next(&bar(13.0, 10.0)), Some(dec!(0)));
+    }
+
+    #[test]
+    fn test_spike() {
+        let mut cv = ChaikinVolatility::default();
+        for _ in 0..30 {
+            assert_eq!(cv.next(&bar(101.0, 100.0)).unwrap_or_default(), dec!(0));
+        }
+
+        let spike: Vec<Decimal> = (0..5).map(|_| cv.next(&bar(105.0, 98.0)).unwrap()).collect();
+        assert!(spike.iter().all(|out| *out > dec!(0)));
+        assert!(spike.windows(2).all(|w| w[1] > w[0]));
+
+        // back to quiet bars: the smoothed range shrinks below its level 10 bars ago
+        for _ in 0..15 {
+            cv.next(&bar(101.0, 100.0));
+        }
+        assert!(cv.next(&bar(101.0, 100.0)).unwrap() < dec!(0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cv = ChaikinVolatility::new(2, 1).unwrap();
+        cv.next(&bar(12.0, 10.0));
+        assert!(cv.next(&bar(13.0, 10.0)).is_some());
+
+        cv.reset();
+        assert_eq!(cv.next(&bar(12.0, 10.0)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        ChaikinVolatility::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", ChaikinVolatility::default()), "CV(10, 10)");
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::errors::Result;
+use ta::{Close, Next, Reset};
+
+use crate::moving_average::{MovingAverage, MovingAverageKind};
+
+/// A smoothing stage of a [Chain](struct.Chain.html), holding its period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Sma(usize),
+    Ema(usize),
+    Wma(usize),
+    /// Wilder's smoothing.
+    Rma(usize),
+}
+
+impl Stage {
+    fn build(self) -> Result<MovingAverage> {
+        match self {
+            Stage::Sma(period) => MovingAverage::new(MovingAverageKind::Simple, period),
+            Stage::Ema(period) => MovingAverage::new(MovingAverageKind::Exponential, period),
+            Stage::Wma(period) => MovingAverage::new(MovingAverageKind::Weighted, period),
+            Stage::Rma(period) => MovingAverage::new(MovingAverageKind::Wilder, period),
+        }
+    }
+}
+
+/// Moving averages applied one after another.
+///
+/// Each stage smooths the output of the previous one, which covers composites such as
+/// triangular (SMA of SMA) or triple-smoothed averages without writing a new indicator.
+/// A stage only receives input once the stage before it has warmed up, so the chain
+/// returns `None` until every stage is primed. An empty chain passes its input through.
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use technical_analysis::chain::Chain;
+/// use technical_analysis::chain::Stage::Sma;
+/// use ta::Next;
+///
+/// // triangular moving average
+/// let mut chain = Chain::new().then(Sma(2)).unwrap().then(Sma(2)).unwrap();
+/// assert_eq!(chain.next(dec!(2)), None);
+/// assert_eq!(chain.next(dec!(4)), None);
+/// assert_eq!(chain.next(dec!(6)), Some(dec!(4)));
+/// assert_eq!(format!("{}", chain), "CHAIN(SMA(2), SMA(2))");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Chain {
+    stages: Vec<MovingAverage>,
+}
+
+impl Chain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a stage fed by the current last stage. Fails if the stage's period is 0.
+    pub fn then(mut self, stage: Stage) -> Result<Self> {
+        self.stages.push(stage.build()?);
+        Ok(self)
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+}
+
+impl Next<Decimal> for Chain {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        self.stages
+            .iter_mut()
+            .try_fold(input, |value, stage| stage.next(value))
+    }
+}
+
+impl<T: Close> Next<&T> for Chain {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for Chain {
+    fn reset(&mut self) {
+        self.stages.iter_mut().for_each(|stage| stage.reset());
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let stages: Vec<String> = self.stages.iter().map(|stage| stage.to_string()).collect();
+        write!(f, "CHAIN({})", stages.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use crate::ema::ExponentialMovingAverage;
+    use crate::simple_moving_average::SimpleMovingAverage;
+    use super::*;
+    use super::Stage::*;
+
+    #[test]
+    fn test_then() {
+        assert!(Chain::new().then(Sma(0)).is_err());
+        assert!(Chain::new().then(Sma(5)).unwrap().then(Ema(0)).is_err());
+        assert_eq!(Chain::new().then(Sma(5)).unwrap().then(Ema(3)).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut chain = Chain::new();
+        assert!(chain.is_empty());
+        assert_eq!(chain.next(dec!(7)), Some(dec!(7)));
+    }
+
+    #[test]
+    fn test_sma_then_ema() {
+        let mut chain = Chain::new().then(Sma(5)).unwrap().then(Ema(3)).unwrap();
+        let mut sma = SimpleMovingAverage::new(5).unwrap();
+        let mut ema = ExponentialMovingAverage::warmup(3).unwrap();
+
+        for i in 0..30 {
+            let input = Decimal::from((i * 37) % 11) + dec!(0.5);
+            let average = sma.next(input);
+            let expected = if i >= 4 { ema.next(average) } else { None };
+            assert_eq!(chain.next(input), expected, "bar {}", i);
+            // 5 inputs prime the SMA, 2 more SMA values prime the EMA
+            assert_eq!(expected.is_some(), i >= 6);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut chain = Chain::new().then(Sma(2)).unwrap().then(Rma(2)).unwrap();
+        for value in [dec!(1), dec!(2), dec!(3)] {
+            chain.next(value);
+        }
+        assert!(chain.next(dec!(4)).is_some());
+
+        chain.reset();
+        assert_eq!(chain.next(dec!(4)), None);
+        assert_eq!(chain.next(dec!(4)), None);
+        assert_eq!(chain.next(dec!(4)), Some(dec!(4)));
+    }
+
+    #[test]
+    fn test_display() {
+        let chain = Chain::new().then(Sma(5)).unwrap().then(Wma(3)).unwrap();
+        assert_eq!(format!("{}", chain), "CHAIN(SMA(5), WMA(3))");
+    }
+}
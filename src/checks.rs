@@ -0,0 +1,55 @@
+//! Output sanity checks behind the `debug-checks` feature.
+//!
+//! `Decimal` has no NaN or infinity and panics on overflow, so a broken formula shows up as
+//! a value outside the range the indicator can produce rather than as a non-finite one,
+//! e.g. an RSI of 180 after dividing by a corrupted average. With the feature enabled these
+//! helpers panic on such values, naming the indicator. Without it they compile to nothing.
+
+use rust_decimal::Decimal;
+
+/// Panics under `debug-checks` if `value` is outside `[min, max]`.
+#[inline(always)]
+pub(crate) fn check_range(indicator: &str, value: Decimal, min: Decimal, max: Decimal) {
+    if cfg!(feature = "debug-checks") {
+        assert!(
+            value >= min && value <= max,
+            "{} output {} is outside [{}, {}]",
+            indicator, value, min, max
+        );
+    }
+}
+
+/// Panics under `debug-checks` if `value` is negative.
+#[inline(always)]
+pub(crate) fn check_non_negative(indicator: &str, value: Decimal) {
+    if cfg!(feature = "debug-checks") {
+        assert!(value >= Decimal::ZERO, "{} output {} is negative", indicator, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    #[test]
+    fn test_in_range() {
+        check_range("RSI", dec!(0), dec!(0), dec!(100));
+        check_range("RSI", dec!(100), dec!(0), dec!(100));
+        check_non_negative("ATR", dec!(0));
+    }
+
+    #[test]
+    #[cfg(feature = "debug-checks")]
+    #[should_panic(expected = "RSI output 100.1 is outside [0, 100]")]
+    fn test_out_of_range() {
+        check_range("RSI", dec!(100.1), dec!(0), dec!(100));
+    }
+
+    #[test]
+    #[cfg(feature = "debug-checks")]
+    #[should_panic(expected = "ATR output -1 is negative")]
+    fn test_negative() {
+        check_non_negative("ATR", dec!(-1));
+    }
+}
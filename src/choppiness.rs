@@ -0,0 +1,196 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, High, Low, Next, Period, Reset};
+
+use crate::math::log10;
+use crate::true_range::TrueRange;
+
+/// Choppiness Index (CHOP).
+///
+/// Measures whether the market is trending or moving sideways. Readings near 100 mean
+/// price is chopping back and forth within its range, readings near 0 mean it is covering
+/// its range in a straight line.
+///
+/// # Formula
+///
+/// CHOP = 100 * log<sub>10</sub>(ΣTR / (HH - LL)) / log<sub>10</sub>(period)
+///
+/// Where:
+///
+/// * _ΣTR_ - sum of the [true range](struct.TrueRange.html) over the last _period_ bars
+/// * _HH_, _LL_ - highest high and lowest low over the same bars
+///
+/// The logarithms come from the [math](../math/index.html) helpers and share their
+/// precision. A window with no range yields `None`.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 1)
+#[doc(alias = "CHOP")]
+#[derive(Debug, Clone)]
+pub struct ChoppinessIndex {
+    period: usize,
+    true_range: TrueRange,
+    window: VecDeque<(Decimal, Decimal, Decimal)>,
+}
+
+impl ChoppinessIndex {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 | 1 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                true_range: TrueRange::new(),
+                window: VecDeque::with_capacity(period),
+            }),
+        }
+    }
+}
+
+impl Period for ChoppinessIndex {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for ChoppinessIndex {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let tr = self.true_range.next(input);
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back((
+            tr,
+            Decimal::from_f64(input.high()).unwrap(),
+            Decimal::from_f64(input.low()).unwrap(),
+        ));
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let (sum_tr, highest, lowest) = self.window.iter().fold(
+            (Decimal::ZERO, Decimal::MIN, Decimal::MAX),
+            |(sum, hh, ll), (tr, high, low)| (sum + tr, hh.max(*high), ll.min(*low)),
+        );
+        let range = highest - lowest;
+        if range.is_zero() {
+            return None;
+        }
+
+        Some(dec!(100) * log10(sum_tr / range) / log10(Decimal::from(self.period)))
+    }
+}
+
+impl Reset for ChoppinessIndex {
+    fn reset(&mut self) {
+        self.true_range.reset();
+        self.window.clear();
+    }
+}
+
+impl Default for ChoppinessIndex {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for ChoppinessIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CHOP({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder()
+            .high(high)
+            .low(low)
+            .close(close)
+            .open(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(ChoppinessIndex::new(0).is_err());
+        assert!(ChoppinessIndex::new(1).is_err());
+        assert!(ChoppinessIndex::new(2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut chop = ChoppinessIndex::new(3).unwrap();
+
+        assert_eq!(chop.next(&bar(10.0, 9.0, 9.5)), None);
+        assert_eq!(chop.next(&bar(10.4, 9.8, 10.2)), None);
+        // tr = 1.0 + 0.9 + 1.3 = 3.2, range = 10.7 - 9.0 = 1.7
+        let expected = 100.0 * (3.2f64 / 1.7).log10() / 3f64.log10();
+        let value = chop.next(&bar(10.7, 9.4, 9.7)).unwrap();
+        assert_eq!(value.round_dp(8), Decimal::from_f64(expected).unwrap().round_dp(8));
+    }
+
+    #[test]
+    fn test_trend_is_low() {
+        let mut chop = ChoppinessIndex::new(14).unwrap();
+        let mut value = None;
+        for i in 0..20 {
+            let i = i as f64;
+            value = chop.next(&bar(10.0 + i, 9.0 + i, 9.5 + i));
+        }
+        assert!(value.unwrap() < dec!(38.2));
+    }
+
+    #[test]
+    fn test_range_is_high() {
+        let mut chop = ChoppinessIndex::new(14).unwrap();
+        let mut value = None;
+        for i in 0..20 {
+            value = if i % 2 == 0 {
+                chop.next(&bar(11.0, 9.0, 10.5))
+            } else {
+                chop.next(&bar(11.0, 9.0, 9.5))
+            };
+        }
+        assert!(value.unwrap() > dec!(61.8));
+    }
+
+    #[test]
+    fn test_flat_window() {
+        let mut chop = ChoppinessIndex::new(2).unwrap();
+        chop.next(&bar(10.0, 10.0, 10.0));
+        assert_eq!(chop.next(&bar(10.0, 10.0, 10.0)), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut chop = ChoppinessIndex::new(2).unwrap();
+        chop.next(&bar(10.0, 9.0, 9.5));
+        assert!(chop.next(&bar(10.4, 9.8, 10.2)).is_some());
+
+        chop.reset();
+        assert_eq!(chop.next(&bar(10.0, 9.0, 9.5)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        ChoppinessIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let chop = ChoppinessIndex::new(14).unwrap();
+        assert_eq!(format!("{}", chop), "CHOP(14)");
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Open, Reset, Volume};
+
+use crate::volume_policy::VolumePolicy;
+
+/// Cumulative volume delta, estimated from bar direction.
+///
+/// Without trade-by-trade data the buy and sell volume of a bar are approximated from its
+/// direction: all of an up bar's volume counts as buying, all of a down bar's as selling,
+/// and a doji's volume is split evenly. The delta is buy volume minus sell volume, and the
+/// output is its running total since the last reset.
+///
+/// # Formula
+///
+/// delta = volume if close > open, -volume if close < open, 0 otherwise
+///
+/// CumulativeDelta = Σdelta
+///
+/// Bars without volume are counted with a volume of 0 unless another `VolumePolicy` is set.
+#[derive(Debug, Clone, Default)]
+pub struct CumulativeDelta {
+    delta: Decimal,
+    total: Decimal,
+    volume_policy: VolumePolicy,
+}
+
+impl CumulativeDelta {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Like `new`, with `volume_policy` deciding how bars with zero or missing volume are
+    /// handled.
+    pub fn with_volume_policy(volume_policy: VolumePolicy) -> Self {
+        Self {
+            volume_policy,
+            ..Self::default()
+        }
+    }
+
+    /// `next` that returns an error instead of panicking on a bar rejected by
+    /// `VolumePolicy::Error`, with a volume beyond `Decimal`'s range, or that would take
+    /// the total past it.
+    pub fn try_next<T: Open + Close + Volume>(&mut self, input: &T) -> Result<Decimal> {
+        let Some(volume) = self.volume_policy.volume(input.volume())? else {
+            return Ok(self.total);
+        };
+        let (buy, sell) = if input.close() > input.open() {
+            (volume, dec!(0))
+        } else if input.close() < input.open() {
+            (dec!(0), volume)
+        } else {
+            (volume / dec!(2), volume / dec!(2))
+        };
+        let delta = buy - sell;
+        self.total = self.total.checked_add(delta).ok_or(TaError::InvalidParameter)?;
+        self.delta = delta;
+        Ok(self.total)
+    }
+
+    /// Delta of the last bar, 0 before the first.
+    pub fn delta(&self) -> Decimal {
+        self.delta
+    }
+}
+
+impl<T: Open + Close + Volume> Next<&T> for CumulativeDelta {
+    type Output = Decimal;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.try_next(input).expect("invalid volume")
+    }
+}
+
+impl Reset for CumulativeDelta {
+    fn reset(&mut self) {
+        self.delta = dec!(0);
+        self.total = dec!(0);
+    }
+}
+
+impl fmt::Display for CumulativeDelta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CUM_DELTA")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(open: f64, close: f64, volume: f64) -> DataItem {
+        DataItem::builder()
+            .open(open)
+            .high(open.max(close))
+            .low(open.min(close))
+            .close(close)
+            .volume(volume)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_up_bar() {
+        let mut cd = CumulativeDelta::new();
+        assert_eq!(cd.next(&bar(10.0, 11.0, 300.0)), dec!(300));
+        assert_eq!(cd.delta(), dec!(300));
+    }
+
+    #[test]
+    fn test_down_bar() {
+        let mut cd = CumulativeDelta::new();
+        assert_eq!(cd.next(&bar(11.0, 10.0, 200.0)), dec!(-200));
+        assert_eq!(cd.delta(), dec!(-200));
+    }
+
+    #[test]
+    fn test_doji() {
+        let mut cd = CumulativeDelta::new();
+        cd.next(&bar(10.0, 11.0, 100.0));
+        assert_eq!(cd.next(&bar(11.0, 11.0, 500.0)), dec!(100));
+        assert_eq!(cd.delta(), dec!(0));
+    }
+
+    #[test]
+    fn test_accumulates() {
+        let mut cd = CumulativeDelta::new();
+        let bars = [bar(10.0, 11.0, 300.0), bar(11.0, 10.5, 120.0), bar(10.5, 10.5, 50.0), bar(10.5, 12.0, 80.0)];
+        let totals: Vec<Decimal> = bars.iter().map(|bar| cd.next(bar)).collect();
+        assert_eq!(totals, [dec!(300), dec!(180), dec!(180), dec!(260)]);
+        assert_eq!(cd.delta(), dec!(80));
+    }
+
+    #[test]
+    fn test_volume_policy() {
+        let run = |policy| {
+            let mut cd = CumulativeDelta::with_volume_policy(policy);
+            cd.next(&bar(10.0, 11.0, 300.0));
+            (cd.try_next(&bar(11.0, 10.0, 0.0)), cd.delta())
+        };
+        assert_eq!(run(VolumePolicy::TreatAsZero), (Ok(dec!(300)), dec!(0)));
+        assert_eq!(run(VolumePolicy::Skip), (Ok(dec!(300)), dec!(300)));
+        assert_eq!(run(VolumePolicy::Error), (Err(TaError::DataItemInvalid), dec!(300)));
+    }
+
+    #[test]
+    fn test_large_volume() {
+        let mut cd = CumulativeDelta::new();
+        assert_eq!(cd.try_next(&bar(10.0, 11.0, 1e30)), Err(TaError::InvalidParameter));
+        assert!(cd.try_next(&bar(10.0, 11.0, 5e28)).is_ok());
+        // a second 5e28 would take the total past Decimal::MAX
+        assert_eq!(cd.try_next(&bar(10.0, 11.0, 5e28)), Err(TaError::InvalidParameter));
+        assert_eq!(cd.next(&bar(11.0, 10.0, 5e28)), dec!(0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cd = CumulativeDelta::new();
+        cd.next(&bar(10.0, 11.0, 300.0));
+
+        cd.reset();
+        assert_eq!(cd.delta(), dec!(0));
+        assert_eq!(cd.next(&bar(11.0, 10.0, 40.0)), dec!(-40));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", CumulativeDelta::new()), "CUM_DELTA");
+    }
+}
@@ -1,40 +1,145 @@
+use std::collections::VecDeque;
 use std::fmt;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
 use rust_decimal_macros::dec;
 use ta::errors::{Result, TaError};
-use ta::{DataItem, High, Low, Next, Period, Reset};
+use ta::{Close, DataItem, High, Low, Next, Period, Reset};
 
 use crate::average_true_range::AverageTrueRange;
-use crate::model::ADX;
-use crate::rolling_moving_average::RollingMovingAverage;
+use crate::checks::check_range;
+use crate::model::{Bias, DmiComponents, ADX};
+use crate::moving_average::{MovingAverage, MovingAverageKind};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub struct DirectionalMovementIndex {
     period: usize,
-    dmi_plus: RollingMovingAverage,
-    dmi_minus: RollingMovingAverage,
-    adx: RollingMovingAverage,
+    dmi_plus: MovingAverage,
+    dmi_minus: MovingAverage,
+    adx: MovingAverage,
     atr: AverageTrueRange,
-    current_di: DataItem,
-    is_new: bool,
+    prev_low_high: Option<(Decimal, Decimal)>,
+    last: ADX,
+    adx_history: VecDeque<Decimal>,
+    #[serde(default)]
+    components: DmiComponents,
 }
 
 impl DirectionalMovementIndex {
     pub fn new(period: usize) -> Result<Self> {
+        Self::with_smoothing(period, MovingAverageKind::Wilder)
+    }
+
+    /// DMI whose +DI, -DI and ADX are smoothed with `smoothing` instead of Wilder's
+    /// average. The ATR the DIs are normalized by keeps Wilder's smoothing.
+    pub fn with_smoothing(period: usize, smoothing: MovingAverageKind) -> Result<Self> {
         match period {
             0 => Err(TaError::InvalidParameter),
             _ => Ok(Self {
                 period,
-                dmi_plus: RollingMovingAverage::new(period)?,
-                dmi_minus: RollingMovingAverage::new(period)?,
-                adx: RollingMovingAverage::new(period)?,
+                dmi_plus: MovingAverage::new(smoothing, period)?,
+                dmi_minus: MovingAverage::new(smoothing, period)?,
+                adx: MovingAverage::new(smoothing, period)?,
                 atr: AverageTrueRange::new(period)?,
-                current_di: empty_di()?,
-                is_new: true,
+                prev_low_high: None,
+                last: empty_adx(),
+                adx_history: VecDeque::with_capacity(period + 1),
+                components: DmiComponents::default(),
             }),
         }
     }
+
+    /// Whether the last ADX reading is above `threshold`. `None` until ADX is available.
+    pub fn is_trending(&self, threshold: Decimal) -> Option<bool> {
+        self.last.adx_opt.map(|adx| adx > threshold)
+    }
+
+    /// Intermediate values behind the last output, for inspecting how it was reached.
+    pub fn last_components(&self) -> DmiComponents {
+        self.components.clone()
+    }
+
+    /// Directional bias of the last bar, from the sign of `+DI - -DI`.
+    pub fn bias(&self) -> Option<Bias> {
+        match (self.last.di_plus_opt, self.last.di_minus_opt) {
+            (Some(di_plus), Some(di_minus)) if di_plus > di_minus => Some(Bias::Bullish),
+            (Some(di_plus), Some(di_minus)) if di_plus < di_minus => Some(Bias::Bearish),
+            (Some(_), Some(_)) => Some(Bias::Neutral),
+            _ => None,
+        }
+    }
+
+    /// Direction and strength of the last bar in one score from -100 to 100: the ADX,
+    /// signed by whether +DI is above (positive) or below (negative) -DI. 0 when the DIs
+    /// are equal. `None` until ADX is available.
+    pub fn trend_strength_normalized(&self) -> Option<Decimal> {
+        let adx = self.last.adx_opt?;
+        let score = match self.bias()? {
+            Bias::Bullish => adx,
+            Bias::Bearish => -adx,
+            Bias::Neutral => dec!(0),
+        };
+        Some(score)
+    }
+
+    /// `next` for a bar already given as decimals, skipping the conversions from `f64`.
+    pub fn next_hlc(&mut self, high: Decimal, low: Decimal, close: Decimal) -> ADX {
+        // The first bar only seeds the previous high/low and close: there is no move to
+        // measure yet. Leaving its plain high-low range out of the ATR as well keeps both
+        // averages over the same bars, so the DIs and the ATR become ready together.
+        let atr = match self.prev_low_high {
+            None => {
+                self.atr.seed(close);
+                None
+            }
+            Some(_) => self.atr.next_hlc(high, low, close),
+        };
+        let moves = self
+            .prev_low_high
+            .map(|(prev_low, prev_high)| directional_moves(high, low, &prev_low, &prev_high));
+        let mut adx = match moves {
+            Some((_, _, dm_plus, dm_minus)) => adx_from_moves(
+                dm_plus,
+                dm_minus,
+                atr,
+                &mut self.dmi_plus,
+                &mut self.dmi_minus,
+                &mut self.adx,
+            ),
+            None => empty_adx(),
+        };
+        self.prev_low_high = Some((low, high));
+
+        self.components = DmiComponents {
+            up_move: moves.map(|moves| moves.0),
+            down_move: moves.map(|moves| moves.1),
+            dm_plus: moves.map(|moves| moves.2),
+            dm_minus: moves.map(|moves| moves.3),
+            atr,
+            di_plus: adx.di_plus_opt,
+            di_minus: adx.di_minus_opt,
+            dx: match (adx.di_plus_opt, adx.di_minus_opt) {
+                (Some(di_plus), Some(di_minus)) if !(di_plus + di_minus).is_zero() => {
+                    Some(((di_plus - di_minus) / (di_plus + di_minus)).abs() * dec!(100))
+                }
+                _ => None,
+            },
+        };
+
+        if let Some(adx_now) = adx.adx_opt {
+            check_range("ADX", adx_now, dec!(0), dec!(100));
+            if self.adx_history.len() > self.period {
+                self.adx_history.pop_front();
+            }
+            self.adx_history.push_back(adx_now);
+            if self.adx_history.len() > self.period {
+                adx.adxr_opt = self.adx_history.front().map(|adx_then| (adx_now + adx_then) / dec!(2));
+            }
+        }
+
+        self.last = adx.clone();
+        adx
+    }
 }
 
 impl Period for DirectionalMovementIndex {
@@ -47,23 +152,11 @@ impl Next<&DataItem> for DirectionalMovementIndex {
     type Output = ADX;
 
     fn next(&mut self, di: &DataItem) -> Self::Output {
-        let adx = get_adx_indicator(
-            &di,
-            self.atr.next(di),
-            &Decimal::from_f64(self.current_di.low()).unwrap(),
-            &Decimal::from_f64(self.current_di.high()).unwrap(),
-            &mut self.dmi_plus,
-            &mut self.dmi_minus,
-            &mut self.adx,
-            self.is_new,
-        );
-
-        if self.is_new {
-            self.is_new = false;
-        }
-
-        self.current_di = di.clone();
-        adx
+        self.next_hlc(
+            Decimal::from_f64(di.high()).unwrap(),
+            Decimal::from_f64(di.low()).unwrap(),
+            Decimal::from_f64(di.close()).unwrap(),
+        )
     }
 }
 
@@ -72,7 +165,11 @@ impl Reset for DirectionalMovementIndex {
         self.adx.reset();
         self.dmi_plus.reset();
         self.dmi_minus.reset();
-        self.is_new = true;
+        self.atr.reset();
+        self.prev_low_high = None;
+        self.last = empty_adx();
+        self.adx_history.clear();
+        self.components = DmiComponents::default();
     }
 }
 
@@ -88,75 +185,153 @@ impl fmt::Display for DirectionalMovementIndex {
     }
 }
 
-fn empty_di() -> Result<DataItem> {
-    DataItem::builder()
-        .close(0.0)
-        .high(0.0)
-        .low(0.0)
-        .open(0.0)
-        .volume(0.0)
-        .build()
+/// Directional spread, `+DI - -DI`.
+///
+/// A single-line oscillator over [DirectionalMovementIndex](struct.DirectionalMovementIndex.html):
+/// positive while upward movement dominates, negative while downward movement does.
+/// Returns `None` until both DIs are available.
+///
+/// # Parameters
+///
+/// * _period_ - DMI period (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct DiSpread {
+    dmi: DirectionalMovementIndex,
+}
+
+impl DiSpread {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            dmi: DirectionalMovementIndex::new(period)?,
+        })
+    }
+}
+
+impl Period for DiSpread {
+    fn period(&self) -> usize {
+        self.dmi.period()
+    }
+}
+
+impl Next<&DataItem> for DiSpread {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &DataItem) -> Self::Output {
+        let adx = self.dmi.next(input);
+        Some(adx.di_plus_opt? - adx.di_minus_opt?)
+    }
+}
+
+impl Reset for DiSpread {
+    fn reset(&mut self) {
+        self.dmi.reset();
+    }
+}
+
+impl Default for DiSpread {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for DiSpread {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DI_SPREAD({})", self.dmi.period())
+    }
 }
 
-pub fn get_adx_indicator(
+fn empty_adx() -> ADX {
+    ADX {
+        adx_opt: None,
+        di_plus_opt: None,
+        di_minus_opt: None,
+        adxr_opt: None,
+    }
+}
+
+/// Up move, down move, +DM and -DM of a bar against the previous high and low.
+fn directional_moves(
+    high: Decimal,
+    low: Decimal,
+    prev_low: &Decimal,
+    prev_high: &Decimal,
+) -> (Decimal, Decimal, Decimal, Decimal) {
+    let up_move = high - prev_high;
+
+    let down_move = prev_low - low;
+
+    let (dm_plus, dm_minus) = if up_move > down_move && up_move > dec!(0) {
+        (up_move, dec!(0))
+    } else if down_move > up_move && down_move > dec!(0) {
+        (dec!(0), down_move)
+    } else {
+        (dec!(0), dec!(0))
+    };
+    (up_move, down_move, dm_plus, dm_minus)
+}
+
+pub fn get_adx_indicator<M: Next<Decimal, Output = Option<Decimal>>>(
     data_item: &DataItem,
     atr_opt: Option<Decimal>,
     prev_low: &Decimal,
     prev_high: &Decimal,
-    ema_di_plus: &mut RollingMovingAverage,
-    ema_di_minus: &mut RollingMovingAverage,
-    ema_di_adx: &mut RollingMovingAverage,
+    ema_di_plus: &mut M,
+    ema_di_minus: &mut M,
+    ema_di_adx: &mut M,
     is_new: bool,
 ) -> ADX {
     if is_new {
-        ADX {
-            adx_opt: None,
-            di_plus_opt: None,
-            di_minus_opt: None,
-        }
+        empty_adx()
     } else {
-        let up_move = Decimal::from_f64(data_item.high()).unwrap() - prev_high;
-
-        let down_move = prev_low - Decimal::from_f64(data_item.low()).unwrap();
-
-        let (dm_plus, dm_minus) = if up_move > down_move && up_move > dec!(0) {
-            (up_move, dec!(0))
-        } else if down_move > up_move && down_move > dec!(0) {
-            (dec!(0), down_move)
-        } else {
-            (dec!(0), dec!(0))
-        };
-
-        let atr_output = atr_opt.unwrap_or_else(|| dec!(1));
-
-        let di_plus_opt = ema_di_plus.next(dm_plus).map(|f| (f / atr_output) * dec!(100));
-        let di_minus_opt = ema_di_minus
-            .next(dm_minus)
-            .map(|f| (f / atr_output) * dec!(100));
+        let (_, _, dm_plus, dm_minus) = directional_moves(
+            Decimal::from_f64(data_item.high()).unwrap(),
+            Decimal::from_f64(data_item.low()).unwrap(),
+            prev_low,
+            prev_high,
+        );
+        adx_from_moves(dm_plus, dm_minus, atr_opt, ema_di_plus, ema_di_minus, ema_di_adx)
+    }
+}
 
-        let adx_temp_opt = match (di_plus_opt, di_minus_opt) {
-            (Some(di_plus), Some(di_minus)) => {
-                Some(((di_plus - di_minus) / (di_plus + di_minus)).abs())
-            }
-            _ => None,
-        };
+fn adx_from_moves<M: Next<Decimal, Output = Option<Decimal>>>(
+    dm_plus: Decimal,
+    dm_minus: Decimal,
+    atr_opt: Option<Decimal>,
+    ema_di_plus: &mut M,
+    ema_di_minus: &mut M,
+    ema_di_adx: &mut M,
+) -> ADX {
+    let atr_output = atr_opt.unwrap_or_else(|| dec!(1));
 
-        // TODO: No unwrap
-        let adx_opt = match adx_temp_opt {
-            Some(adx_temp) => ema_di_adx.next(adx_temp).map(|adx| adx * dec!(100)),
-            _ => None,
-        };
+    let di_plus_opt = ema_di_plus.next(dm_plus).map(|f| (f / atr_output) * dec!(100));
+    let di_minus_opt = ema_di_minus
+        .next(dm_minus)
+        .map(|f| (f / atr_output) * dec!(100));
 
-        ADX {
-            adx_opt,
-            di_plus_opt,
-            di_minus_opt,
+    let adx_temp_opt = match (di_plus_opt, di_minus_opt) {
+        (Some(di_plus), Some(di_minus)) if !(di_plus + di_minus).is_zero() => {
+            Some(((di_plus - di_minus) / (di_plus + di_minus)).abs())
         }
+        _ => None,
+    };
+
+    // TODO: No unwrap
+    let adx_opt = match adx_temp_opt {
+        Some(adx_temp) => ema_di_adx.next(adx_temp).map(|adx| adx * dec!(100)),
+        _ => None,
+    };
+
+    ADX {
+        adx_opt,
+        di_plus_opt,
+        di_minus_opt,
+        adxr_opt: None,
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::test_util::{self, assert_full_reset};
     use super::*;
 
     #[test]
@@ -245,6 +420,337 @@ mod tests {
         // assert_eq!(ema.next(6.25), 4.25);
     }
 
+    fn bar(high: f64, low: f64) -> DataItem {
+        test_util::bar(high, low, (high + low) / 2.0, 1.0)
+    }
+
+    #[test]
+    #[cfg(feature = "debug-checks")]
+    #[should_panic(expected = "ADX output")]
+    fn test_debug_checks() {
+        let mut dmi = DirectionalMovementIndex::new(2).unwrap();
+        let mut high = 10.0;
+        while dmi.next(&bar(high, high - 1.0)).adx_opt.is_none() {
+            high += 1.0;
+        }
+        // feed the ADX smoothing a value outside the 0..=1 ratio it normally averages
+        dmi.adx.next(dec!(-10));
+        dmi.next(&bar(high + 1.0, high));
+    }
+
+    #[test]
+    fn test_last_components() {
+        let mut dmi = DirectionalMovementIndex::new(1).unwrap();
+        assert_eq!(dmi.last_components(), DmiComponents::default());
+
+        dmi.next(&bar(10.0, 9.0));
+        let components = dmi.last_components();
+        assert_eq!(components.up_move, None);
+        assert_eq!(components.dm_plus, None);
+        assert_eq!(components.atr, None);
+        assert_eq!(components.dx, None);
+
+        dmi.next(&bar(12.0, 9.5));
+        assert_eq!(
+            dmi.last_components(),
+            DmiComponents {
+                up_move: Some(dec!(2)),
+                down_move: Some(dec!(-0.5)),
+                dm_plus: Some(dec!(2)),
+                dm_minus: Some(dec!(0)),
+                atr: Some(dec!(2.5)),
+                di_plus: Some(dec!(80)),
+                di_minus: Some(dec!(0)),
+                dx: Some(dec!(100)),
+            }
+        );
+
+        dmi.next(&bar(11.0, 8.0));
+        let components = dmi.last_components();
+        assert_eq!(components.up_move, Some(dec!(-1)));
+        assert_eq!(components.down_move, Some(dec!(1.5)));
+        assert_eq!(components.dm_minus, Some(dec!(1.5)));
+        assert_eq!(components.atr, Some(dec!(3)));
+        assert_eq!(components.di_minus, Some(dec!(50)));
+
+        dmi.reset();
+        assert_eq!(dmi.last_components(), DmiComponents::default());
+    }
+
+    #[test]
+    fn test_di_spread() {
+        assert!(DiSpread::new(0).is_err());
+
+        let mut spread = DiSpread::new(3).unwrap();
+        assert_eq!(spread.next(&bar(10.0, 9.0)), None);
+
+        let rising: Vec<Option<Decimal>> = (1..8)
+            .map(|i| spread.next(&bar(10.0 + i as f64, 9.0 + i as f64 * 0.75)))
+            .collect();
+        assert!(rising.iter().flatten().count() > 0);
+        assert!(rising.iter().flatten().all(|value| *value > dec!(0)));
+
+        spread.reset();
+        spread.next(&bar(30.0, 29.0));
+        let falling: Vec<Option<Decimal>> = (1..8)
+            .map(|i| spread.next(&bar(30.0 - i as f64 * 0.75, 29.0 - i as f64)))
+            .collect();
+        assert!(falling.iter().flatten().count() > 0);
+        assert!(falling.iter().flatten().all(|value| *value < dec!(0)));
+
+        let out = spread.dmi.last.clone();
+        assert_eq!(falling.last().unwrap(), &Some(out.di_plus_opt.unwrap() - out.di_minus_opt.unwrap()));
+        assert_eq!(format!("{}", spread), "DI_SPREAD(3)");
+    }
+
+    #[test]
+    fn test_first_move_uses_first_bar() {
+        let mut dmi = DirectionalMovementIndex::new(1).unwrap();
+
+        assert_eq!(dmi.next(&bar(10.0, 9.0)), empty_adx());
+
+        // +DM = 12 - 10 = 2, -DM = 0, TR = 12 - 9.5 = 2.5
+        let out = dmi.next(&bar(12.0, 9.5));
+        assert_eq!(out.di_plus_opt, Some(dec!(80)));
+        assert_eq!(out.di_minus_opt, Some(dec!(0)));
+
+        // -DM = 9.5 - 8 = 1.5 against the second bar, TR = 11 - 8 = 3
+        let out = dmi.next(&bar(11.0, 8.0));
+        assert_eq!(out.di_plus_opt, Some(dec!(0)));
+        assert_eq!(out.di_minus_opt, Some(dec!(50)));
+    }
+
+    #[test]
+    fn test_trend_predicates_before_output() {
+        let mut dmi = DirectionalMovementIndex::new(3).unwrap();
+        assert_eq!(dmi.is_trending(dec!(25)), None);
+        assert_eq!(dmi.bias(), None);
+
+        dmi.next(&bar(10.0, 9.0));
+        assert_eq!(dmi.is_trending(dec!(25)), None);
+        assert_eq!(dmi.bias(), None);
+    }
+
+    #[test]
+    fn test_trend_predicates_trending() {
+        let mut dmi = DirectionalMovementIndex::new(3).unwrap();
+        for i in 0..12 {
+            dmi.next(&bar(10.0 + i as f64, 9.0 + i as f64));
+        }
+        assert_eq!(dmi.is_trending(dec!(25)), Some(true));
+        assert_eq!(dmi.bias(), Some(Bias::Bullish));
+
+        let mut dmi = DirectionalMovementIndex::new(3).unwrap();
+        for i in 0..12 {
+            dmi.next(&bar(30.0 - i as f64, 29.0 - i as f64));
+        }
+        assert_eq!(dmi.is_trending(dec!(25)), Some(true));
+        assert_eq!(dmi.bias(), Some(Bias::Bearish));
+    }
+
+    #[test]
+    fn test_trend_strength_normalized() {
+        let mut dmi = DirectionalMovementIndex::new(3).unwrap();
+        dmi.next(&bar(10.0, 9.0));
+        assert_eq!(dmi.trend_strength_normalized(), None);
+        for i in 1..12 {
+            dmi.next(&bar(10.0 + i as f64, 9.0 + i as f64));
+        }
+        let up = dmi.trend_strength_normalized().unwrap();
+        assert!(up > dec!(50) && up <= dec!(100));
+        assert_eq!(up, dmi.last.adx_opt.unwrap());
+
+        let mut dmi = DirectionalMovementIndex::new(3).unwrap();
+        for i in 0..12 {
+            dmi.next(&bar(30.0 - i as f64, 29.0 - i as f64));
+        }
+        let down = dmi.trend_strength_normalized().unwrap();
+        assert!(down < dec!(-50) && down >= dec!(-100));
+
+        dmi.reset();
+        assert_eq!(dmi.trend_strength_normalized(), None);
+    }
+
+    #[test]
+    fn test_di_and_atr_ready_together() {
+        for period in 1..6 {
+            let mut dmi = DirectionalMovementIndex::new(period).unwrap();
+            for i in 0..12 {
+                let adx = dmi.next(&bar(10.0 + 2.0 * i as f64, 8.0 + (i % 2) as f64));
+                let atr = dmi.last_components().atr;
+                assert_eq!(adx.di_plus_opt.is_some(), atr.is_some(), "period {} bar {}", period, i);
+                assert_eq!(adx.di_minus_opt.is_some(), atr.is_some(), "period {} bar {}", period, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_atr_skips_first_bar() {
+        let mut dmi = DirectionalMovementIndex::new(2).unwrap();
+        // a wide first bar, then true ranges of 2 and 4 against the previous closes
+        dmi.next(&bar(20.0, 0.0));
+        dmi.next(&bar(11.0, 9.0));
+        let adx = dmi.next(&bar(14.0, 10.0));
+        assert_eq!(dmi.last_components().atr, Some(dec!(3)));
+        // +DM: 0 then 3, -DM: 0 then 0
+        assert_eq!(adx.di_plus_opt, Some(dec!(50)));
+        assert_eq!(adx.di_minus_opt, Some(dec!(0)));
+    }
+
+    #[test]
+    fn test_trend_predicates_ranging() {
+        let mut dmi = DirectionalMovementIndex::new(4).unwrap();
+        for i in 0..20 {
+            if i % 2 == 0 {
+                dmi.next(&bar(10.0, 9.0));
+            } else {
+                dmi.next(&bar(11.0, 10.0));
+            }
+        }
+        assert_eq!(dmi.is_trending(dec!(25)), Some(false));
+    }
+
+    #[test]
+    fn test_no_directional_movement() {
+        // the same range every bar: the ATR is positive but both DIs are 0
+        let mut dmi = DirectionalMovementIndex::new(3).unwrap();
+        for _ in 0..10 {
+            let adx = dmi.next(&bar(11.0, 9.0));
+            assert_eq!(adx.adx_opt, None);
+        }
+        assert_eq!(dmi.last_components().di_plus, Some(dec!(0)));
+        assert_eq!(dmi.last_components().dx, None);
+    }
+
+    #[test]
+    fn test_trend_predicates_reset() {
+        let mut dmi = DirectionalMovementIndex::new(3).unwrap();
+        for i in 0..12 {
+            dmi.next(&bar(10.0 + i as f64, 9.0 + i as f64));
+        }
+        dmi.reset();
+        assert_eq!(dmi.is_trending(dec!(25)), None);
+        assert_eq!(dmi.bias(), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let first = [bar(10.0, 9.0), bar(12.0, 9.5), bar(11.0, 8.0), bar(13.0, 10.0), bar(12.5, 11.0), bar(14.0, 12.0)];
+        let second = [bar(50.0, 48.0), bar(49.0, 46.0), bar(51.0, 47.5), bar(47.0, 45.0), bar(46.0, 43.0), bar(48.0, 44.0), bar(45.0, 42.0)];
+
+        let mut dmi = DirectionalMovementIndex::new(3).unwrap();
+        for di in first.iter() {
+            dmi.next(di);
+        }
+        dmi.reset();
+
+        let mut fresh = DirectionalMovementIndex::new(3).unwrap();
+        for di in second.iter() {
+            assert_eq!(dmi.next(di), fresh.next(di));
+        }
+    }
+
+    #[test]
+    fn test_full_reset() {
+        let bars = [
+            bar(10.0, 9.0), bar(12.0, 9.5), bar(11.0, 8.0), bar(13.0, 10.0), bar(12.5, 11.0),
+            bar(14.0, 12.0), bar(13.0, 11.5), bar(15.0, 13.0), bar(14.5, 12.0), bar(16.0, 14.0),
+        ];
+        let series: Vec<&DataItem> = bars.iter().collect();
+        assert_full_reset(DirectionalMovementIndex::new(3).unwrap(), &series);
+        assert_full_reset(DiSpread::new(3).unwrap(), &series);
+    }
+
+    #[test]
+    fn test_adxr() {
+        let bars = [
+            bar(10.0, 9.0), bar(12.0, 9.5), bar(11.0, 8.0), bar(13.0, 10.0), bar(12.5, 11.0),
+            bar(14.0, 12.0), bar(13.0, 11.5), bar(15.0, 13.0), bar(14.5, 12.0), bar(16.0, 14.0),
+            bar(15.0, 13.5), bar(17.0, 15.0),
+        ];
+        let mut dmi = DirectionalMovementIndex::new(3).unwrap();
+
+        let outputs: Vec<ADX> = bars.iter().map(|di| dmi.next(di)).collect();
+        let adx_values: Vec<(usize, Decimal)> = outputs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, out)| out.adx_opt.map(|adx| (i, adx)))
+            .collect();
+        assert!(adx_values.len() > 3);
+
+        for (n, (i, adx_now)) in adx_values.iter().enumerate() {
+            if n < 3 {
+                assert_eq!(outputs[*i].adxr_opt, None);
+            } else {
+                let adx_then = adx_values[n - 3].1;
+                assert_eq!(outputs[*i].adxr_opt, Some((*adx_now + adx_then) / dec!(2)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_with_smoothing() {
+        assert!(DirectionalMovementIndex::with_smoothing(0, MovingAverageKind::Exponential).is_err());
+
+        let bars = [
+            bar(10.0, 9.0), bar(12.0, 9.5), bar(11.0, 8.0), bar(13.0, 10.0), bar(12.5, 11.0),
+            bar(14.0, 12.0), bar(13.0, 11.5), bar(15.0, 13.0), bar(14.5, 12.0), bar(16.0, 14.0),
+        ];
+        let mut wilder = DirectionalMovementIndex::new(3).unwrap();
+        let mut default = DirectionalMovementIndex::with_smoothing(3, MovingAverageKind::Wilder).unwrap();
+        let mut ema = DirectionalMovementIndex::with_smoothing(3, MovingAverageKind::Exponential).unwrap();
+
+        let mut differs = false;
+        for di in bars.iter() {
+            let wilder_out = wilder.next(di);
+            let ema_out = ema.next(di);
+            assert_eq!(default.next(di), wilder_out);
+
+            // same warmup, different smoothing
+            assert_eq!(wilder_out.adx_opt.is_some(), ema_out.adx_opt.is_some());
+            differs |= wilder_out.adx_opt != ema_out.adx_opt;
+        }
+        assert!(differs);
+    }
+
+    #[test]
+    fn test_next_hlc() {
+        let bars = [
+            bar(10.0, 9.0), bar(12.0, 9.5), bar(11.0, 8.0), bar(13.0, 10.0), bar(12.5, 11.0),
+            bar(14.0, 12.0), bar(13.0, 11.5), bar(15.0, 13.0), bar(14.5, 12.0), bar(16.0, 14.0),
+        ];
+        let mut from_items = DirectionalMovementIndex::new(3).unwrap();
+        let mut from_decimals = DirectionalMovementIndex::new(3).unwrap();
+        for di in bars.iter() {
+            let expected = from_items.next(di);
+            let high = Decimal::from_f64(di.high()).unwrap();
+            let low = Decimal::from_f64(di.low()).unwrap();
+            let close = Decimal::from_f64(di.close()).unwrap();
+            assert_eq!(from_decimals.next_hlc(high, low, close), expected);
+        }
+        assert!(from_decimals.last.adxr_opt.is_some());
+        assert_eq!(from_decimals.last_components(), from_items.last_components());
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let bars = [
+            bar(10.0, 9.0), bar(12.0, 9.5), bar(11.0, 8.0), bar(13.0, 10.0), bar(12.5, 11.0),
+            bar(14.0, 12.0), bar(13.0, 11.5), bar(15.0, 13.0), bar(14.5, 12.0), bar(16.0, 14.0),
+        ];
+        let mut dmi = DirectionalMovementIndex::with_smoothing(3, MovingAverageKind::Exponential).unwrap();
+        for di in bars[..4].iter() {
+            dmi.next(di);
+        }
+
+        let json = serde_json::to_string(&dmi).unwrap();
+        let mut restored: DirectionalMovementIndex = serde_json::from_str(&json).unwrap();
+        for di in bars[4..].iter() {
+            assert_eq!(restored.next(di), dmi.next(di));
+        }
+        assert!(dmi.last.adxr_opt.is_some());
+    }
+
     // #[test]
     // fn test_reset() {
     //     let mut ema = DirectionalMovementIndex::new(5).unwrap();
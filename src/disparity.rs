@@ -0,0 +1,144 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::Result;
+use ta::{Close, Next, Period, Reset};
+
+use crate::simple_moving_average::SimpleMovingAverage;
+
+/// Disparity Index (DISP).
+///
+/// How far the close has stretched away from its moving average, as a percentage of the
+/// average. Positive when price is above the average, negative below it.
+///
+/// # Formula
+///
+/// DISP = 100 * (close - SMA(period)) / SMA(period)
+///
+/// Returns `None` until the average has _period_ values, and when the average is zero.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods of the SMA (integer greater than 0)
+#[doc(alias = "DISP")]
+#[derive(Debug, Clone)]
+pub struct DisparityIndex {
+    period: usize,
+    sma: SimpleMovingAverage,
+    count: usize,
+}
+
+impl DisparityIndex {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            period,
+            sma: SimpleMovingAverage::new(period)?,
+            count: 0,
+        })
+    }
+}
+
+impl Period for DisparityIndex {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for DisparityIndex {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let ma = self.sma.next(input);
+        if self.count < self.period {
+            self.count += 1;
+        }
+        if self.count < self.period || ma.is_zero() {
+            return None;
+        }
+        Some(dec!(100) * (input - ma) / ma)
+    }
+}
+
+impl<T: Close> Next<&T> for DisparityIndex {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for DisparityIndex {
+    fn reset(&mut self) {
+        self.sma.reset();
+        self.count = 0;
+    }
+}
+
+impl Default for DisparityIndex {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for DisparityIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DISP({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(close: f64) -> DataItem {
+        DataItem::builder().high(close).low(close).close(close).open(close).volume(0.0).build().unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(DisparityIndex::new(0).is_err());
+        assert!(DisparityIndex::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut disp = DisparityIndex::new(3).unwrap();
+
+        assert_eq!(disp.next(&bar(10.0)), None);
+        assert_eq!(disp.next(&bar(10.0)), None);
+        assert_eq!(disp.next(&bar(10.0)), Some(dec!(0)));
+        // sma = 32 / 3, stretched 4 / 3 above it
+        assert_eq!(disp.next(&bar(12.0)).unwrap().round_dp(8), dec!(12.5));
+        // sma = 10
+        assert_eq!(disp.next(&bar(8.0)), Some(dec!(-20)));
+    }
+
+    #[test]
+    fn test_zero_average() {
+        let mut disp = DisparityIndex::new(2).unwrap();
+        disp.next(dec!(1));
+        assert_eq!(disp.next(dec!(-1)), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut disp = DisparityIndex::new(2).unwrap();
+        disp.next(dec!(4));
+        assert!(disp.next(dec!(6)).is_some());
+
+        disp.reset();
+        assert_eq!(disp.next(dec!(99)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        DisparityIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let disp = DisparityIndex::new(14).unwrap();
+        assert_eq!(format!("{}", disp), "DISP(14)");
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::Result;
+use ta::{Close, High, Low, Next, Period, Reset};
+
+use crate::extremes::{Highest, Lowest};
+
+/// Distance of the close below the rolling high, in percent.
+///
+/// 0 when the close is at the _period_ high; the larger the value, the further the price
+/// has pulled back from its recent peak.
+///
+/// # Formula
+///
+/// Distance = (Highest(high, period) - close) / Highest(high, period) * 100
+///
+/// Returns `None` until _period_ bars have been seen, and while the rolling high is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct DistanceFromHigh {
+    highest: Highest,
+}
+
+impl DistanceFromHigh {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            highest: Highest::new(period)?,
+        })
+    }
+}
+
+impl Period for DistanceFromHigh {
+    fn period(&self) -> usize {
+        self.highest.period()
+    }
+}
+
+impl<T: Close + High + Low> Next<&T> for DistanceFromHigh {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let highest = self.highest.next(input)?;
+        if highest.is_zero() {
+            return None;
+        }
+        let close = Decimal::from_f64_retain(input.close()).unwrap();
+        Some((highest - close) / highest * dec!(100))
+    }
+}
+
+impl Reset for DistanceFromHigh {
+    fn reset(&mut self) {
+        self.highest.reset();
+    }
+}
+
+impl Default for DistanceFromHigh {
+    fn default() -> Self {
+        Self::new(52).unwrap()
+    }
+}
+
+impl fmt::Display for DistanceFromHigh {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DIST_HIGH({})", self.period())
+    }
+}
+
+/// Distance of the close above the rolling low, in percent.
+///
+/// 0 when the close is at the _period_ low; the larger the value, the further the price
+/// has run up from its recent trough.
+///
+/// # Formula
+///
+/// Distance = (close - Lowest(low, period)) / Lowest(low, period) * 100
+///
+/// Returns `None` until _period_ bars have been seen, and while the rolling low is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct DistanceFromLow {
+    lowest: Lowest,
+}
+
+impl DistanceFromLow {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            lowest: Lowest::new(period)?,
+        })
+    }
+}
+
+impl Period for DistanceFromLow {
+    fn period(&self) -> usize {
+        self.lowest.period()
+    }
+}
+
+impl<T: Close + High + Low> Next<&T> for DistanceFromLow {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let lowest = self.lowest.next(input)?;
+        if lowest.is_zero() {
+            return None;
+        }
+        let close = Decimal::from_f64_retain(input.close()).unwrap();
+        Some((close - lowest) / lowest * dec!(100))
+    }
+}
+
+impl Reset for DistanceFromLow {
+    fn reset(&mut self) {
+        self.lowest.reset();
+    }
+}
+
+impl Default for DistanceFromLow {
+    fn default() -> Self {
+        Self::new(52).unwrap()
+    }
+}
+
+impl fmt::Display for DistanceFromLow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DIST_LOW({})", self.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder()
+            .high(high)
+            .low(low)
+            .close(close)
+            .open(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(DistanceFromHigh::new(0).is_err());
+        assert!(DistanceFromHigh::new(1).is_ok());
+        assert!(DistanceFromLow::new(0).is_err());
+        assert!(DistanceFromLow::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_near_new_high() {
+        let mut from_high = DistanceFromHigh::new(3).unwrap();
+        let mut from_low = DistanceFromLow::new(3).unwrap();
+        let bars = [
+            bar(100.0, 90.0, 95.0),
+            bar(110.0, 95.0, 108.0),
+            bar(120.0, 105.0, 120.0),
+            bar(118.0, 108.0, 114.0),
+        ];
+        let highs: Vec<_> = bars.iter().map(|bar| from_high.next(bar)).collect();
+        let lows: Vec<_> = bars.iter().map(|bar| from_low.next(bar)).collect();
+
+        // closing at the new high
+        assert_eq!(highs[..3], [None, None, Some(dec!(0))]);
+        // 6 below the 120 high
+        assert_eq!(highs[3], Some(dec!(5)));
+        // 30 above the 90 low, then 19 above the 95 low
+        assert_eq!(lows[2].unwrap().round_dp(4), dec!(33.3333));
+        assert_eq!(lows[3].unwrap().round_dp(4), dec!(20));
+    }
+
+    #[test]
+    fn test_near_new_low() {
+        let mut from_high = DistanceFromHigh::new(2).unwrap();
+        let mut from_low = DistanceFromLow::new(2).unwrap();
+        let bars = [bar(50.0, 45.0, 46.0), bar(46.0, 40.0, 40.0), bar(42.0, 38.0, 39.9)];
+        let highs: Vec<_> = bars.iter().map(|bar| from_high.next(bar)).collect();
+        let lows: Vec<_> = bars.iter().map(|bar| from_low.next(bar)).collect();
+
+        assert_eq!(lows[1], Some(dec!(0)));
+        assert_eq!(lows[2].unwrap().round_dp(4), dec!(5));
+        assert_eq!(highs[1], Some(dec!(20)));
+        assert_eq!(highs[2].unwrap().round_dp(4), dec!(13.2609));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut from_high = DistanceFromHigh::new(2).unwrap();
+        from_high.next(&bar(10.0, 9.0, 9.5));
+        assert!(from_high.next(&bar(10.0, 9.0, 9.5)).is_some());
+        from_high.reset();
+        assert_eq!(from_high.next(&bar(10.0, 9.0, 9.5)), None);
+
+        let mut from_low = DistanceFromLow::new(1).unwrap();
+        from_low.next(&bar(10.0, 8.0, 9.0));
+        from_low.reset();
+        assert_eq!(from_low.next(&bar(10.0, 5.0, 6.0)), Some(dec!(20)));
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(DistanceFromHigh::default().period(), 52);
+        assert_eq!(DistanceFromLow::default().period(), 52);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", DistanceFromHigh::default()), "DIST_HIGH(52)");
+        assert_eq!(format!("{}", DistanceFromLow::new(20).unwrap()), "DIST_LOW(20)");
+    }
+}
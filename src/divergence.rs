@@ -0,0 +1,188 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::errors::{Result, TaError};
+use ta::{Close, DataItem, Next, Period, Reset};
+
+use crate::model::Divergence;
+use crate::pivot::pivot_points::{Pivot, PivotPoints, PivotType};
+use crate::pivot::scalar_pivots::ScalarPivots;
+use crate::rsi::RelativeStrengthIndex;
+
+/// RSI divergence detector.
+///
+/// Runs [PivotPoints](../struct.PivotPoints.html) on price and
+/// [ScalarPivots](../struct.ScalarPivots.html) on the RSI side by side. When both confirm
+/// a pivot of the same type on the same bar, the pair is compared with the previous pair
+/// of that type:
+///
+/// * lower price low, higher RSI low - `BullishRegular`
+/// * higher price high, lower RSI high - `BearishRegular`
+/// * higher price low, lower RSI low - `BullishHidden`
+/// * lower price high, higher RSI high - `BearishHidden`
+///
+/// Every other bar emits `Divergence::None`. Bars are only fed to the pivot detectors once
+/// the RSI is available, so both series line up. If a single bar confirms both a high and
+/// a low pair, the low's result is reported.
+///
+/// # Parameters
+///
+/// * _rsi_period_ - RSI period (integer greater than 0)
+/// * _pivot_lookback_ - number of bars on each side of a pivot (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct RsiDivergence {
+    rsi: RelativeStrengthIndex,
+    price_pivots: PivotPoints,
+    rsi_pivots: ScalarPivots,
+    last_high: Option<(Decimal, Decimal)>,
+    last_low: Option<(Decimal, Decimal)>,
+}
+
+impl RsiDivergence {
+    pub fn new(rsi_period: usize, pivot_lookback: usize) -> Result<Self> {
+        if pivot_lookback == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            rsi: RelativeStrengthIndex::new(rsi_period)?,
+            price_pivots: PivotPoints::new(pivot_lookback, 2)?,
+            rsi_pivots: ScalarPivots::new(pivot_lookback, 2)?,
+            last_high: None,
+            last_low: None,
+        })
+    }
+}
+
+fn find(pivots: &[Pivot], pivot_type: PivotType) -> Option<Decimal> {
+    pivots.iter().find(|pivot| *pivot.pivot_type() == pivot_type).map(Pivot::price)
+}
+
+impl Next<&DataItem> for RsiDivergence {
+    type Output = Divergence;
+
+    fn next(&mut self, input: &DataItem) -> Self::Output {
+        let rsi = match self.rsi.next(Decimal::from_f64_retain(input.close()).unwrap()) {
+            Some(rsi) => rsi,
+            None => return Divergence::None,
+        };
+        self.price_pivots.next(input);
+        self.rsi_pivots.next(rsi);
+
+        let mut divergence = Divergence::None;
+        let price = self.price_pivots.confirmed();
+        let osc = self.rsi_pivots.confirmed();
+
+        if let (Some(price), Some(osc)) = (find(price, PivotType::High), find(osc, PivotType::High)) {
+            divergence = match self.last_high.replace((price, osc)) {
+                Some((prev_price, prev_osc)) if price > prev_price && osc < prev_osc => Divergence::BearishRegular,
+                Some((prev_price, prev_osc)) if price < prev_price && osc > prev_osc => Divergence::BearishHidden,
+                _ => Divergence::None,
+            };
+        }
+        if let (Some(price), Some(osc)) = (find(price, PivotType::Low), find(osc, PivotType::Low)) {
+            divergence = match self.last_low.replace((price, osc)) {
+                Some((prev_price, prev_osc)) if price < prev_price && osc > prev_osc => Divergence::BullishRegular,
+                Some((prev_price, prev_osc)) if price > prev_price && osc < prev_osc => Divergence::BullishHidden,
+                _ => Divergence::None,
+            };
+        }
+        divergence
+    }
+}
+
+impl Reset for RsiDivergence {
+    fn reset(&mut self) {
+        self.rsi.reset();
+        self.price_pivots.reset();
+        self.rsi_pivots.reset();
+        self.last_high = None;
+        self.last_low = None;
+    }
+}
+
+impl Default for RsiDivergence {
+    fn default() -> Self {
+        Self::new(14, 5).unwrap()
+    }
+}
+
+impl fmt::Display for RsiDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RSI_DIV({})", self.rsi.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(price: f64) -> DataItem {
+        DataItem::builder().open(price).high(price).low(price).close(price).volume(1.0).build().unwrap()
+    }
+
+    fn run(div: &mut RsiDivergence, prices: &[f64]) -> Vec<Divergence> {
+        prices.iter().map(|price| div.next(&bar(*price))).collect()
+    }
+
+    // sharp drop to 15, rally, then a lower low at 14.9 reached with less downside momentum
+    const BULLISH: [f64; 11] = [20.0, 21.0, 22.0, 21.0, 15.0, 18.0, 19.0, 19.5, 14.9, 17.0, 16.0];
+
+    #[test]
+    fn test_new() {
+        assert!(RsiDivergence::new(0, 5).is_err());
+        assert!(RsiDivergence::new(14, 0).is_err());
+        assert!(RsiDivergence::new(14, 5).is_ok());
+    }
+
+    #[test]
+    fn test_bullish_regular() {
+        let mut div = RsiDivergence::new(2, 1).unwrap();
+        let events = run(&mut div, &BULLISH);
+
+        // the 14.9 low is confirmed one bar later
+        assert_eq!(events[9], Divergence::BullishRegular);
+        assert_eq!(events.iter().filter(|event| **event != Divergence::None).count(), 1);
+    }
+
+    #[test]
+    fn test_bearish_regular() {
+        // mirror image: higher price high, lower RSI high
+        let mirrored: Vec<f64> = BULLISH.iter().map(|price| 40.0 - price).collect();
+        let mut div = RsiDivergence::new(2, 1).unwrap();
+        let events = run(&mut div, &mirrored);
+        assert_eq!(events[9], Divergence::BearishRegular);
+    }
+
+    #[test]
+    fn test_hidden() {
+        // highs at 18 then 17 with the RSI higher the second time
+        let prices = [20.0, 21.0, 22.0, 21.0, 15.0, 18.0, 17.5, 17.0, 16.5, 14.5, 17.0, 16.0];
+        let mut div = RsiDivergence::new(2, 1).unwrap();
+        let events = run(&mut div, &prices);
+        assert_eq!(events[11], Divergence::BearishHidden);
+
+        let mirrored: Vec<f64> = prices.iter().map(|price| 40.0 - price).collect();
+        let mut div = RsiDivergence::new(2, 1).unwrap();
+        let events = run(&mut div, &mirrored);
+        assert_eq!(events[11], Divergence::BullishHidden);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut div = RsiDivergence::new(2, 1).unwrap();
+        run(&mut div, &BULLISH[..8]);
+
+        div.reset();
+        let events = run(&mut div, &BULLISH[4..]);
+        assert!(events.iter().all(|event| *event == Divergence::None));
+    }
+
+    #[test]
+    fn test_default() {
+        RsiDivergence::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", RsiDivergence::default()), "RSI_DIV(14)");
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::{Close, Next, Reset};
+
+use crate::model::DrawdownOutput;
+
+/// Drawdown of an equity curve or price series.
+///
+/// Tracks the highest value seen since the last reset and how far the series has fallen
+/// from it, now and at worst.
+///
+/// # Formula
+///
+/// DD = (peak - value) / peak * 100
+///
+/// Where _peak_ is the highest value seen so far. A non-positive peak gives a drawdown of
+/// 0, as a percentage decline from it has no meaning.
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use technical_analysis::drawdown::Drawdown;
+/// use ta::Next;
+///
+/// let mut dd = Drawdown::new();
+/// dd.next(dec!(100));
+/// assert_eq!(dd.next(dec!(80)).current_dd, dec!(20));
+/// ```
+#[doc(alias = "DD")]
+#[derive(Debug, Clone, Default)]
+pub struct Drawdown {
+    peak: Option<Decimal>,
+    max_dd: Decimal,
+}
+
+impl Drawdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Next<Decimal> for Drawdown {
+    type Output = DrawdownOutput;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let peak = self.peak.map_or(input, |peak| peak.max(input));
+        self.peak = Some(peak);
+
+        let current_dd = if peak > dec!(0) {
+            (peak - input) / peak * dec!(100)
+        } else {
+            dec!(0)
+        };
+        self.max_dd = self.max_dd.max(current_dd);
+        DrawdownOutput {
+            current_dd,
+            max_dd: self.max_dd,
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for Drawdown {
+    type Output = DrawdownOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for Drawdown {
+    fn reset(&mut self) {
+        self.peak = None;
+        self.max_dd = dec!(0);
+    }
+}
+
+impl fmt::Display for Drawdown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DRAWDOWN")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dd(current_dd: Decimal, max_dd: Decimal) -> DrawdownOutput {
+        DrawdownOutput { current_dd, max_dd }
+    }
+
+    #[test]
+    fn test_next() {
+        let mut drawdown = Drawdown::new();
+        assert_eq!(drawdown.next(dec!(100)), dd(dec!(0), dec!(0)));
+        assert_eq!(drawdown.next(dec!(120)), dd(dec!(0), dec!(0)));
+        assert_eq!(drawdown.next(dec!(108)), dd(dec!(10), dec!(10)));
+        assert_eq!(drawdown.next(dec!(90)), dd(dec!(25), dec!(25)));
+        // partial recovery keeps the peak and the worst drawdown
+        assert_eq!(drawdown.next(dec!(114)), dd(dec!(5), dec!(25)));
+        // a new peak clears the current drawdown only
+        assert_eq!(drawdown.next(dec!(130)), dd(dec!(0), dec!(25)));
+    }
+
+    #[test]
+    fn test_non_positive_peak() {
+        let mut drawdown = Drawdown::new();
+        assert_eq!(drawdown.next(dec!(-5)), dd(dec!(0), dec!(0)));
+        assert_eq!(drawdown.next(dec!(-10)), dd(dec!(0), dec!(0)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut drawdown = Drawdown::new();
+        drawdown.next(dec!(100));
+        drawdown.next(dec!(50));
+
+        drawdown.reset();
+        assert_eq!(drawdown.next(dec!(40)), dd(dec!(0), dec!(0)));
+        assert_eq!(drawdown.next(dec!(30)), dd(dec!(25), dec!(25)));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Drawdown::new()), "DRAWDOWN");
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+/// Kaufman efficiency ratio (ER).
+///
+/// How much of the path travelled over the last _period_ bars went in the net direction.
+/// 1 means every bar moved the same way (a pure trend), 0 means the moves cancelled out
+/// (pure noise).
+///
+/// # Formula
+///
+/// ER = |value - value<sub>period bars ago</sub>| / Σ|value<sub>i</sub> - value<sub>i-1</sub>|
+///
+/// Where the sum runs over the last _period_ changes. A window without any movement
+/// returns 0. Returns `None` until _period_ + 1 values have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[doc(alias = "ER")]
+#[derive(Debug, Clone)]
+pub struct EfficiencyRatio {
+    period: usize,
+    window: VecDeque<Decimal>,
+    volatility: Decimal,
+}
+
+impl EfficiencyRatio {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                window: VecDeque::with_capacity(period + 1),
+                volatility: dec!(0),
+            }),
+        }
+    }
+}
+
+impl Period for EfficiencyRatio {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for EfficiencyRatio {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        if let Some(last) = self.window.back() {
+            self.volatility += (input - last).abs();
+        }
+        if self.window.len() > self.period {
+            let oldest = self.window.pop_front().unwrap();
+            self.volatility -= (self.window[0] - oldest).abs();
+        }
+        self.window.push_back(input);
+        if self.window.len() <= self.period {
+            return None;
+        }
+
+        if self.volatility.is_zero() {
+            return Some(dec!(0));
+        }
+        Some((input - self.window[0]).abs() / self.volatility)
+    }
+}
+
+impl<T: Close> Next<&T> for EfficiencyRatio {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for EfficiencyRatio {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.volatility = dec!(0);
+    }
+}
+
+impl Default for EfficiencyRatio {
+    fn default() -> Self {
+        Self::new(10).unwrap()
+    }
+}
+
+impl fmt::Display for EfficiencyRatio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ER({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(EfficiencyRatio::new(0).is_err());
+        assert!(EfficiencyRatio::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut er = EfficiencyRatio::new(3).unwrap();
+        assert_eq!(er.next(dec!(10)), None);
+        assert_eq!(er.next(dec!(12)), None);
+        assert_eq!(er.next(dec!(11)), None);
+        // net 4 over a path of 2 + 1 + 3
+        assert_eq!(er.next(dec!(14)), Some(dec!(4) / dec!(6)));
+        // 12, 11, 14, 13: net 1 over 1 + 3 + 1
+        assert_eq!(er.next(dec!(13)), Some(dec!(0.2)));
+    }
+
+    #[test]
+    fn test_trending_vs_choppy() {
+        let mut trending = EfficiencyRatio::new(5).unwrap();
+        let mut choppy = EfficiencyRatio::new(5).unwrap();
+        for i in 0..12 {
+            let trend = trending.next(Decimal::from(100 + 2 * i));
+            let chop = choppy.next(Decimal::from(100 + i % 2));
+            if i >= 5 {
+                assert_eq!(trend, Some(dec!(1)));
+                assert!(chop.unwrap() <= dec!(0.2));
+            }
+        }
+    }
+
+    #[test]
+    fn test_flat() {
+        let mut er = EfficiencyRatio::new(2).unwrap();
+        er.next(dec!(5));
+        er.next(dec!(5));
+        assert_eq!(er.next(dec!(5)), Some(dec!(0)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut er = EfficiencyRatio::new(1).unwrap();
+        er.next(dec!(5));
+        assert!(er.next(dec!(6)).is_some());
+
+        er.reset();
+        assert_eq!(er.next(dec!(6)), None);
+        assert_eq!(er.next(dec!(8)), Some(dec!(1)));
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(EfficiencyRatio::default().period(), 10);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", EfficiencyRatio::default()), "ER(10)");
+    }
+}
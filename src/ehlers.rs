@@ -0,0 +1,210 @@
+//! Filters from John Ehlers' cycle analysis work.
+
+use std::f64::consts::PI;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+/// Ehlers Super Smoother (SSF).
+///
+/// Two-pole Butterworth low-pass filter. It removes components with a cycle shorter than
+/// _period_ bars while adding much less lag than a moving average of the same length.
+///
+/// # Formula
+///
+/// a = exp(-1.414 * π / period)
+///
+/// c<sub>2</sub> = 2 * a * cos(1.414 * π / period)
+///
+/// c<sub>3</sub> = -a<sup>2</sup>
+///
+/// c<sub>1</sub> = 1 - c<sub>2</sub> - c<sub>3</sub>
+///
+/// SSF<sub>t</sub> = c<sub>1</sub> * (x<sub>t</sub> + x<sub>t-1</sub>) / 2 + c<sub>2</sub> * SSF<sub>t-1</sub> + c<sub>3</sub> * SSF<sub>t-2</sub>
+///
+/// The first two outputs are the inputs themselves, which seeds the recursion.
+///
+/// # Parameters
+///
+/// * _period_ - critical period in bars (integer greater than 0)
+#[doc(alias = "SSF")]
+#[derive(Debug, Clone)]
+pub struct SuperSmoother {
+    period: usize,
+    c1: Decimal,
+    c2: Decimal,
+    c3: Decimal,
+    prev_input: Decimal,
+    prev_outputs: [Decimal; 2],
+    count: usize,
+}
+
+impl SuperSmoother {
+    pub fn new(period: usize) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        let arg = 1.414 * PI / period as f64;
+        let a = (-arg).exp();
+        let c2 = 2.0 * a * arg.cos();
+        let c3 = -a * a;
+        let c1 = 1.0 - c2 - c3;
+        Ok(Self {
+            period,
+            c1: Decimal::from_f64(c1).unwrap(),
+            c2: Decimal::from_f64(c2).unwrap(),
+            c3: Decimal::from_f64(c3).unwrap(),
+            prev_input: Decimal::ZERO,
+            prev_outputs: [Decimal::ZERO; 2],
+            count: 0,
+        })
+    }
+}
+
+impl Period for SuperSmoother {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for SuperSmoother {
+    type Output = Decimal;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let output = if self.count < 2 {
+            self.count += 1;
+            input
+        } else {
+            self.c1 * (input + self.prev_input) / Decimal::TWO
+                + self.c2 * self.prev_outputs[1]
+                + self.c3 * self.prev_outputs[0]
+        };
+        self.prev_input = input;
+        self.prev_outputs = [self.prev_outputs[1], output];
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for SuperSmoother {
+    type Output = Decimal;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for SuperSmoother {
+    fn reset(&mut self) {
+        self.prev_input = Decimal::ZERO;
+        self.prev_outputs = [Decimal::ZERO; 2];
+        self.count = 0;
+    }
+}
+
+impl Default for SuperSmoother {
+    fn default() -> Self {
+        Self::new(10).unwrap()
+    }
+}
+
+impl fmt::Display for SuperSmoother {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SSF({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(SuperSmoother::new(0).is_err());
+        assert!(SuperSmoother::new(10).is_ok());
+    }
+
+    #[test]
+    fn test_coefficients_sum_to_one() {
+        let ssf = SuperSmoother::new(10).unwrap();
+        assert_eq!((ssf.c1 + ssf.c2 + ssf.c3).round_dp(12), dec!(1));
+    }
+
+    #[test]
+    fn test_seed() {
+        let mut ssf = SuperSmoother::new(10).unwrap();
+        assert_eq!(ssf.next(dec!(5)), dec!(5));
+        assert_eq!(ssf.next(dec!(7)), dec!(7));
+    }
+
+    #[test]
+    fn test_constant_input() {
+        let mut ssf = SuperSmoother::new(10).unwrap();
+        let mut out = dec!(0);
+        for _ in 0..50 {
+            out = ssf.next(dec!(20));
+        }
+        assert_eq!(out.round_dp(10), dec!(20));
+    }
+
+    #[test]
+    fn test_low_pass() {
+        // 2-bar cycle around 10: well under the critical period, so it should be removed
+        let mut noisy = SuperSmoother::new(10).unwrap();
+        let mut swing = dec!(0);
+        for i in 0..60 {
+            let input = if i % 2 == 0 { dec!(11) } else { dec!(9) };
+            let out = noisy.next(input);
+            if i >= 40 {
+                swing = swing.max((out - dec!(10)).abs());
+            }
+        }
+        assert!(swing < dec!(0.01));
+
+        // a clean ramp passes through, trailing the input by a roughly constant lag
+        let mut ramp = SuperSmoother::new(10).unwrap();
+        let mut lags = Vec::new();
+        for i in 0..60 {
+            let input = Decimal::from(i);
+            let out = ramp.next(input);
+            lags.push(input - out);
+        }
+        assert!(lags[59] > dec!(0));
+        assert!((lags[59] - lags[58]).abs() < dec!(0.001));
+        assert!(lags[59] < dec!(5));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        fn bar(close: f64) -> ta::DataItem {
+            ta::DataItem::builder().open(close).high(close).low(close).close(close).volume(1.0).build().unwrap()
+        }
+
+        let mut ssf = SuperSmoother::new(10).unwrap();
+        assert_eq!(ssf.next(&bar(3.0)), dec!(3));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ssf = SuperSmoother::new(10).unwrap();
+        ssf.next(dec!(1));
+        ssf.next(dec!(2));
+        ssf.next(dec!(3));
+
+        ssf.reset();
+        assert_eq!(ssf.next(dec!(9)), dec!(9));
+        assert_eq!(ssf.next(dec!(8)), dec!(8));
+    }
+
+    #[test]
+    fn test_default() {
+        SuperSmoother::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", SuperSmoother::new(10).unwrap()), "SSF(10)");
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::errors::Result;
+use ta::{Close, Next, Period, Reset};
+
+use crate::ema::ExponentialMovingAverage;
+use crate::macd::MovingAverageConvergenceDivergence;
+use crate::model::Impulse;
+
+/// Elder Impulse System.
+///
+/// Colours each bar from two slopes: the EMA of price (trend) and the MACD histogram
+/// (momentum).
+///
+/// * `Green` - the EMA and the histogram are both rising
+/// * `Red` - the EMA and the histogram are both falling
+/// * `Blue` - anything else
+///
+/// Returns `None` on the first bar, which has no slope yet.
+///
+/// # Parameters
+///
+/// * _ema_period_ - period of the trend EMA (integer greater than 0)
+/// * _macd_fast_, _macd_slow_, _macd_signal_ - periods of the MACD
+#[derive(Debug, Clone)]
+pub struct ElderImpulse {
+    ema: ExponentialMovingAverage,
+    macd: MovingAverageConvergenceDivergence,
+    prev: Option<(Decimal, Decimal)>,
+}
+
+impl ElderImpulse {
+    pub fn new(ema_period: usize, macd_fast: usize, macd_slow: usize, macd_signal: usize) -> Result<Self> {
+        Ok(Self {
+            ema: ExponentialMovingAverage::new(ema_period)?,
+            macd: MovingAverageConvergenceDivergence::new(macd_fast, macd_slow, macd_signal)?,
+            prev: None,
+        })
+    }
+}
+
+impl<T: Close> Next<&T> for ElderImpulse {
+    type Output = Option<Impulse>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let ema = self.ema.next(input);
+        let histogram = self.macd.next(input).histogram;
+        let (prev_ema, prev_histogram) = self.prev.replace((ema, histogram))?;
+
+        Some(if ema > prev_ema && histogram > prev_histogram {
+            Impulse::Green
+        } else if ema < prev_ema && histogram < prev_histogram {
+            Impulse::Red
+        } else {
+            Impulse::Blue
+        })
+    }
+}
+
+impl Reset for ElderImpulse {
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.macd.reset();
+        self.prev = None;
+    }
+}
+
+impl Default for ElderImpulse {
+    fn default() -> Self {
+        Self::new(13, 12, 26, 9).unwrap()
+    }
+}
+
+impl fmt::Display for ElderImpulse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "IMPULSE({}, {})", self.ema.period(), self.macd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(close: f64) -> DataItem {
+        DataItem::builder()
+            .open(close)
+            .high(close)
+            .low(close)
+            .close(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    fn run(impulse: &mut ElderImpulse, closes: impl Iterator<Item = f64>) -> Vec<Option<Impulse>> {
+        closes.map(|close| impulse.next(&bar(close))).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(ElderImpulse::new(0, 12, 26, 9).is_err());
+        assert!(ElderImpulse::new(13, 26, 12, 9).is_err());
+        assert!(ElderImpulse::new(13, 12, 26, 9).is_ok());
+    }
+
+    #[test]
+    fn test_green() {
+        // accelerating rally: price and momentum both climbing
+        let mut impulse = ElderImpulse::new(3, 2, 4, 2).unwrap();
+        let out = run(&mut impulse, (0..20).map(|i| 100.0 + (i * i) as f64));
+        assert_eq!(out[0], None);
+        assert_eq!(out[19], Some(Impulse::Green));
+    }
+
+    #[test]
+    fn test_red() {
+        let mut impulse = ElderImpulse::new(3, 2, 4, 2).unwrap();
+        let out = run(&mut impulse, (0..20).map(|i| 1000.0 - (i * i) as f64));
+        assert_eq!(out[19], Some(Impulse::Red));
+    }
+
+    #[test]
+    fn test_blue() {
+        // steady rally: the EMA keeps rising while the histogram decays towards zero
+        let mut impulse = ElderImpulse::new(3, 2, 4, 2).unwrap();
+        let out = run(&mut impulse, (0..30).map(|i| 100.0 + i as f64));
+        assert_eq!(out[29], Some(Impulse::Blue));
+
+        // flat market
+        let mut impulse = ElderImpulse::new(3, 2, 4, 2).unwrap();
+        let out = run(&mut impulse, (0..5).map(|_| 100.0));
+        assert_eq!(out[4], Some(Impulse::Blue));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut impulse = ElderImpulse::new(3, 2, 4, 2).unwrap();
+        run(&mut impulse, (0..10).map(|i| 100.0 + i as f64));
+
+        impulse.reset();
+        assert_eq!(impulse.next(&bar(50.0)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        ElderImpulse::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", ElderImpulse::default()), "IMPULSE(13, MACD(12, 26, 9))");
+    }
+}
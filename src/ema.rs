@@ -1,9 +1,12 @@
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal_macros::dec;
 use std::fmt;
 use ta::errors::{Result, TaError};
 use ta::{Close, Next, Period, Reset};
 
+use crate::warmup::{WarmupPolicy, WithWarmupPolicy};
+
 /// An exponential moving average (EMA), also known as an exponentially weighted moving average
 /// (EWMA).
 ///
@@ -52,7 +55,7 @@ use ta::{Close, Next, Period, Reset};
 ///
 
 #[doc(alias = "EMA")]
-#[derive(Debug, Clone)]
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub struct ExponentialMovingAverage {
     period: usize,
     k: Decimal,
@@ -72,6 +75,34 @@ impl ExponentialMovingAverage {
             }),
         }
     }
+
+    /// EMA with the smoothing factor given directly instead of derived from a period.
+    /// `alpha` must be in (0, 1]. `period` then reports the equivalent
+    /// `2 / alpha - 1`, rounded, at least 1 and saturating at `usize::MAX`.
+    pub fn with_alpha(alpha: Decimal) -> Result<Self> {
+        if alpha <= dec!(0) || alpha > dec!(1) {
+            return Err(TaError::InvalidParameter);
+        }
+        let period = (dec!(2) / alpha - dec!(1)).round().to_usize().unwrap_or(usize::MAX).max(1);
+        Ok(Self {
+            k: alpha,
+            ..Self::new(period)?
+        })
+    }
+
+    /// Same average, but emits `None` until `period` inputs have been seen.
+    pub fn warmup(period: usize) -> Result<WarmupExponentialMovingAverage> {
+        Ok(WarmupExponentialMovingAverage {
+            ema: ExponentialMovingAverage::new(period)?,
+            count: 0,
+        })
+    }
+
+    /// Wraps the EMA to pick its warmup output. Unwrapped, it starts from the first input
+    /// and emits every value, which is `WarmupPolicy::Emit`.
+    pub fn warmup_policy(self, policy: WarmupPolicy) -> WithWarmupPolicy<Self> {
+        WithWarmupPolicy::new(self, policy)
+    }
 }
 
 impl Period for ExponentialMovingAverage {
@@ -121,6 +152,186 @@ impl fmt::Display for ExponentialMovingAverage {
     }
 }
 
+/// Exponential moving average that stays silent during warmup.
+///
+/// Wraps [ExponentialMovingAverage](struct.ExponentialMovingAverage.html) and returns `None`
+/// until `period` inputs have been seen, so composed indicators don't pick up the early,
+/// under-sampled values. Create it with `ExponentialMovingAverage::warmup`.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct WarmupExponentialMovingAverage {
+    ema: ExponentialMovingAverage,
+    count: usize,
+}
+
+impl WarmupExponentialMovingAverage {
+    /// The underlying EMA's latest value, warmup included, or 0 before the first input.
+    pub(crate) fn current(&self) -> Decimal {
+        self.ema.current
+    }
+}
+
+impl Period for WarmupExponentialMovingAverage {
+    fn period(&self) -> usize {
+        self.ema.period()
+    }
+}
+
+impl Next<Decimal> for WarmupExponentialMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let current = self.ema.next(input);
+        if self.count < self.ema.period() {
+            self.count += 1;
+        }
+        if self.count < self.ema.period() {
+            None
+        } else {
+            Some(current)
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for WarmupExponentialMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for WarmupExponentialMovingAverage {
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.count = 0;
+    }
+}
+
+impl Default for WarmupExponentialMovingAverage {
+    fn default() -> Self {
+        ExponentialMovingAverage::warmup(9).unwrap()
+    }
+}
+
+impl fmt::Display for WarmupExponentialMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EMA({})", self.ema.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::assert_full_reset;
+    use super::*;
+
+    #[test]
+    fn test_full_reset() {
+        let series = [dec!(2), dec!(5), dec!(1), dec!(6.25), dec!(3)];
+        assert_full_reset(ExponentialMovingAverage::new(3).unwrap(), &series);
+        assert_full_reset(ExponentialMovingAverage::warmup(3).unwrap(), &series);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        ema.next(dec!(2));
+        ema.next(dec!(5));
+
+        let json = serde_json::to_string(&ema).unwrap();
+        let mut restored: ExponentialMovingAverage = serde_json::from_str(&json).unwrap();
+        for value in [dec!(1), dec!(6.25), dec!(3)] {
+            assert_eq!(restored.next(value), ema.next(value));
+        }
+    }
+
+    #[test]
+    fn test_with_alpha_new() {
+        assert!(ExponentialMovingAverage::with_alpha(dec!(0)).is_err());
+        assert!(ExponentialMovingAverage::with_alpha(dec!(-0.5)).is_err());
+        assert!(ExponentialMovingAverage::with_alpha(dec!(1.01)).is_err());
+        assert_eq!(ExponentialMovingAverage::with_alpha(dec!(1)).unwrap().period(), 1);
+        assert_eq!(ExponentialMovingAverage::with_alpha(dec!(0.3)).unwrap().period(), 6);
+        // the equivalent period doesn't fit in a usize
+        let mut tiny = ExponentialMovingAverage::with_alpha(dec!(0.0000000000000000000001)).unwrap();
+        assert_eq!(tiny.period(), usize::MAX);
+        assert_eq!(tiny.next(dec!(5)), dec!(5));
+    }
+
+    #[test]
+    fn test_with_alpha_matches_period() {
+        let n = 10;
+        let mut ema = ExponentialMovingAverage::new(n).unwrap();
+        let mut alpha = ExponentialMovingAverage::with_alpha(dec!(2) / Decimal::from(n + 1)).unwrap();
+        assert_eq!(format!("{}", alpha), "EMA(10)");
+
+        for input in [dec!(4), dec!(7.5), dec!(3), dec!(9), dec!(11), dec!(6)] {
+            assert_eq!(alpha.next(input), ema.next(input));
+        }
+    }
+
+    #[test]
+    fn test_warmup_policy() {
+        let outputs = |policy| {
+            let mut ema = ExponentialMovingAverage::new(4).unwrap().warmup_policy(policy);
+            [dec!(2), dec!(7), dec!(3), dec!(8)].map(|input| ema.next(input))
+        };
+        assert_eq!(outputs(WarmupPolicy::Emit), [Some(dec!(2)), Some(dec!(4)), Some(dec!(3.6)), Some(dec!(5.36))]);
+        assert_eq!(outputs(WarmupPolicy::Partial), [Some(dec!(2)), Some(dec!(4.5)), Some(dec!(4)), Some(dec!(5.36))]);
+        assert_eq!(outputs(WarmupPolicy::None), [None, None, None, Some(dec!(5.36))]);
+    }
+
+    #[test]
+    fn test_warmup_new() {
+        assert!(ExponentialMovingAverage::warmup(0).is_err());
+        assert!(ExponentialMovingAverage::warmup(1).is_ok());
+    }
+
+    #[test]
+    fn test_warmup_next() {
+        let mut ema = ExponentialMovingAverage::warmup(3).unwrap();
+
+        assert_eq!(ema.next(dec!(2.0)), None);
+        assert_eq!(ema.next(dec!(5.0)), None);
+        assert_eq!(ema.next(dec!(1.0)), Some(dec!(2.25)));
+        assert_eq!(ema.next(dec!(6.25)), Some(dec!(4.25)));
+    }
+
+    #[test]
+    fn test_warmup_matches_ema() {
+        let mut ema = ExponentialMovingAverage::new(4).unwrap();
+        let mut warmup = ExponentialMovingAverage::warmup(4).unwrap();
+
+        for (i, input) in [dec!(4), dec!(7), dec!(3), dec!(9), dec!(11), dec!(6)].into_iter().enumerate() {
+            let expected = ema.next(input);
+            match warmup.next(input) {
+                None => assert!(i < 3),
+                Some(value) => {
+                    assert!(i >= 3);
+                    assert_eq!(value, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_warmup_reset() {
+        let mut ema = ExponentialMovingAverage::warmup(2).unwrap();
+
+        ema.next(dec!(4.0));
+        assert!(ema.next(dec!(10.0)).is_some());
+
+        ema.reset();
+        assert_eq!(ema.next(dec!(4.0)), None);
+        assert_eq!(ema.next(dec!(4.0)), Some(dec!(4.0)));
+    }
+
+    #[test]
+    fn test_warmup_display() {
+        let ema = ExponentialMovingAverage::warmup(7).unwrap();
+        assert_eq!(format!("{}", ema), "EMA(7)");
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use super::*;
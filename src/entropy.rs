@@ -0,0 +1,215 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::math::ln;
+
+/// Rolling Shannon entropy of returns.
+///
+/// Measures how spread out the recent one-bar returns are, to tell directional regimes
+/// from noisy ones. The returns are binned into _bins_ equal buckets over a range that is
+/// symmetric around zero, so a trend, whose returns all fall on one side, fills at most half
+/// of the buckets and reads low, while a market going nowhere fills both sides and reads
+/// close to 1. A series with constant returns reads 0.
+///
+/// # Formula
+///
+/// r = close / previous close - 1, or 0 when the previous close is 0
+///
+/// The last _period_ returns are binned over [-m, m], where m = max |r|.
+///
+/// H = -Σ p<sub>i</sub> * ln(p<sub>i</sub>) / ln(bins)
+///
+/// Where p<sub>i</sub> is the share of returns in bucket _i_, and empty buckets are
+/// skipped. Returns `None` until _period_ + 1 inputs have been seen. Logarithms come from
+/// the [math](../math/index.html) helpers.
+///
+/// # Parameters
+///
+/// * _period_ - number of returns binned (integer greater than 0)
+/// * _bins_ - number of buckets (integer of at least 2)
+#[derive(Debug, Clone)]
+pub struct ShannonEntropy {
+    period: usize,
+    bins: usize,
+    previous: Option<Decimal>,
+    returns: VecDeque<Decimal>,
+}
+
+impl ShannonEntropy {
+    pub fn new(period: usize, bins: usize) -> Result<Self> {
+        if period == 0 || bins < 2 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            period,
+            bins,
+            previous: None,
+            returns: VecDeque::with_capacity(period),
+        })
+    }
+
+    fn entropy(&self) -> Decimal {
+        let bound = self.returns.iter().map(|r| r.abs()).max().unwrap();
+        let mut counts = vec![0usize; self.bins];
+        for r in &self.returns {
+            let bucket = if bound.is_zero() {
+                self.bins / 2
+            } else {
+                ((r + bound) / (bound * dec!(2)) * Decimal::from(self.bins)).floor().to_usize().unwrap()
+            };
+            counts[bucket.min(self.bins - 1)] += 1;
+        }
+
+        let n = Decimal::from(self.returns.len());
+        let sum: Decimal = counts
+            .into_iter()
+            .filter(|count| *count > 0)
+            .map(|count| {
+                let p = Decimal::from(count) / n;
+                p * ln(p)
+            })
+            .sum();
+        -sum / ln(Decimal::from(self.bins))
+    }
+}
+
+impl Period for ShannonEntropy {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for ShannonEntropy {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let previous = self.previous.replace(input)?;
+        let r = if previous.is_zero() { dec!(0) } else { input / previous - dec!(1) };
+        if self.returns.len() == self.period {
+            self.returns.pop_front();
+        }
+        self.returns.push_back(r);
+        if self.returns.len() < self.period {
+            return None;
+        }
+        Some(self.entropy())
+    }
+}
+
+impl<T: Close> Next<&T> for ShannonEntropy {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for ShannonEntropy {
+    fn reset(&mut self) {
+        self.previous = None;
+        self.returns.clear();
+    }
+}
+
+impl Default for ShannonEntropy {
+    fn default() -> Self {
+        Self::new(50, 10).unwrap()
+    }
+}
+
+impl fmt::Display for ShannonEntropy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ENTROPY({}, {})", self.period, self.bins)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::prelude::FromPrimitive;
+    use super::*;
+
+    /// Deterministic noise in [-0.5, 0.5).
+    fn noise(seed: &mut u64) -> f64 {
+        *seed = (*seed * 1103515245 + 12345) % (1 << 31);
+        *seed as f64 / (1u64 << 31) as f64 - 0.5
+    }
+
+    /// Last entropy reading over prices whose changes come from `change(noise)`.
+    fn estimate(change: impl Fn(f64) -> f64) -> Decimal {
+        let mut entropy = ShannonEntropy::default();
+        let mut seed = 12345;
+        let mut price = 100.0;
+        let mut last = None;
+        for _ in 0..=100 {
+            price += change(noise(&mut seed));
+            last = entropy.next(Decimal::from_f64(price).unwrap());
+        }
+        last.unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(ShannonEntropy::new(0, 10).is_err());
+        assert!(ShannonEntropy::new(50, 1).is_err());
+        assert!(ShannonEntropy::new(1, 2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut entropy = ShannonEntropy::new(4, 2).unwrap();
+        for close in [dec!(100), dec!(101), dec!(102), dec!(101)] {
+            assert_eq!(entropy.next(close), None);
+        }
+        // two returns up, two down: one bit out of one
+        assert_eq!(entropy.next(dec!(100)).unwrap().round_dp(12), dec!(1));
+        assert_eq!(entropy.next(dec!(101)).unwrap().round_dp(12), dec!(1));
+        assert_eq!(entropy.next(dec!(102)).unwrap().round_dp(12), dec!(1));
+        // three up, one down
+        assert_eq!(entropy.next(dec!(103)).unwrap().round_dp(6), dec!(0.811278));
+    }
+
+    #[test]
+    fn test_constant_returns() {
+        let mut entropy = ShannonEntropy::new(3, 10).unwrap();
+        let outputs: Vec<_> = [dec!(8), dec!(8), dec!(8), dec!(8), dec!(8)].iter().map(|c| entropy.next(*c)).collect();
+        assert_eq!(outputs, [None, None, None, Some(dec!(0)), Some(dec!(0))]);
+
+        let mut entropy = ShannonEntropy::new(3, 10).unwrap();
+        let outputs: Vec<_> = [dec!(1), dec!(2), dec!(4), dec!(8)].iter().map(|c| entropy.next(*c)).collect();
+        assert_eq!(outputs[3], Some(dec!(0)));
+    }
+
+    #[test]
+    fn test_trending_vs_random() {
+        let trending = estimate(|noise| 1.0 + noise);
+        let random = estimate(|noise| noise);
+
+        assert!(trending < dec!(0.7), "{}", trending);
+        assert!(random > dec!(0.85), "{}", random);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut entropy = ShannonEntropy::new(1, 2).unwrap();
+        entropy.next(dec!(10));
+        assert!(entropy.next(dec!(11)).is_some());
+
+        entropy.reset();
+        assert_eq!(entropy.next(dec!(12)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        ShannonEntropy::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", ShannonEntropy::default()), "ENTROPY(50, 10)");
+    }
+}
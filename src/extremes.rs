@@ -0,0 +1,233 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::errors::{Result, TaError};
+use ta::{High, Low, Next, Period, Reset};
+
+/// Highest value over the last _period_ values.
+///
+/// Keeps the window's candidates for the maximum in a monotonic queue, so each update is
+/// O(1) amortized instead of a scan of the window.
+///
+/// Returns `None` until _period_ values have been seen. Fed bars, it tracks their highs.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use technical_analysis::extremes::Highest;
+/// use ta::Next;
+///
+/// let mut highest = Highest::new(2).unwrap();
+/// assert_eq!(highest.next(dec!(3)), None);
+/// assert_eq!(highest.next(dec!(1)), Some(dec!(3)));
+/// assert_eq!(highest.next(dec!(2)), Some(dec!(2)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Highest {
+    period: usize,
+    count: usize,
+    /// (index, value) pairs with strictly decreasing values; the front is the maximum.
+    candidates: VecDeque<(usize, Decimal)>,
+}
+
+impl Highest {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                count: 0,
+                candidates: VecDeque::with_capacity(period),
+            }),
+        }
+    }
+}
+
+impl Period for Highest {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for Highest {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        while self.candidates.back().is_some_and(|(_, value)| *value <= input) {
+            self.candidates.pop_back();
+        }
+        self.candidates.push_back((self.count, input));
+        self.count += 1;
+        if self.candidates[0].0 + self.period < self.count {
+            self.candidates.pop_front();
+        }
+
+        if self.count < self.period {
+            return None;
+        }
+        Some(self.candidates[0].1)
+    }
+}
+
+impl<T: High> Next<&T> for Highest {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.high()).unwrap())
+    }
+}
+
+impl Reset for Highest {
+    fn reset(&mut self) {
+        self.count = 0;
+        self.candidates.clear();
+    }
+}
+
+impl Default for Highest {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for Highest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HIGHEST({})", self.period)
+    }
+}
+
+/// Lowest value over the last _period_ values.
+///
+/// The mirror image of [Highest](struct.Highest.html): returns `None` until _period_
+/// values have been seen, and fed bars, it tracks their lows.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct Lowest {
+    /// Highest of the negated values.
+    highest: Highest,
+}
+
+impl Lowest {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            highest: Highest::new(period)?,
+        })
+    }
+}
+
+impl Period for Lowest {
+    fn period(&self) -> usize {
+        self.highest.period()
+    }
+}
+
+impl Next<Decimal> for Lowest {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        self.highest.next(-input).map(|value| -value)
+    }
+}
+
+impl<T: Low> Next<&T> for Lowest {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.low()).unwrap())
+    }
+}
+
+impl Reset for Lowest {
+    fn reset(&mut self) {
+        self.highest.reset();
+    }
+}
+
+impl Default for Lowest {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for Lowest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LOWEST({})", self.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(Highest::new(0).is_err());
+        assert!(Highest::new(1).is_ok());
+        assert!(Lowest::new(0).is_err());
+        assert!(Lowest::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut highest = Highest::new(3).unwrap();
+        let mut lowest = Lowest::new(3).unwrap();
+        let inputs = [dec!(4), dec!(2), dec!(5), dec!(1), dec!(3), dec!(3), dec!(2)];
+        let highs: Vec<_> = inputs.iter().map(|input| highest.next(*input)).collect();
+        let lows: Vec<_> = inputs.iter().map(|input| lowest.next(*input)).collect();
+
+        assert_eq!(highs, [None, None, Some(dec!(5)), Some(dec!(5)), Some(dec!(5)), Some(dec!(3)), Some(dec!(3))]);
+        assert_eq!(lows, [None, None, Some(dec!(2)), Some(dec!(1)), Some(dec!(1)), Some(dec!(1)), Some(dec!(2))]);
+    }
+
+    #[test]
+    fn test_matches_scan() {
+        let mut highest = Highest::new(5).unwrap();
+        let mut lowest = Lowest::new(5).unwrap();
+        let inputs: Vec<Decimal> = (0..60).map(|i| Decimal::from((i * 37) % 23)).collect();
+        for (i, input) in inputs.iter().enumerate() {
+            let (high, low) = (highest.next(*input), lowest.next(*input));
+            if i >= 4 {
+                let window = &inputs[i - 4..=i];
+                assert_eq!(high, window.iter().max().copied());
+                assert_eq!(low, window.iter().min().copied());
+            }
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut highest = Highest::new(2).unwrap();
+        highest.next(dec!(9));
+        highest.next(dec!(1));
+        highest.reset();
+        assert_eq!(highest.next(dec!(2)), None);
+        assert_eq!(highest.next(dec!(1)), Some(dec!(2)));
+
+        let mut lowest = Lowest::new(2).unwrap();
+        lowest.next(dec!(1));
+        lowest.reset();
+        assert_eq!(lowest.next(dec!(5)), None);
+        assert_eq!(lowest.next(dec!(6)), Some(dec!(5)));
+    }
+
+    #[test]
+    fn test_default() {
+        Highest::default();
+        Lowest::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Highest::new(20).unwrap()), "HIGHEST(20)");
+        assert_eq!(format!("{}", Lowest::new(20).unwrap()), "LOWEST(20)");
+    }
+}
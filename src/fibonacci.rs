@@ -0,0 +1,93 @@
+//! Fibonacci retracement and extension levels.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::model::FibLevels;
+
+/// Fibonacci levels of the swing from `start` to `end`, e.g. a pivot low and the
+/// following pivot high.
+///
+/// Each level retraces the swing by its ratio, measured back from `end`: 0 is `end`
+/// itself, 1.0 is `start`, and 1.272 / 1.618 extend past `start`. For an upward swing the
+/// levels therefore fall as the ratio grows, and for a downward swing they rise.
+///
+/// # Formula
+///
+/// level<sub>r</sub> = end - r * (end - start)
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use technical_analysis::fibonacci::fib_levels;
+///
+/// let levels = fib_levels(dec!(100), dec!(200));
+/// assert_eq!(levels.level_500, dec!(150));
+/// assert_eq!(levels.level_618, dec!(138.2));
+/// ```
+pub fn fib_levels(start: Decimal, end: Decimal) -> FibLevels {
+    let range = end - start;
+    let level = |ratio: Decimal| end - ratio * range;
+    FibLevels {
+        level_0: level(dec!(0)),
+        level_236: level(dec!(0.236)),
+        level_382: level(dec!(0.382)),
+        level_500: level(dec!(0.5)),
+        level_618: level(dec!(0.618)),
+        level_786: level(dec!(0.786)),
+        level_1000: level(dec!(1)),
+        level_1272: level(dec!(1.272)),
+        level_1618: level(dec!(1.618)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn as_vec(levels: &FibLevels) -> Vec<Decimal> {
+        vec![
+            levels.level_0,
+            levels.level_236,
+            levels.level_382,
+            levels.level_500,
+            levels.level_618,
+            levels.level_786,
+            levels.level_1000,
+            levels.level_1272,
+            levels.level_1618,
+        ]
+    }
+
+    #[test]
+    fn test_upward_swing() {
+        let levels = fib_levels(dec!(100), dec!(200));
+
+        assert_eq!(
+            as_vec(&levels),
+            vec![
+                dec!(200), dec!(176.4), dec!(161.8), dec!(150), dec!(138.2), dec!(121.4),
+                dec!(100), dec!(72.8), dec!(38.2),
+            ]
+        );
+        assert!(as_vec(&levels).windows(2).all(|w| w[0] > w[1]));
+    }
+
+    #[test]
+    fn test_downward_swing() {
+        let levels = fib_levels(dec!(50), dec!(40));
+
+        assert_eq!(levels.level_0, dec!(40));
+        assert_eq!(levels.level_382, dec!(43.82));
+        assert_eq!(levels.level_1000, dec!(50));
+        assert_eq!(levels.level_1618, dec!(56.18));
+        assert!(as_vec(&levels).windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_flat_swing() {
+        let levels = fib_levels(dec!(10), dec!(10));
+        assert!(as_vec(&levels).iter().all(|level| *level == dec!(10)));
+    }
+}
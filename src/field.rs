@@ -0,0 +1,186 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::{Close, DataItem, High, Low, Next, Open, Period, Reset, Volume};
+
+/// Picks the value an indicator sees out of a bar.
+pub trait FieldExtractor {
+    fn extract(&self, input: &DataItem) -> Decimal;
+}
+
+/// Bar field or price composite, selectable at runtime.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Field {
+    Open,
+    High,
+    Low,
+    Close,
+    Volume,
+    /// (high + low) / 2
+    Hl2,
+    /// (high + low + close) / 3
+    Hlc3,
+    /// (open + high + low + close) / 4
+    Ohlc4,
+}
+
+impl FieldExtractor for Field {
+    fn extract(&self, input: &DataItem) -> Decimal {
+        let value = |v: f64| Decimal::from_f64(v).unwrap();
+        match self {
+            Field::Open => value(input.open()),
+            Field::High => value(input.high()),
+            Field::Low => value(input.low()),
+            Field::Close => value(input.close()),
+            Field::Volume => value(input.volume()),
+            Field::Hl2 => (value(input.high()) + value(input.low())) / dec!(2),
+            Field::Hlc3 => (value(input.high()) + value(input.low()) + value(input.close())) / dec!(3),
+            Field::Ohlc4 => {
+                (value(input.open()) + value(input.high()) + value(input.low()) + value(input.close())) / dec!(4)
+            }
+        }
+    }
+}
+
+impl FieldExtractor for Box<dyn FieldExtractor> {
+    fn extract(&self, input: &DataItem) -> Decimal {
+        (**self).extract(input)
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Field::Open => "open",
+            Field::High => "high",
+            Field::Low => "low",
+            Field::Close => "close",
+            Field::Volume => "volume",
+            Field::Hl2 => "hl2",
+            Field::Hlc3 => "hlc3",
+            Field::Ohlc4 => "ohlc4",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Runs a `Next<Decimal>` indicator on a field of the bar chosen at runtime.
+///
+/// Every bar passed to `next` goes through the extractor and the resulting value is handed
+/// to the wrapped indicator. The extractor is usually a [Field](enum.Field.html), but any
+/// [FieldExtractor](trait.FieldExtractor.html), including a boxed one, works.
+///
+/// # Example
+///
+/// ```
+/// use technical_analysis::field::{Field, OnField};
+/// use technical_analysis::simple_moving_average::SimpleMovingAverage;
+///
+/// let sma_hl2 = OnField::new(SimpleMovingAverage::new(3).unwrap(), Field::Hl2);
+/// assert_eq!(format!("{}", sma_hl2), "SMA(3)[hl2]");
+/// ```
+#[derive(Debug, Clone)]
+pub struct OnField<I, F = Field> {
+    indicator: I,
+    field: F,
+}
+
+impl<I, F: FieldExtractor> OnField<I, F> {
+    pub fn new(indicator: I, field: F) -> Self {
+        Self { indicator, field }
+    }
+}
+
+impl<I: Next<Decimal>, F: FieldExtractor> Next<&DataItem> for OnField<I, F> {
+    type Output = I::Output;
+
+    fn next(&mut self, input: &DataItem) -> Self::Output {
+        self.indicator.next(self.field.extract(input))
+    }
+}
+
+impl<I: Period, F> Period for OnField<I, F> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I: Reset, F> Reset for OnField<I, F> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+impl<I: fmt::Display, F: fmt::Display> fmt::Display for OnField<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}[{}]", self.indicator, self.field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simple_moving_average::SimpleMovingAverage;
+
+    fn bar(open: f64, high: f64, low: f64, close: f64, volume: f64) -> DataItem {
+        DataItem::builder().open(open).high(high).low(low).close(close).volume(volume).build().unwrap()
+    }
+
+    fn series() -> Vec<DataItem> {
+        vec![bar(10.0, 12.0, 8.0, 11.0, 100.0), bar(11.0, 14.0, 10.0, 13.0, 300.0)]
+    }
+
+    fn sma_over(field: Field) -> Decimal {
+        let mut sma = OnField::new(SimpleMovingAverage::new(2).unwrap(), field);
+        series().iter().map(|di| sma.next(di)).last().unwrap()
+    }
+
+    #[test]
+    fn test_fields() {
+        assert_eq!(sma_over(Field::Open), dec!(10.5));
+        assert_eq!(sma_over(Field::High), dec!(13));
+        assert_eq!(sma_over(Field::Low), dec!(9));
+        assert_eq!(sma_over(Field::Close), dec!(12));
+        assert_eq!(sma_over(Field::Volume), dec!(200));
+        // hl2: 10, 12
+        assert_eq!(sma_over(Field::Hl2), dec!(11));
+        // hlc3: 31 / 3, 37 / 3
+        assert_eq!(sma_over(Field::Hlc3).round_dp(10), dec!(11.3333333333));
+        // ohlc4: 10.25, 12
+        assert_eq!(sma_over(Field::Ohlc4), dec!(11.125));
+    }
+
+    #[test]
+    fn test_boxed_extractor() {
+        struct Range;
+        impl FieldExtractor for Range {
+            fn extract(&self, input: &DataItem) -> Decimal {
+                Decimal::from_f64(input.high() - input.low()).unwrap()
+            }
+        }
+
+        let extractor: Box<dyn FieldExtractor> = Box::new(Range);
+        let mut sma = OnField::new(SimpleMovingAverage::new(2).unwrap(), extractor);
+        let out = series().iter().map(|di| sma.next(di)).last().unwrap();
+        assert_eq!(out, dec!(4));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sma = OnField::new(SimpleMovingAverage::new(2).unwrap(), Field::High);
+        for di in series().iter() {
+            sma.next(di);
+        }
+
+        sma.reset();
+        assert_eq!(sma.next(&series()[1]), dec!(14));
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = OnField::new(SimpleMovingAverage::new(5).unwrap(), Field::Ohlc4);
+        assert_eq!(format!("{}", sma), "SMA(5)[ohlc4]");
+        assert_eq!(sma.period(), 5);
+    }
+}
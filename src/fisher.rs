@@ -0,0 +1,161 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::{Close, Next, Reset};
+
+use crate::math::exp;
+
+/// Inputs beyond this are already within 1e-17 of ±1, and `exp` of twice their size would
+/// overflow a `Decimal` soon after.
+const SATURATION: Decimal = dec!(20);
+
+/// Inverse Fisher transform of another indicator's output.
+///
+/// Compresses the wrapped indicator's values into -1..1, pushing anything away from the
+/// center towards the edges, which turns oscillators such as RSI into sharper signals.
+/// Oscillators are usually rescaled around zero first, see `with_scaling`.
+///
+/// # Formula
+///
+/// IFISH = (e<sup>2x</sup> - 1) / (e<sup>2x</sup> + 1)
+///
+/// Where _x_ = (value - center) * scale, clamped to ±20. Returns `None` while the wrapped
+/// indicator does.
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use technical_analysis::fisher::InverseFisher;
+/// use technical_analysis::rsi::RelativeStrengthIndex;
+///
+/// // RSI 30..70 maps to -2..2 before the transform
+/// let ifish = InverseFisher::with_scaling(RelativeStrengthIndex::new(5).unwrap(), dec!(50), dec!(0.1));
+/// assert_eq!(format!("{}", ifish), "IFISH(RSI(5))");
+/// ```
+#[doc(alias = "IFISH")]
+#[derive(Debug, Clone)]
+pub struct InverseFisher<I> {
+    indicator: I,
+    center: Decimal,
+    scale: Decimal,
+}
+
+impl<I> InverseFisher<I> {
+    pub fn new(indicator: I) -> Self {
+        Self::with_scaling(indicator, dec!(0), dec!(1))
+    }
+
+    /// Transforms `(value - center) * scale` instead of the raw value.
+    pub fn with_scaling(indicator: I, center: Decimal, scale: Decimal) -> Self {
+        Self {
+            indicator,
+            center,
+            scale,
+        }
+    }
+
+    fn transform(&self, value: Decimal) -> Decimal {
+        let x = ((value - self.center) * self.scale).clamp(-SATURATION, SATURATION);
+        let e = exp(dec!(2) * x);
+        (e - dec!(1)) / (e + dec!(1))
+    }
+}
+
+impl<I: Next<Decimal, Output = Option<Decimal>>> Next<Decimal> for InverseFisher<I> {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let value = self.indicator.next(input)?;
+        Some(self.transform(value))
+    }
+}
+
+impl<I: Next<Decimal, Output = Option<Decimal>>, T: Close> Next<&T> for InverseFisher<I> {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl<I: Reset> Reset for InverseFisher<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for InverseFisher<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "IFISH({})", self.indicator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lag::Lag;
+    use crate::rsi::RelativeStrengthIndex;
+    use super::*;
+
+    fn scaled_rsi(period: usize) -> InverseFisher<RelativeStrengthIndex> {
+        InverseFisher::with_scaling(RelativeStrengthIndex::new(period).unwrap(), dec!(50), dec!(0.1))
+    }
+
+    #[test]
+    fn test_neutral_rsi() {
+        let mut ifish = scaled_rsi(3);
+        assert_eq!(ifish.next(dec!(10)), None);
+        // RSI is 50 on a flat input
+        for _ in 0..3 {
+            ifish.next(dec!(10));
+        }
+        assert_eq!(ifish.next(dec!(10)), Some(dec!(0)));
+    }
+
+    #[test]
+    fn test_saturation() {
+        let mut up = scaled_rsi(3);
+        let mut down = scaled_rsi(3);
+        let mut highest = None;
+        let mut lowest = None;
+        for i in 0..10 {
+            highest = up.next(Decimal::from(10 + i));
+            lowest = down.next(Decimal::from(30 - i));
+        }
+        // RSI 100 and 0 become ±5 before the transform
+        assert!(highest.unwrap() > dec!(0.9999));
+        assert!(highest.unwrap() <= dec!(1));
+        assert!(lowest.unwrap() < dec!(-0.9999));
+        assert!(lowest.unwrap() >= dec!(-1));
+    }
+
+    #[test]
+    fn test_large_input() {
+        let mut ifish = InverseFisher::new(Lag::new(1).unwrap());
+        ifish.next(dec!(1000000));
+        assert_eq!(ifish.next(dec!(-1000000)).unwrap().round_dp(12), dec!(1));
+        assert_eq!(ifish.next(dec!(0)).unwrap().round_dp(12), dec!(-1));
+    }
+
+    #[test]
+    fn test_symmetry() {
+        let ifish = InverseFisher::new(Lag::new(1).unwrap());
+        assert_eq!(ifish.transform(dec!(0.5)).round_dp(12), -ifish.transform(dec!(-0.5)).round_dp(12));
+        assert_eq!(ifish.transform(dec!(0.5)).round_dp(9), dec!(0.462117157));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ifish = scaled_rsi(1);
+        ifish.next(dec!(10));
+        assert!(ifish.next(dec!(11)).is_some());
+
+        ifish.reset();
+        assert_eq!(ifish.next(dec!(11)), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", scaled_rsi(14)), "IFISH(RSI(14))");
+    }
+}
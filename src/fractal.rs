@@ -0,0 +1,144 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use ta::{High, Low, Next, Reset};
+
+use crate::model::FractalOutput;
+
+/// Bill Williams fractals.
+///
+/// A fixed five-bar pattern: an up fractal is a bar whose high is above the highs of the
+/// two bars on either side, a down fractal one whose low is below their lows. A bar can be
+/// both. For adjustable windows and swing filtering see
+/// [PivotPoints](../struct.PivotPoints.html).
+///
+/// The two bars after the fractal are needed to confirm it, so each output describes the
+/// bar two bars before the current one. Both fields are `None` until five bars have been
+/// seen.
+#[derive(Debug, Clone)]
+pub struct Fractals {
+    window: VecDeque<(Decimal, Decimal)>,
+}
+
+impl Fractals {
+    pub fn new() -> Self {
+        Self {
+            window: VecDeque::with_capacity(5),
+        }
+    }
+}
+
+impl<T: High + Low> Next<&T> for Fractals {
+    type Output = FractalOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        if self.window.len() == 5 {
+            self.window.pop_front();
+        }
+        self.window.push_back((
+            Decimal::from_f64(input.high()).unwrap(),
+            Decimal::from_f64(input.low()).unwrap(),
+        ));
+        if self.window.len() < 5 {
+            return FractalOutput { up: None, down: None };
+        }
+
+        let (high, low) = self.window[2];
+        let others = || self.window.iter().enumerate().filter(|(i, _)| *i != 2).map(|(_, bar)| bar);
+        FractalOutput {
+            up: others().all(|(other_high, _)| *other_high < high).then_some(high),
+            down: others().all(|(_, other_low)| *other_low > low).then_some(low),
+        }
+    }
+}
+
+impl Reset for Fractals {
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl Default for Fractals {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Fractals {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FRACTALS")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(high: f64, low: f64) -> DataItem {
+        DataItem::builder()
+            .high(high)
+            .low(low)
+            .close(low)
+            .open(low)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    fn run(bars: &[(f64, f64)]) -> Vec<FractalOutput> {
+        let mut fractals = Fractals::new();
+        bars.iter().map(|&(high, low)| fractals.next(&bar(high, low))).collect()
+    }
+
+    #[test]
+    fn test_up_fractal() {
+        let outputs = run(&[(10.0, 9.0), (11.0, 9.5), (13.0, 10.0), (12.0, 9.5), (11.0, 9.2)]);
+        for output in &outputs[..4] {
+            assert_eq!(*output, FractalOutput { up: None, down: None });
+        }
+        assert_eq!(outputs[4], FractalOutput { up: Some(dec!(13)), down: None });
+    }
+
+    #[test]
+    fn test_down_fractal() {
+        let outputs = run(&[(12.0, 10.0), (11.5, 9.0), (11.0, 7.0), (11.2, 8.0), (11.8, 9.5), (12.0, 10.0)]);
+        assert_eq!(outputs[4], FractalOutput { up: None, down: Some(dec!(7)) });
+        assert_eq!(outputs[5], FractalOutput { up: None, down: None });
+    }
+
+    #[test]
+    fn test_both_fractals() {
+        // an outside bar in the middle
+        let outputs = run(&[(10.0, 9.0), (10.0, 9.0), (12.0, 8.0), (10.0, 9.0), (10.0, 9.0)]);
+        assert_eq!(outputs[4], FractalOutput { up: Some(dec!(12)), down: Some(dec!(8)) });
+    }
+
+    #[test]
+    fn test_equal_highs_are_not_fractals() {
+        let outputs = run(&[(10.0, 9.0), (13.0, 9.5), (13.0, 10.0), (12.0, 9.5), (11.0, 9.2)]);
+        assert_eq!(outputs[4].up, None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut fractals = Fractals::new();
+        for (high, low) in [(10.0, 9.0), (11.0, 9.5), (13.0, 10.0), (12.0, 9.5)] {
+            fractals.next(&bar(high, low));
+        }
+        fractals.reset();
+        assert_eq!(fractals.next(&bar(11.0, 9.2)), FractalOutput { up: None, down: None });
+    }
+
+    #[test]
+    fn test_default() {
+        Fractals::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Fractals::new()), "FRACTALS");
+    }
+}
@@ -0,0 +1,129 @@
+//! Gann angles projected from a pivot.
+
+use rust_decimal::Decimal;
+use ta::errors::{Result, TaError};
+
+/// The nine classic fan ratios, as (price units, bars), from the flattest to the steepest.
+pub const FAN_RATIOS: [(u32, u32); 9] = [(1, 8), (1, 4), (1, 3), (1, 2), (1, 1), (2, 1), (3, 1), (4, 1), (8, 1)];
+
+/// Gann fan.
+///
+/// Straight lines through an anchor, typically a pivot, that rise at fixed ratios of price
+/// to time. A ratio of (p, t) climbs _p_ price units every _t_ bars, so the 1x1 line gains
+/// one _unit_ per bar and the 2x1 line two. The lines act as projected support (or, for a
+/// negative _unit_ anchored at a pivot high, resistance).
+///
+/// # Formula
+///
+/// value = start_price + unit * p / t * (index - start_index)
+///
+/// Indices before _start_index_ extend the lines backwards.
+///
+/// # Parameters
+///
+/// * _start_price_ - price of the anchor
+/// * _start_index_ - bar index of the anchor
+/// * _unit_ - price change per bar of the 1x1 line (not 0)
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use technical_analysis::gann::GannFan;
+///
+/// let fan = GannFan::new(dec!(100), 10, dec!(0.5)).unwrap();
+/// assert_eq!(fan.value_at(14, (1, 1)), dec!(102));
+/// assert_eq!(fan.value_at(14, (1, 2)), dec!(101));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GannFan {
+    start_price: Decimal,
+    start_index: usize,
+    unit: Decimal,
+}
+
+impl GannFan {
+    pub fn new(start_price: Decimal, start_index: usize, unit: Decimal) -> Result<Self> {
+        if unit.is_zero() {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            start_price,
+            start_index,
+            unit,
+        })
+    }
+
+    /// Value of the line with the given (price, time) `ratio` at bar `index`.
+    ///
+    /// Panics if the time part of `ratio` is 0.
+    pub fn value_at(&self, index: usize, ratio: (u32, u32)) -> Decimal {
+        assert!(ratio.1 > 0, "Gann ratio needs a non-zero time part");
+        let bars = Decimal::from(index) - Decimal::from(self.start_index);
+        self.start_price + self.unit * Decimal::from(ratio.0) * bars / Decimal::from(ratio.1)
+    }
+
+    /// Values of all the `FAN_RATIOS` lines at bar `index`, in the same order.
+    pub fn fan_at(&self, index: usize) -> [Decimal; 9] {
+        FAN_RATIOS.map(|ratio| self.value_at(index, ratio))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(GannFan::new(dec!(100), 0, dec!(0)).is_err());
+        assert!(GannFan::new(dec!(100), 0, dec!(1)).is_ok());
+        assert!(GannFan::new(dec!(100), 0, dec!(-1)).is_ok());
+    }
+
+    #[test]
+    fn test_one_by_one() {
+        let fan = GannFan::new(dec!(50), 5, dec!(0.25)).unwrap();
+        assert_eq!(fan.value_at(5, (1, 1)), dec!(50));
+        for index in 5..20 {
+            assert_eq!(fan.value_at(index + 1, (1, 1)) - fan.value_at(index, (1, 1)), dec!(0.25));
+        }
+    }
+
+    #[test]
+    fn test_two_by_one() {
+        let fan = GannFan::new(dec!(50), 5, dec!(0.25)).unwrap();
+        for index in 5..20 {
+            assert_eq!(fan.value_at(index + 1, (2, 1)) - fan.value_at(index, (2, 1)), dec!(0.5));
+        }
+        assert_eq!(fan.value_at(9, (1, 2)), dec!(50.5));
+    }
+
+    #[test]
+    fn test_before_anchor() {
+        let fan = GannFan::new(dec!(50), 5, dec!(1)).unwrap();
+        assert_eq!(fan.value_at(2, (1, 1)), dec!(47));
+    }
+
+    #[test]
+    fn test_descending() {
+        let fan = GannFan::new(dec!(50), 0, dec!(-2)).unwrap();
+        assert_eq!(fan.value_at(3, (1, 1)), dec!(44));
+    }
+
+    #[test]
+    fn test_fan_at() {
+        let fan = GannFan::new(dec!(100), 0, dec!(1)).unwrap();
+        let values = fan.fan_at(8);
+        assert_eq!(values[0], dec!(101));
+        assert_eq!(values[4], dec!(108));
+        assert_eq!(values[8], dec!(164));
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_time() {
+        GannFan::new(dec!(100), 0, dec!(1)).unwrap().value_at(1, (1, 0));
+    }
+}
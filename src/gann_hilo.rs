@@ -0,0 +1,176 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use ta::errors::Result;
+use ta::{Close, High, Low, Next, Period, Reset};
+
+use crate::model::{GannHiLoOutput, Trend};
+use crate::simple_moving_average::SimpleMovingAverage;
+
+/// Gann HiLo Activator.
+///
+/// A trend-following line built from a simple moving average of highs and one of lows.
+/// While the close stays above the average of highs the activator is long and follows the
+/// average of lows underneath price; once the close drops below the average of lows it
+/// flips short and follows the average of highs above price.
+///
+/// On the first bar with a full window, the direction is taken from which side of the
+/// two averages' midpoint the close is on.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods for both averages (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct GannHiLoActivator {
+    period: usize,
+    sma_high: SimpleMovingAverage,
+    sma_low: SimpleMovingAverage,
+    count: usize,
+    direction: Option<Trend>,
+}
+
+impl GannHiLoActivator {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            period,
+            sma_high: SimpleMovingAverage::new(period)?,
+            sma_low: SimpleMovingAverage::new(period)?,
+            count: 0,
+            direction: None,
+        })
+    }
+}
+
+impl Period for GannHiLoActivator {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for GannHiLoActivator {
+    type Output = Option<GannHiLoOutput>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let high = self.sma_high.next(Decimal::from_f64(input.high()).unwrap());
+        let low = self.sma_low.next(Decimal::from_f64(input.low()).unwrap());
+        let close = Decimal::from_f64(input.close()).unwrap();
+
+        if self.count < self.period {
+            self.count += 1;
+        }
+        if self.count < self.period {
+            return None;
+        }
+
+        let direction = if close > high {
+            Trend::Up
+        } else if close < low {
+            Trend::Down
+        } else {
+            match self.direction {
+                Some(direction) => direction,
+                None if close * Decimal::from(2) >= high + low => Trend::Up,
+                None => Trend::Down,
+            }
+        };
+        self.direction = Some(direction);
+
+        let value = match direction {
+            Trend::Up => low,
+            Trend::Down => high,
+        };
+        Some(GannHiLoOutput { value, direction })
+    }
+}
+
+impl Reset for GannHiLoActivator {
+    fn reset(&mut self) {
+        self.sma_high.reset();
+        self.sma_low.reset();
+        self.count = 0;
+        self.direction = None;
+    }
+}
+
+impl Default for GannHiLoActivator {
+    fn default() -> Self {
+        Self::new(10).unwrap()
+    }
+}
+
+impl fmt::Display for GannHiLoActivator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HILO({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder()
+            .high(high)
+            .low(low)
+            .close(close)
+            .open(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(GannHiLoActivator::new(0).is_err());
+        assert!(GannHiLoActivator::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut hilo = GannHiLoActivator::new(3).unwrap();
+
+        assert_eq!(hilo.next(&bar(10.0, 9.0, 9.5)), None);
+        assert_eq!(hilo.next(&bar(11.0, 10.0, 10.5)), None);
+
+        let out = hilo.next(&bar(12.0, 11.0, 11.5)).unwrap();
+        assert_eq!(out.direction, Trend::Up);
+        assert_eq!(out.value, dec!(10));
+
+        let out = hilo.next(&bar(13.0, 12.0, 12.5)).unwrap();
+        assert_eq!(out.direction, Trend::Up);
+        assert_eq!(out.value, dec!(11));
+
+        // close breaks below the average of lows
+        let out = hilo.next(&bar(9.0, 8.0, 8.2)).unwrap();
+        assert_eq!(out.direction, Trend::Down);
+        assert_eq!(out.value, dec!(34) / dec!(3));
+
+        // inside the averages: direction is kept
+        let out = hilo.next(&bar(11.0, 9.0, 10.0)).unwrap();
+        assert_eq!(out.direction, Trend::Down);
+        assert_eq!(out.value, dec!(11));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut hilo = GannHiLoActivator::new(2).unwrap();
+        hilo.next(&bar(10.0, 9.0, 9.5));
+        assert!(hilo.next(&bar(11.0, 10.0, 10.5)).is_some());
+
+        hilo.reset();
+        assert_eq!(hilo.next(&bar(10.0, 9.0, 9.5)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        GannHiLoActivator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let hilo = GannHiLoActivator::new(10).unwrap();
+        assert_eq!(format!("{}", hilo), "HILO(10)");
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Open, Reset};
+
+use crate::model::{Gap, GapOutput};
+
+/// Opening gap detector.
+///
+/// Compares each bar's open with the previous bar's close and flags the bar as a gap when
+/// the move between them is larger than _threshold_percent_ of the previous close.
+///
+/// # Formula
+///
+/// gap% = (open - prev close) / prev close * 100
+///
+/// * `Up` when gap% > threshold_percent
+/// * `Down` when gap% < -threshold_percent
+/// * `None` otherwise, and on the first bar
+///
+/// The output's `size` is always open - prev close, even when no gap is flagged.
+///
+/// # Parameters
+///
+/// * _threshold_percent_ - minimum gap, in percent of the previous close (not negative)
+#[derive(Debug, Clone)]
+pub struct GapDetector {
+    threshold_percent: Decimal,
+    prev_close: Option<Decimal>,
+}
+
+impl GapDetector {
+    pub fn new(threshold_percent: Decimal) -> Result<Self> {
+        if threshold_percent < dec!(0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            threshold_percent,
+            prev_close: None,
+        })
+    }
+}
+
+impl<T: Open + Close> Next<&T> for GapDetector {
+    type Output = GapOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let open = Decimal::from_f64_retain(input.open()).unwrap();
+        let prev_close = self.prev_close.replace(Decimal::from_f64_retain(input.close()).unwrap());
+
+        let Some(prev_close) = prev_close else {
+            return GapOutput { gap: Gap::None, size: dec!(0) };
+        };
+        let size = open - prev_close;
+        if prev_close.is_zero() {
+            return GapOutput { gap: Gap::None, size };
+        }
+
+        let percent = size / prev_close * dec!(100);
+        let gap = if percent > self.threshold_percent {
+            Gap::Up
+        } else if percent < -self.threshold_percent {
+            Gap::Down
+        } else {
+            Gap::None
+        };
+        GapOutput { gap, size }
+    }
+}
+
+impl Reset for GapDetector {
+    fn reset(&mut self) {
+        self.prev_close = None;
+    }
+}
+
+impl Default for GapDetector {
+    fn default() -> Self {
+        Self::new(dec!(0.5)).unwrap()
+    }
+}
+
+impl fmt::Display for GapDetector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GAP({}%)", self.threshold_percent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(open: f64, close: f64) -> DataItem {
+        DataItem::builder()
+            .open(open)
+            .high(open.max(close))
+            .low(open.min(close))
+            .close(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(GapDetector::new(dec!(-0.1)).is_err());
+        assert!(GapDetector::new(dec!(0)).is_ok());
+    }
+
+    #[test]
+    fn test_up_gap() {
+        let mut gap = GapDetector::new(dec!(1)).unwrap();
+        assert_eq!(gap.next(&bar(99.0, 100.0)), GapOutput { gap: Gap::None, size: dec!(0) });
+        assert_eq!(gap.next(&bar(102.0, 103.0)), GapOutput { gap: Gap::Up, size: dec!(2) });
+    }
+
+    #[test]
+    fn test_down_gap() {
+        let mut gap = GapDetector::new(dec!(1)).unwrap();
+        gap.next(&bar(99.0, 100.0));
+        assert_eq!(gap.next(&bar(98.5, 97.0)), GapOutput { gap: Gap::Down, size: dec!(-1.5) });
+    }
+
+    #[test]
+    fn test_below_threshold() {
+        let mut gap = GapDetector::new(dec!(1)).unwrap();
+        gap.next(&bar(99.0, 100.0));
+        assert_eq!(gap.next(&bar(100.5, 100.0)), GapOutput { gap: Gap::None, size: dec!(0.5) });
+        assert_eq!(gap.next(&bar(99.0, 99.0)), GapOutput { gap: Gap::None, size: dec!(-1) });
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut gap = GapDetector::default();
+        gap.next(&bar(99.0, 100.0));
+
+        gap.reset();
+        assert_eq!(gap.next(&bar(110.0, 110.0)).gap, Gap::None);
+    }
+
+    #[test]
+    fn test_default() {
+        GapDetector::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", GapDetector::default()), "GAP(0.5%)");
+    }
+}
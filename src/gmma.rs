@@ -0,0 +1,161 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::{Close, Next, Reset};
+
+use crate::ema::ExponentialMovingAverage;
+use crate::model::GmmaOutput;
+
+const SHORT_PERIODS: [usize; 6] = [3, 5, 8, 10, 12, 15];
+const LONG_PERIODS: [usize; 6] = [30, 35, 40, 45, 50, 60];
+
+/// Guppy multiple moving average (GMMA).
+///
+/// Two ribbons of six EMAs each. The short group (3, 5, 8, 10, 12 and 15 bars) follows the
+/// traders, the long group (30, 35, 40, 45, 50 and 60 bars) the investors. Ribbons that
+/// fan out signal a trend the group agrees on, ribbons that bunch up a consolidation, and
+/// [ribbon_compression](#method.ribbon_compression) puts a number on the latter.
+///
+/// The EMAs start from the first input, so every output holds all twelve values.
+#[doc(alias = "GMMA")]
+#[derive(Debug, Clone)]
+pub struct GuppyMultipleMovingAverage {
+    short: [ExponentialMovingAverage; 6],
+    long: [ExponentialMovingAverage; 6],
+    last: Option<(Decimal, GmmaOutput)>,
+}
+
+impl GuppyMultipleMovingAverage {
+    pub fn new() -> Self {
+        Self {
+            short: SHORT_PERIODS.map(|period| ExponentialMovingAverage::new(period).unwrap()),
+            long: LONG_PERIODS.map(|period| ExponentialMovingAverage::new(period).unwrap()),
+            last: None,
+        }
+    }
+
+    /// Spread between the highest and the lowest of the twelve EMAs, as a fraction of the
+    /// last price. Near 0 when the ribbons are bunched together, larger as they fan out.
+    /// `None` before the first input, or when the last price is zero.
+    pub fn ribbon_compression(&self) -> Option<Decimal> {
+        let (price, output) = self.last.as_ref()?;
+        if price.is_zero() {
+            return None;
+        }
+        let values = output.short.iter().chain(output.long.iter());
+        let max = values.clone().max()?;
+        let min = values.min()?;
+        Some((max - min) / price.abs())
+    }
+}
+
+impl Next<Decimal> for GuppyMultipleMovingAverage {
+    type Output = GmmaOutput;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let output = GmmaOutput {
+            short: self.short.each_mut().map(|ema| ema.next(input)),
+            long: self.long.each_mut().map(|ema| ema.next(input)),
+        };
+        self.last = Some((input, output.clone()));
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for GuppyMultipleMovingAverage {
+    type Output = GmmaOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for GuppyMultipleMovingAverage {
+    fn reset(&mut self) {
+        for ema in self.short.iter_mut().chain(self.long.iter_mut()) {
+            ema.reset();
+        }
+        self.last = None;
+    }
+}
+
+impl Default for GuppyMultipleMovingAverage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for GuppyMultipleMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GMMA")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use crate::test_util::assert_full_reset;
+    use super::*;
+
+    #[test]
+    fn test_next() {
+        let mut gmma = GuppyMultipleMovingAverage::new();
+        let out = gmma.next(dec!(10));
+        assert_eq!(out.short, [dec!(10); 6]);
+        assert_eq!(out.long, [dec!(10); 6]);
+
+        // the 3-bar EMA moves by half the change, the 60-bar one by 2/61 of it
+        let out = gmma.next(dec!(20));
+        assert_eq!(out.short[0], dec!(15));
+        assert_eq!(out.long[5], dec!(10) + dec!(20) / dec!(61));
+        assert!(out.short.windows(2).all(|pair| pair[0] > pair[1]));
+        assert!(out.long.windows(2).all(|pair| pair[0] > pair[1]));
+    }
+
+    #[test]
+    fn test_ribbon_compression() {
+        let mut gmma = GuppyMultipleMovingAverage::new();
+        assert_eq!(gmma.ribbon_compression(), None);
+
+        let mut price = dec!(100);
+        for _ in 0..50 {
+            gmma.next(price);
+        }
+        assert_eq!(gmma.ribbon_compression(), Some(dec!(0)));
+
+        // a steady trend fans the ribbons out
+        for _ in 0..40 {
+            price += dec!(2);
+            gmma.next(price);
+        }
+        let diverging = gmma.ribbon_compression().unwrap();
+        assert!(diverging > dec!(0.2));
+
+        // a long consolidation pulls them back together
+        for _ in 0..300 {
+            gmma.next(price);
+        }
+        let converging = gmma.ribbon_compression().unwrap();
+        assert!(converging < dec!(0.001));
+    }
+
+    #[test]
+    fn test_reset() {
+        let series = [dec!(10), dec!(12), dec!(11), dec!(15), dec!(14)];
+        assert_full_reset(GuppyMultipleMovingAverage::new(), &series);
+
+        let mut gmma = GuppyMultipleMovingAverage::new();
+        gmma.next(dec!(10));
+        gmma.reset();
+        assert_eq!(gmma.ribbon_compression(), None);
+    }
+
+    #[test]
+    fn test_default() {
+        GuppyMultipleMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", GuppyMultipleMovingAverage::new()), "GMMA");
+    }
+}
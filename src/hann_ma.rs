@@ -0,0 +1,187 @@
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+/// Hann-window weighted moving average.
+///
+/// Weights the last _period_ values with a Hann (raised cosine) window, so values in the
+/// middle of the window count most and the weights taper smoothly to both edges. The
+/// taper suppresses the ripple a flat SMA window lets through.
+///
+/// # Formula
+///
+/// w<sub>i</sub> = 1 - cos(2π * (i + 1) / (period + 1)), for i = 0 .. period - 1
+///
+/// HANN = Σ(w<sub>i</sub> * p<sub>i</sub>) / Σw<sub>i</sub>
+///
+/// The weights are computed once in `f64` and stored as `Decimal`.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct HannMovingAverage {
+    period: usize,
+    weights: Vec<Decimal>,
+    weight_sum: Decimal,
+    window: VecDeque<Decimal>,
+}
+
+impl HannMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => {
+                let weights: Vec<Decimal> = (0..period)
+                    .map(|i| {
+                        let angle = 2.0 * PI * (i + 1) as f64 / (period + 1) as f64;
+                        Decimal::from_f64(1.0 - angle.cos()).unwrap()
+                    })
+                    .collect();
+                Ok(Self {
+                    period,
+                    weight_sum: weights.iter().sum(),
+                    weights,
+                    window: VecDeque::with_capacity(period),
+                })
+            }
+        }
+    }
+}
+
+impl Period for HannMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for HannMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(input);
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let weighted: Decimal = self
+            .window
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(value, weight)| value * weight)
+            .sum();
+        Some(weighted / self.weight_sum)
+    }
+}
+
+impl<T: Close> Next<&T> for HannMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for HannMovingAverage {
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl Default for HannMovingAverage {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for HannMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HANN({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+    use crate::simple_moving_average::SimpleMovingAverage;
+
+    #[test]
+    fn test_new() {
+        assert!(HannMovingAverage::new(0).is_err());
+        assert!(HannMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        // weights for period 3: 1 - cos(90°), 1 - cos(180°), 1 - cos(270°) = 1, 2, 1
+        let mut hann = HannMovingAverage::new(3).unwrap();
+
+        assert_eq!(hann.next(dec!(4)), None);
+        assert_eq!(hann.next(dec!(8)), None);
+        assert_eq!(hann.next(dec!(4)).unwrap().round_dp(8), dec!(6));
+        assert_eq!(hann.next(dec!(16)).unwrap().round_dp(8), dec!(8));
+    }
+
+    #[test]
+    fn test_constant_input() {
+        let mut hann = HannMovingAverage::new(7).unwrap();
+        let mut out = None;
+        for _ in 0..10 {
+            out = hann.next(dec!(42));
+        }
+        assert_eq!(out.unwrap().round_dp(8), dec!(42));
+    }
+
+    #[test]
+    fn test_smoother_than_sma() {
+        let mut hann = HannMovingAverage::new(5).unwrap();
+        let mut sma = SimpleMovingAverage::new(5).unwrap();
+        let mut hann_prev: Option<Decimal> = None;
+        let mut sma_prev: Option<Decimal> = None;
+        let mut hann_variation = dec!(0);
+        let mut sma_variation = dec!(0);
+
+        for i in 0..30 {
+            let input = if i % 2 == 0 { dec!(11) } else { dec!(9) };
+            let sma_out = sma.next(input);
+            if let Some(hann_out) = hann.next(input) {
+                if let (Some(h), Some(s)) = (hann_prev, sma_prev) {
+                    hann_variation += (hann_out - h).abs();
+                    sma_variation += (sma_out - s).abs();
+                }
+                hann_prev = Some(hann_out);
+                sma_prev = Some(sma_out);
+            }
+        }
+        assert!(hann_variation < sma_variation);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut hann = HannMovingAverage::new(2).unwrap();
+        hann.next(dec!(4));
+        assert!(hann.next(dec!(6)).is_some());
+
+        hann.reset();
+        assert_eq!(hann.next(dec!(99)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        HannMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let hann = HannMovingAverage::new(20).unwrap();
+        assert_eq!(format!("{}", hann), "HANN(20)");
+    }
+}
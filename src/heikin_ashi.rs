@@ -0,0 +1,195 @@
+use std::fmt;
+use ta::{Close, DataItem, High, Low, Next, Open, Reset, Volume};
+
+/// Heikin-Ashi candle transform.
+///
+/// Turns regular OHLC bars into Heikin-Ashi bars, which average out part of the bar-to-bar
+/// noise. Volume is passed through unchanged.
+///
+/// # Formula
+///
+/// * HA close = (open + high + low + close) / 4
+/// * HA open = (HA open<sub>prev</sub> + HA close<sub>prev</sub>) / 2, or (open + close) / 2 on the first bar
+/// * HA high = max(high, HA open, HA close)
+/// * HA low = min(low, HA open, HA close)
+#[derive(Debug, Clone, Default)]
+pub struct HeikinAshi {
+    prev: Option<(f64, f64)>,
+}
+
+impl HeikinAshi {
+    pub fn new() -> Self {
+        Self { prev: None }
+    }
+}
+
+impl<T: Open + High + Low + Close + Volume> Next<&T> for HeikinAshi {
+    type Output = DataItem;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let close = (input.open() + input.high() + input.low() + input.close()) / 4.0;
+        let open = match self.prev {
+            Some((prev_open, prev_close)) => (prev_open + prev_close) / 2.0,
+            None => (input.open() + input.close()) / 2.0,
+        };
+        self.prev = Some((open, close));
+
+        DataItem::builder()
+            .open(open)
+            .high(input.high().max(open).max(close))
+            .low(input.low().min(open).min(close))
+            .close(close)
+            .volume(input.volume())
+            .build()
+            .unwrap()
+    }
+}
+
+impl Reset for HeikinAshi {
+    fn reset(&mut self) {
+        self.prev = None;
+    }
+}
+
+impl fmt::Display for HeikinAshi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HA")
+    }
+}
+
+/// Runs an indicator on Heikin-Ashi candles instead of the raw bars.
+///
+/// Every bar passed to `next` goes through [HeikinAshi](struct.HeikinAshi.html) first and
+/// the resulting candle is handed to the wrapped indicator.
+///
+/// # Example
+///
+/// ```
+/// use technical_analysis::average_true_range::AverageTrueRange;
+/// use technical_analysis::heikin_ashi::HaPipe;
+///
+/// let ha_atr = HaPipe::new(AverageTrueRange::new(14).unwrap());
+/// assert_eq!(format!("{}", ha_atr), "HA(ATR(14))");
+/// ```
+#[derive(Debug, Clone)]
+pub struct HaPipe<I> {
+    heikin_ashi: HeikinAshi,
+    indicator: I,
+}
+
+impl<I> HaPipe<I> {
+    pub fn new(indicator: I) -> Self {
+        Self {
+            heikin_ashi: HeikinAshi::new(),
+            indicator,
+        }
+    }
+}
+
+impl<I, O> Next<&DataItem> for HaPipe<I>
+where
+    I: for<'a> Next<&'a DataItem, Output = O>,
+{
+    type Output = O;
+
+    fn next(&mut self, input: &DataItem) -> Self::Output {
+        let candle = self.heikin_ashi.next(input);
+        self.indicator.next(&candle)
+    }
+}
+
+impl<I: Reset> Reset for HaPipe<I> {
+    fn reset(&mut self) {
+        self.heikin_ashi.reset();
+        self.indicator.reset();
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for HaPipe<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HA({})", self.indicator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::average_true_range::AverageTrueRange;
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder().open(open).high(high).low(low).close(close).volume(100.0).build().unwrap()
+    }
+
+    fn series() -> Vec<DataItem> {
+        vec![
+            bar(10.0, 11.0, 9.0, 10.5),
+            bar(10.5, 12.0, 10.0, 11.5),
+            bar(11.5, 11.75, 9.5, 10.0),
+            bar(10.0, 10.5, 8.0, 8.5),
+            bar(8.5, 10.0, 8.25, 9.75),
+        ]
+    }
+
+    #[test]
+    fn test_heikin_ashi() {
+        let mut ha = HeikinAshi::new();
+
+        let first = ha.next(&bar(10.0, 11.0, 9.0, 10.5));
+        assert_eq!(first.close(), 10.125);
+        assert_eq!(first.open(), 10.25);
+        assert_eq!(first.high(), 11.0);
+        assert_eq!(first.low(), 9.0);
+        assert_eq!(first.volume(), 100.0);
+
+        let second = ha.next(&bar(10.5, 12.0, 10.0, 11.5));
+        assert_eq!(second.open(), (10.25 + 10.125) / 2.0);
+        assert_eq!(second.close(), 11.0);
+        assert_eq!(second.low(), 10.0);
+    }
+
+    #[test]
+    fn test_heikin_ashi_reset() {
+        let mut ha = HeikinAshi::new();
+        ha.next(&bar(10.0, 11.0, 9.0, 10.5));
+
+        ha.reset();
+        assert_eq!(ha.next(&bar(10.0, 11.0, 9.0, 10.5)).open(), 10.25);
+    }
+
+    #[test]
+    fn test_ha_pipe_atr() {
+        let mut ha_atr = HaPipe::new(AverageTrueRange::new(3).unwrap());
+        let mut raw_atr = AverageTrueRange::new(3).unwrap();
+        let mut ha = HeikinAshi::new();
+        let mut manual_atr = AverageTrueRange::new(3).unwrap();
+
+        let mut differs = false;
+        for di in series().iter() {
+            let piped = ha_atr.next(di);
+            let raw = raw_atr.next(di);
+            let manual = manual_atr.next(&ha.next(di));
+
+            assert_eq!(piped, manual);
+            differs |= piped != raw;
+        }
+        assert!(differs);
+    }
+
+    #[test]
+    fn test_ha_pipe_reset() {
+        let mut ha_atr = HaPipe::new(AverageTrueRange::new(2).unwrap());
+        for di in series().iter() {
+            ha_atr.next(di);
+        }
+
+        ha_atr.reset();
+        assert_eq!(ha_atr.next(&series()[0]), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let ha_atr = HaPipe::new(AverageTrueRange::new(14).unwrap());
+        assert_eq!(format!("{}", ha_atr), "HA(ATR(14))");
+        assert_eq!(format!("{}", HeikinAshi::new()), "HA");
+    }
+}
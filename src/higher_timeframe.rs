@@ -0,0 +1,212 @@
+use std::fmt;
+use ta::errors::{Result, TaError};
+use ta::{Close, DataItem, High, Low, Next, Open, Reset, Volume};
+
+/// Runs an indicator on a higher timeframe while reporting after every lower-timeframe bar.
+///
+/// Every _bars_ consecutive input bars are merged into one higher-timeframe bar: the first
+/// open, the highest high, the lowest low, the last close and the summed volume. The
+/// wrapped indicator only advances when a higher-timeframe bar completes, but after each
+/// input bar a clone of it is fed the in-progress bar. Strategies therefore see the value
+/// the indicator would have if the higher-timeframe bar closed now, and on the closing
+/// input bar that value is the final one.
+///
+/// # Parameters
+///
+/// * _indicator_ - indicator fed the higher-timeframe bars
+/// * _bars_ - input bars per higher-timeframe bar (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use ta::{DataItem, Next};
+/// use technical_analysis::higher_timeframe::HigherTimeframe;
+/// use technical_analysis::simple_moving_average::SimpleMovingAverage;
+///
+/// let bar = |close: f64| DataItem::builder().open(close).high(close).low(close).close(close).volume(1.0).build().unwrap();
+///
+/// let mut htf = HigherTimeframe::new(SimpleMovingAverage::new(2).unwrap(), 2).unwrap();
+/// assert_eq!(htf.next(&bar(10.0)), dec!(10));
+/// assert_eq!(htf.next(&bar(12.0)), dec!(12));
+/// assert!(htf.is_bar_closed());
+/// // the second bar in progress, averaged with the first
+/// assert_eq!(htf.next(&bar(16.0)), dec!(14));
+/// ```
+#[doc(alias = "HTF")]
+#[derive(Debug, Clone)]
+pub struct HigherTimeframe<I> {
+    indicator: I,
+    bars: usize,
+    partial: Option<Partial>,
+    count: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Partial {
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+}
+
+impl Partial {
+    fn to_item(&self) -> DataItem {
+        DataItem::builder()
+            .open(self.open)
+            .high(self.high)
+            .low(self.low)
+            .close(self.close)
+            .volume(self.volume)
+            .build()
+            .unwrap()
+    }
+}
+
+impl<I> HigherTimeframe<I> {
+    pub fn new(indicator: I, bars: usize) -> Result<Self> {
+        if bars == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            indicator,
+            bars,
+            partial: None,
+            count: 0,
+        })
+    }
+
+    /// Whether the last input bar completed a higher-timeframe bar.
+    pub fn is_bar_closed(&self) -> bool {
+        self.partial.is_none()
+    }
+
+    /// The indicator as of the last completed higher-timeframe bar.
+    pub fn inner(&self) -> &I {
+        &self.indicator
+    }
+}
+
+impl<I, T, O> Next<&T> for HigherTimeframe<I>
+where
+    I: for<'a> Next<&'a DataItem, Output = O> + Clone,
+    T: Open + High + Low + Close + Volume,
+{
+    type Output = O;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let partial = match self.partial.take() {
+            None => Partial {
+                open: input.open(),
+                high: input.high(),
+                low: input.low(),
+                close: input.close(),
+                volume: input.volume(),
+            },
+            Some(partial) => Partial {
+                high: partial.high.max(input.high()),
+                low: partial.low.min(input.low()),
+                close: input.close(),
+                volume: partial.volume + input.volume(),
+                ..partial
+            },
+        };
+        let item = partial.to_item();
+
+        self.count += 1;
+        if self.count == self.bars {
+            self.count = 0;
+            return self.indicator.next(&item);
+        }
+        self.partial = Some(partial);
+        self.indicator.clone().next(&item)
+    }
+}
+
+impl<I: Reset> Reset for HigherTimeframe<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.partial = None;
+        self.count = 0;
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for HigherTimeframe<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HTF({}, {})", self.indicator, self.bars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use crate::average_true_range::AverageTrueRange;
+    use crate::simple_moving_average::SimpleMovingAverage;
+    use super::*;
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder()
+            .open(open)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(HigherTimeframe::new(SimpleMovingAverage::default(), 0).is_err());
+        assert!(HigherTimeframe::new(SimpleMovingAverage::default(), 1).is_ok());
+    }
+
+    #[test]
+    fn test_in_progress_matches_final() {
+        let mut htf = HigherTimeframe::new(SimpleMovingAverage::new(2).unwrap(), 3).unwrap();
+        let mut reference = SimpleMovingAverage::new(2).unwrap();
+
+        // first higher-timeframe bar closes at 12
+        htf.next(&bar(10.0, 11.0, 9.0, 10.0));
+        htf.next(&bar(10.0, 13.0, 10.0, 13.0));
+        assert!(!htf.is_bar_closed());
+        assert_eq!(htf.next(&bar(13.0, 13.0, 11.0, 12.0)), reference.next(dec!(12)));
+        assert!(htf.is_bar_closed());
+
+        // the second one evolves through 14 and 18 to a close of 16
+        assert_eq!(htf.next(&bar(12.0, 14.0, 12.0, 14.0)), dec!(13));
+        assert_eq!(htf.next(&bar(14.0, 18.0, 14.0, 18.0)), dec!(15));
+        let closed = htf.next(&bar(18.0, 18.0, 15.0, 16.0));
+        assert_eq!(closed, reference.next(dec!(16)));
+        assert_eq!(closed, dec!(14));
+    }
+
+    #[test]
+    fn test_aggregates_range() {
+        let mut htf = HigherTimeframe::new(AverageTrueRange::new(1).unwrap(), 2).unwrap();
+        assert_eq!(htf.next(&bar(10.0, 11.0, 9.0, 10.0)), Some(dec!(2)));
+        // merged bar spans 8 to 12
+        assert_eq!(htf.next(&bar(10.0, 12.0, 8.0, 11.0)), Some(dec!(4)));
+        // new bar: 11 to 11.5 against a previous close of 11
+        assert_eq!(htf.next(&bar(11.0, 11.5, 11.0, 11.5)), Some(dec!(0.5)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut htf = HigherTimeframe::new(SimpleMovingAverage::new(2).unwrap(), 2).unwrap();
+        htf.next(&bar(10.0, 10.0, 10.0, 10.0));
+        htf.next(&bar(20.0, 20.0, 20.0, 20.0));
+        htf.next(&bar(30.0, 30.0, 30.0, 30.0));
+
+        htf.reset();
+        assert!(htf.is_bar_closed());
+        assert_eq!(htf.next(&bar(4.0, 4.0, 4.0, 4.0)), dec!(4));
+    }
+
+    #[test]
+    fn test_display() {
+        let htf = HigherTimeframe::new(SimpleMovingAverage::new(3).unwrap(), 4).unwrap();
+        assert_eq!(format!("{}", htf), "HTF(SMA(3), 4)");
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::{Close, Next, Reset};
+
+use crate::math::atan;
+
+/// Inputs before the first estimate; the deepest history feeding the discriminator.
+const WARMUP: usize = 32;
+
+/// Dominant cycle period, from Ehlers' Hilbert transform homodyne discriminator.
+///
+/// Estimates the length in bars of the cycle currently dominating the price. The smoothed
+/// price is split into in-phase and quadrature components with a four-tap Hilbert
+/// transform, and the phase change between consecutive bars gives the period. Adaptive
+/// indicators use it to tune their lookback to the market's rhythm.
+///
+/// # Formula
+///
+/// Following Ehlers (_Rocket Science for Traders_, ch. 7):
+///
+/// * the price is smoothed with a 4-bar WMA and detrended by the Hilbert transform
+///   HT(x) = (0.0962 * x<sub>t</sub> + 0.5769 * x<sub>t-2</sub> - 0.5769 * x<sub>t-4</sub> - 0.0962 * x<sub>t-6</sub>) * (0.075 * period<sub>t-1</sub> + 0.54)
+/// * the quadrature Q1 is HT(detrender) and the in-phase I1 the detrender 3 bars ago,
+///   each advanced 90° by HT and combined into the smoothed phasor (I2, Q2)
+/// * Re and Im are the smoothed real and imaginary parts of the phasor times the previous
+///   one's conjugate, and period = 2π / atan(Im / Re)
+/// * the period is limited to 0.67-1.5 times the previous one and to 6-50 bars, then
+///   smoothed twice
+///
+/// Returns `None` for the first 32 inputs.
+#[derive(Debug, Clone)]
+pub struct DominantCyclePeriod {
+    prices: VecDeque<Decimal>,
+    smooth: VecDeque<Decimal>,
+    detrender: VecDeque<Decimal>,
+    i1: VecDeque<Decimal>,
+    q1: VecDeque<Decimal>,
+    i2: Decimal,
+    q2: Decimal,
+    re: Decimal,
+    im: Decimal,
+    period: Decimal,
+    smooth_period: Decimal,
+    count: usize,
+}
+
+/// Pushes `value` to the front of `history`, keeping the newest `len` values.
+fn push(history: &mut VecDeque<Decimal>, value: Decimal, len: usize) {
+    history.push_front(value);
+    history.truncate(len);
+}
+
+/// Value `bars` back in `history`, 0 before it is filled.
+fn at(history: &VecDeque<Decimal>, bars: usize) -> Decimal {
+    history.get(bars).copied().unwrap_or(dec!(0))
+}
+
+impl DominantCyclePeriod {
+    pub fn new() -> Self {
+        Self {
+            prices: VecDeque::with_capacity(4),
+            smooth: VecDeque::with_capacity(7),
+            detrender: VecDeque::with_capacity(7),
+            i1: VecDeque::with_capacity(7),
+            q1: VecDeque::with_capacity(7),
+            i2: dec!(0),
+            q2: dec!(0),
+            re: dec!(0),
+            im: dec!(0),
+            period: dec!(0),
+            smooth_period: dec!(0),
+            count: 0,
+        }
+    }
+
+    fn hilbert(&self, history: &VecDeque<Decimal>) -> Decimal {
+        (dec!(0.0962) * at(history, 0) + dec!(0.5769) * at(history, 2)
+            - dec!(0.5769) * at(history, 4)
+            - dec!(0.0962) * at(history, 6))
+            * (dec!(0.075) * self.period + dec!(0.54))
+    }
+}
+
+impl Next<Decimal> for DominantCyclePeriod {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        push(&mut self.prices, input, 4);
+        let smooth = (dec!(4) * at(&self.prices, 0)
+            + dec!(3) * at(&self.prices, 1)
+            + dec!(2) * at(&self.prices, 2)
+            + at(&self.prices, 3))
+            / dec!(10);
+        push(&mut self.smooth, smooth, 7);
+
+        let detrender = self.hilbert(&self.smooth);
+        push(&mut self.detrender, detrender, 7);
+        let q1 = self.hilbert(&self.detrender);
+        push(&mut self.q1, q1, 7);
+        push(&mut self.i1, at(&self.detrender, 3), 7);
+
+        // advance the phase of I1 and Q1 by 90 degrees
+        let ji = self.hilbert(&self.i1);
+        let jq = self.hilbert(&self.q1);
+        let i2 = dec!(0.2) * (at(&self.i1, 0) - jq) + dec!(0.8) * self.i2;
+        let q2 = dec!(0.2) * (q1 + ji) + dec!(0.8) * self.q2;
+
+        // homodyne discriminator
+        self.re = dec!(0.2) * (i2 * self.i2 + q2 * self.q2) + dec!(0.8) * self.re;
+        self.im = dec!(0.2) * (i2 * self.q2 - q2 * self.i2) + dec!(0.8) * self.im;
+        self.i2 = i2;
+        self.q2 = q2;
+
+        let prev_period = self.period;
+        let mut period = prev_period;
+        if !self.im.is_zero() && !self.re.is_zero() {
+            let angle = atan(self.im / self.re);
+            if !angle.is_zero() {
+                period = Decimal::from_f64(2.0 * PI).unwrap() / angle;
+            }
+        }
+        period = period.min(dec!(1.5) * prev_period).max(dec!(0.67) * prev_period);
+        period = period.clamp(dec!(6), dec!(50));
+        self.period = dec!(0.2) * period + dec!(0.8) * prev_period;
+        self.smooth_period = dec!(0.33) * self.period + dec!(0.67) * self.smooth_period;
+
+        if self.count < WARMUP {
+            self.count += 1;
+            return None;
+        }
+        Some(self.smooth_period)
+    }
+}
+
+impl<T: Close> Next<&T> for DominantCyclePeriod {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for DominantCyclePeriod {
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for DominantCyclePeriod {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for DominantCyclePeriod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HT_DCPERIOD")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::sin;
+    use crate::test_util::assert_full_reset;
+    use super::*;
+
+    fn sinusoid(period: usize, len: usize) -> Vec<Decimal> {
+        (0..len)
+            .map(|i| dec!(100) + dec!(5) * sin(Decimal::from_f64(2.0 * PI).unwrap() * Decimal::from(i) / Decimal::from(period)))
+            .collect()
+    }
+
+    fn estimate(period: usize) -> Decimal {
+        let mut dcp = DominantCyclePeriod::new();
+        sinusoid(period, 400).into_iter().map(|value| dcp.next(value)).last().unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_warmup() {
+        let mut dcp = DominantCyclePeriod::new();
+        let values = sinusoid(20, WARMUP + 1);
+        for value in &values[..WARMUP] {
+            assert_eq!(dcp.next(*value), None);
+        }
+        assert!(dcp.next(values[WARMUP]).is_some());
+    }
+
+    #[test]
+    fn test_sinusoid() {
+        for period in [15, 20, 30] {
+            let estimated = estimate(period);
+            let error = (estimated - Decimal::from(period)).abs();
+            assert!(error < dec!(1.5), "period {} estimated as {}", period, estimated);
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut dcp = DominantCyclePeriod::new();
+        for (i, value) in sinusoid(3, 200).into_iter().enumerate() {
+            if let Some(period) = dcp.next(value) {
+                assert!(period >= dec!(0) && period <= dec!(50), "bar {}: {}", i, period);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        assert_full_reset(DominantCyclePeriod::new(), &sinusoid(20, 60));
+    }
+
+    #[test]
+    fn test_default() {
+        DominantCyclePeriod::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", DominantCyclePeriod::new()), "HT_DCPERIOD");
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::math::{ln, sqrt};
+
+/// Smallest chunk the rescaled range is measured over.
+const MIN_CHUNK: usize = 8;
+
+/// Hurst exponent, estimated by rescaled-range (R/S) analysis.
+///
+/// Tells trending series from mean-reverting ones. Around 0.5 the changes behave like a
+/// random walk, above it they tend to persist (trending), below it they tend to reverse
+/// (mean-reverting). The fractal dimension of the series is 2 - H.
+///
+/// # Formula
+///
+/// The last _period_ changes of the input are split into chunks of _period_,
+/// _period_ / 2, _period_ / 4, ... values, down to 8. For each chunk size, R/S is averaged
+/// over the chunks, where for a chunk:
+///
+/// R = max - min of the running sum of (change - mean change)
+///
+/// S = standard deviation of the changes
+///
+/// H is the least-squares slope of ln(R/S) against ln(chunk size).
+///
+/// Chunks with no dispersion are skipped. Returns `None` until _period_ + 1 inputs have
+/// been seen, and when fewer than two chunk sizes are left to fit. Small windows bias the
+/// estimate up, so compare readings taken with the same _period_. Each update is
+/// O(period log period). Logarithms and roots come from the [math](../math/index.html) helpers.
+///
+/// # Parameters
+///
+/// * _period_ - number of changes analysed (integer of at least 16)
+#[doc(alias = "HURST")]
+#[derive(Debug, Clone)]
+pub struct HurstExponent {
+    period: usize,
+    window: VecDeque<Decimal>,
+}
+
+impl HurstExponent {
+    pub fn new(period: usize) -> Result<Self> {
+        if period < 2 * MIN_CHUNK {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            period,
+            window: VecDeque::with_capacity(period + 1),
+        })
+    }
+}
+
+/// Rescaled range of a chunk, `None` when it has no dispersion.
+fn rescaled_range(changes: &[Decimal]) -> Option<Decimal> {
+    let n = Decimal::from(changes.len());
+    let mean = changes.iter().sum::<Decimal>() / n;
+    let (mut cumulative, mut highest, mut lowest, mut sum_sq) = (dec!(0), dec!(0), dec!(0), dec!(0));
+    for change in changes {
+        let deviation = change - mean;
+        cumulative += deviation;
+        highest = highest.max(cumulative);
+        lowest = lowest.min(cumulative);
+        sum_sq += deviation * deviation;
+    }
+    if sum_sq.is_zero() {
+        return None;
+    }
+    Some((highest - lowest) / sqrt(sum_sq / n))
+}
+
+impl Period for HurstExponent {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for HurstExponent {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        if self.window.len() > self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(input);
+        if self.window.len() <= self.period {
+            return None;
+        }
+
+        let changes: Vec<Decimal> = self
+            .window
+            .iter()
+            .zip(self.window.iter().skip(1))
+            .map(|(prev, value)| value - prev)
+            .collect();
+
+        let mut points = Vec::new();
+        let mut size = self.period;
+        while size >= MIN_CHUNK {
+            let ratios: Vec<Decimal> = changes.chunks_exact(size).filter_map(rescaled_range).collect();
+            let sum: Decimal = ratios.iter().sum();
+            if !ratios.is_empty() && sum > dec!(0) {
+                points.push((ln(Decimal::from(size)), ln(sum / Decimal::from(ratios.len()))));
+            }
+            size /= 2;
+        }
+        if points.len() < 2 {
+            return None;
+        }
+
+        let count = Decimal::from(points.len());
+        let mean_x = points.iter().map(|(x, _)| x).sum::<Decimal>() / count;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<Decimal>() / count;
+        let covariance: Decimal = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let variance: Decimal = points.iter().map(|(x, _)| (x - mean_x) * (x - mean_x)).sum();
+        Some(covariance / variance)
+    }
+}
+
+impl<T: Close> Next<&T> for HurstExponent {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for HurstExponent {
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl Default for HurstExponent {
+    fn default() -> Self {
+        Self::new(100).unwrap()
+    }
+}
+
+impl fmt::Display for HurstExponent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HURST({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal::prelude::FromPrimitive;
+    use super::*;
+
+    /// Deterministic noise in [-0.5, 0.5).
+    fn noise(seed: &mut u64) -> f64 {
+        *seed = (*seed * 1103515245 + 12345) % (1 << 31);
+        *seed as f64 / (1u64 << 31) as f64 - 0.5
+    }
+
+    /// Prices whose changes come from `change(i, noise)`.
+    fn prices(change: impl Fn(usize, f64) -> f64) -> Vec<Decimal> {
+        let mut seed = 12345;
+        let mut price = 100.0;
+        (0..=100)
+            .map(|i| {
+                price += change(i, noise(&mut seed));
+                Decimal::from_f64(price).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(HurstExponent::new(15).is_err());
+        assert!(HurstExponent::new(16).is_ok());
+    }
+
+    #[test]
+    fn test_trending_vs_random_walk() {
+        let estimate = |series: Vec<Decimal>| {
+            let mut hurst = HurstExponent::default();
+            let outputs: Vec<Option<Decimal>> = series.into_iter().map(|price| hurst.next(price)).collect();
+            assert!(outputs[..100].iter().all(Option::is_none));
+            outputs[100].unwrap()
+        };
+
+        // changes drift slowly from positive to negative: long persistent runs
+        let trending = estimate(prices(|i, noise| (i as f64 / 15.0).sin() + 0.3 * noise));
+        let random_walk = estimate(prices(|_, noise| noise));
+        let reverting = estimate(prices(|i, noise| if i % 2 == 0 { 1.0 } else { -1.0 } * (1.0 + 0.2 * noise)));
+
+        assert!(trending > dec!(0.9), "{}", trending);
+        assert!(random_walk > dec!(0.4) && random_walk < dec!(0.7), "{}", random_walk);
+        assert!(reverting < dec!(0.3), "{}", reverting);
+    }
+
+    #[test]
+    fn test_no_dispersion() {
+        let mut hurst = HurstExponent::new(16).unwrap();
+        let outputs: Vec<Option<Decimal>> = (0..20).map(|i| hurst.next(Decimal::from(i))).collect();
+        assert!(outputs.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut hurst = HurstExponent::new(16).unwrap();
+        for price in prices(|_, noise| noise).into_iter().take(17) {
+            hurst.next(price);
+        }
+
+        hurst.reset();
+        assert_eq!(hurst.next(dec!(100)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(HurstExponent::default().period(), 100);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", HurstExponent::new(64).unwrap()), "HURST(64)");
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, High, Low, Next, Period, Reset};
+
+use crate::average_true_range::AverageTrueRange;
+use crate::ema::{ExponentialMovingAverage, WarmupExponentialMovingAverage};
+use crate::model::KeltnerChannelOutput;
+
+/// Keltner Channel (KC).
+///
+/// An EMA of the typical price with bands _multiplier_ ATRs above and below it.
+///
+/// # Formula
+///
+/// Average = EMA(period) of (high + low + close) / 3
+///
+/// Upper = Average + ATR(period) * multiplier
+///
+/// Lower = Average - ATR(period) * multiplier
+///
+/// Returns `None` until _period_ bars have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods of the EMA and ATR (integer greater than 0)
+/// * _multiplier_ - number of ATRs (greater than 0)
+#[doc(alias = "KC")]
+#[derive(Debug, Clone)]
+pub struct KeltnerChannel {
+    ema: WarmupExponentialMovingAverage,
+    atr: AverageTrueRange,
+    multiplier: Decimal,
+}
+
+impl KeltnerChannel {
+    pub fn new(period: usize, multiplier: Decimal) -> Result<Self> {
+        if multiplier <= dec!(0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            ema: ExponentialMovingAverage::warmup(period)?,
+            atr: AverageTrueRange::new(period)?,
+            multiplier,
+        })
+    }
+
+    pub fn multiplier(&self) -> Decimal {
+        self.multiplier
+    }
+}
+
+impl Period for KeltnerChannel {
+    fn period(&self) -> usize {
+        self.atr.period()
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for KeltnerChannel {
+    type Output = Option<KeltnerChannelOutput>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let typical = Decimal::from_f64((input.high() + input.low() + input.close()) / 3.0).unwrap();
+        let average = self.ema.next(typical);
+        let atr = self.atr.next(input);
+        let (average, atr) = (average?, atr?);
+        Some(KeltnerChannelOutput {
+            average,
+            upper: average + atr * self.multiplier,
+            lower: average - atr * self.multiplier,
+        })
+    }
+}
+
+impl Reset for KeltnerChannel {
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.atr.reset();
+    }
+}
+
+impl Default for KeltnerChannel {
+    fn default() -> Self {
+        Self::new(20, dec!(2.0)).unwrap()
+    }
+}
+
+impl fmt::Display for KeltnerChannel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KC({}, {})", self.atr.period(), self.multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder().open(close).high(high).low(low).close(close).volume(1.0).build().unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(KeltnerChannel::new(0, dec!(2)).is_err());
+        assert!(KeltnerChannel::new(20, dec!(0)).is_err());
+        assert!(KeltnerChannel::new(20, dec!(2)).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut kc = KeltnerChannel::new(2, dec!(2)).unwrap();
+        assert_eq!(kc.next(&bar(12.0, 9.0, 9.0)), None);
+
+        // EMA of typical prices 10 and 13 = 0.666.. * 13 + 0.333.. * 10 = 12
+        // ATR = mean(3, 6) = 4.5
+        let out = kc.next(&bar(15.0, 12.0, 12.0)).unwrap();
+        assert_eq!(out.average.round_dp(8), dec!(12));
+        assert_eq!(out.upper.round_dp(8), dec!(21));
+        assert_eq!(out.lower.round_dp(8), dec!(3));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut kc = KeltnerChannel::new(2, dec!(2)).unwrap();
+        kc.next(&bar(12.0, 9.0, 9.0));
+        assert!(kc.next(&bar(15.0, 12.0, 12.0)).is_some());
+
+        kc.reset();
+        assert_eq!(kc.next(&bar(12.0, 9.0, 9.0)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        KeltnerChannel::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", KeltnerChannel::new(20, dec!(1.5)).unwrap()), "KC(20, 1.5)");
+    }
+}
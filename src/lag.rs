@@ -0,0 +1,139 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+/// Lag (LAG).
+///
+/// Returns the input from _period_ bars ago, shifting a series to the right. Useful on its
+/// own for plotting displaced series and as a building block for displacement-based
+/// indicators.
+///
+/// Returns `None` until _period_ + 1 values have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of bars to delay by (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use technical_analysis::lag::Lag;
+/// use ta::Next;
+///
+/// let mut lag = Lag::new(2).unwrap();
+/// assert_eq!(lag.next(dec!(1)), None);
+/// assert_eq!(lag.next(dec!(2)), None);
+/// assert_eq!(lag.next(dec!(3)), Some(dec!(1)));
+/// ```
+#[doc(alias = "LAG")]
+#[derive(Debug, Clone)]
+pub struct Lag {
+    period: usize,
+    buffer: VecDeque<Decimal>,
+}
+
+impl Lag {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                buffer: VecDeque::with_capacity(period + 1),
+            }),
+        }
+    }
+}
+
+impl Period for Lag {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for Lag {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        self.buffer.push_back(input);
+        if self.buffer.len() > self.period {
+            self.buffer.pop_front()
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for Lag {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for Lag {
+    fn reset(&mut self) {
+        self.buffer.clear();
+    }
+}
+
+impl Default for Lag {
+    fn default() -> Self {
+        Self::new(1).unwrap()
+    }
+}
+
+impl fmt::Display for Lag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LAG({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(Lag::new(0).is_err());
+        assert!(Lag::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut lag = Lag::new(3).unwrap();
+
+        assert_eq!(lag.next(dec!(1)), None);
+        assert_eq!(lag.next(dec!(2)), None);
+        assert_eq!(lag.next(dec!(3)), None);
+        assert_eq!(lag.next(dec!(4)), Some(dec!(1)));
+        assert_eq!(lag.next(dec!(5)), Some(dec!(2)));
+        assert_eq!(lag.next(dec!(6)), Some(dec!(3)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut lag = Lag::new(1).unwrap();
+        lag.next(dec!(1));
+        assert_eq!(lag.next(dec!(2)), Some(dec!(1)));
+
+        lag.reset();
+        assert_eq!(lag.next(dec!(3)), None);
+        assert_eq!(lag.next(dec!(4)), Some(dec!(3)));
+    }
+
+    #[test]
+    fn test_default() {
+        Lag::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let lag = Lag::new(5).unwrap();
+        assert_eq!(format!("{}", lag), "LAG(5)");
+    }
+}
@@ -0,0 +1,276 @@
+//! Ehlers' Laguerre filter and the RSI built on its state.
+
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Reset};
+
+/// Laguerre filter.
+///
+/// A four-element Laguerre polynomial smoother. The damping factor _gamma_ sets the lag:
+/// 0 passes the price through almost unchanged and values near 1 smooth heavily, with far
+/// fewer bars of data needed than a moving average of similar smoothness.
+///
+/// # Formula
+///
+/// L0<sub>t</sub> = (1 - γ) * x<sub>t</sub> + γ * L0<sub>t-1</sub>
+///
+/// L1<sub>t</sub> = -γ * L0<sub>t</sub> + L0<sub>t-1</sub> + γ * L1<sub>t-1</sub>
+///
+/// L2<sub>t</sub> = -γ * L1<sub>t</sub> + L1<sub>t-1</sub> + γ * L2<sub>t-1</sub>
+///
+/// L3<sub>t</sub> = -γ * L2<sub>t</sub> + L2<sub>t-1</sub> + γ * L3<sub>t-1</sub>
+///
+/// Filter = (L0 + 2 * L1 + 2 * L2 + L3) / 6
+///
+/// All four elements start at the first input.
+///
+/// # Parameters
+///
+/// * _gamma_ - damping factor (at least 0, less than 1)
+#[derive(Debug, Clone)]
+pub struct LaguerreFilter {
+    gamma: Decimal,
+    elements: Option<[Decimal; 4]>,
+}
+
+impl LaguerreFilter {
+    pub fn new(gamma: Decimal) -> Result<Self> {
+        if gamma < dec!(0) || gamma >= dec!(1) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self { gamma, elements: None })
+    }
+
+    /// Advances L0..L3 by one input and returns them.
+    fn update(&mut self, input: Decimal) -> [Decimal; 4] {
+        let gamma = self.gamma;
+        let elements = match self.elements {
+            None => [input; 4],
+            Some([l0, l1, l2, l3]) => {
+                let new_l0 = (dec!(1) - gamma) * input + gamma * l0;
+                let new_l1 = -gamma * new_l0 + l0 + gamma * l1;
+                let new_l2 = -gamma * new_l1 + l1 + gamma * l2;
+                let new_l3 = -gamma * new_l2 + l2 + gamma * l3;
+                [new_l0, new_l1, new_l2, new_l3]
+            }
+        };
+        self.elements = Some(elements);
+        elements
+    }
+}
+
+impl Next<Decimal> for LaguerreFilter {
+    type Output = Decimal;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let [l0, l1, l2, l3] = self.update(input);
+        (l0 + dec!(2) * l1 + dec!(2) * l2 + l3) / dec!(6)
+    }
+}
+
+impl<T: Close> Next<&T> for LaguerreFilter {
+    type Output = Decimal;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for LaguerreFilter {
+    fn reset(&mut self) {
+        self.elements = None;
+    }
+}
+
+impl Default for LaguerreFilter {
+    fn default() -> Self {
+        Self::new(dec!(0.8)).unwrap()
+    }
+}
+
+impl fmt::Display for LaguerreFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LAGUERRE({})", self.gamma)
+    }
+}
+
+/// Laguerre RSI (LRSI).
+///
+/// An RSI computed from the differences between consecutive elements of a
+/// [LaguerreFilter](struct.LaguerreFilter.html) instead of from price changes. It ranges
+/// from 0 to 1 and reacts within a few bars.
+///
+/// # Formula
+///
+/// CU = sum of the positive differences L0 - L1, L1 - L2 and L2 - L3
+///
+/// CD = sum of the magnitudes of the negative ones
+///
+/// LRSI = CU / (CU + CD), or 0.5 when both are 0
+///
+/// # Parameters
+///
+/// * _gamma_ - damping factor (at least 0, less than 1)
+#[doc(alias = "LRSI")]
+#[derive(Debug, Clone)]
+pub struct LaguerreRsi {
+    filter: LaguerreFilter,
+}
+
+impl LaguerreRsi {
+    pub fn new(gamma: Decimal) -> Result<Self> {
+        Ok(Self {
+            filter: LaguerreFilter::new(gamma)?,
+        })
+    }
+}
+
+impl Next<Decimal> for LaguerreRsi {
+    type Output = Decimal;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let elements = self.filter.update(input);
+        let (mut cu, mut cd) = (dec!(0), dec!(0));
+        for pair in elements.windows(2) {
+            let diff = pair[0] - pair[1];
+            if diff > dec!(0) {
+                cu += diff;
+            } else {
+                cd -= diff;
+            }
+        }
+        if (cu + cd).is_zero() {
+            return dec!(0.5);
+        }
+        cu / (cu + cd)
+    }
+}
+
+impl<T: Close> Next<&T> for LaguerreRsi {
+    type Output = Decimal;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for LaguerreRsi {
+    fn reset(&mut self) {
+        self.filter.reset();
+    }
+}
+
+impl Default for LaguerreRsi {
+    fn default() -> Self {
+        Self::new(dec!(0.8)).unwrap()
+    }
+}
+
+impl fmt::Display for LaguerreRsi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LRSI({})", self.filter.gamma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(LaguerreFilter::new(dec!(-0.1)).is_err());
+        assert!(LaguerreFilter::new(dec!(1)).is_err());
+        assert!(LaguerreFilter::new(dec!(0)).is_ok());
+        assert!(LaguerreFilter::new(dec!(0.99)).is_ok());
+        assert!(LaguerreRsi::new(dec!(1)).is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut filter = LaguerreFilter::new(dec!(0.5)).unwrap();
+        assert_eq!(filter.next(dec!(10)), dec!(10));
+        // L0 = 13, L1 = -6.5 + 10 + 5 = 8.5, L2 = -4.25 + 10 + 5 = 10.75, L3 = -5.375 + 10 + 5 = 9.625
+        assert_eq!(filter.next(dec!(16)), (dec!(13) + dec!(17) + dec!(21.5) + dec!(9.625)) / dec!(6));
+    }
+
+    #[test]
+    fn test_zero_gamma() {
+        let mut filter = LaguerreFilter::new(dec!(0)).unwrap();
+        filter.next(dec!(10));
+        // L0 = 16 and every other element is the previous bar's one above it
+        assert_eq!(filter.next(dec!(16)), (dec!(16) + dec!(20) + dec!(20) + dec!(10)) / dec!(6));
+    }
+
+    #[test]
+    fn test_lower_gamma_tracks_faster() {
+        let mut fast = LaguerreFilter::new(dec!(0.2)).unwrap();
+        let mut slow = LaguerreFilter::new(dec!(0.8)).unwrap();
+        for _ in 0..5 {
+            fast.next(dec!(10));
+            slow.next(dec!(10));
+        }
+        for _ in 0..5 {
+            let fast_out = fast.next(dec!(20));
+            let slow_out = slow.next(dec!(20));
+            assert!(fast_out > slow_out);
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let bar = DataItem::builder().high(5.0).low(5.0).close(5.0).open(5.0).volume(0.0).build().unwrap();
+        let mut filter = LaguerreFilter::default();
+        assert_eq!(filter.next(&bar), dec!(5));
+    }
+
+    #[test]
+    fn test_rsi() {
+        let mut rsi = LaguerreRsi::new(dec!(0.5)).unwrap();
+        assert_eq!(rsi.next(dec!(10)), dec!(0.5));
+
+        let mut value = dec!(10);
+        let mut out = dec!(0);
+        for _ in 0..30 {
+            value += dec!(1);
+            out = rsi.next(value);
+            assert!(out >= dec!(0) && out <= dec!(1));
+        }
+        assert_eq!(out, dec!(1));
+
+        for _ in 0..30 {
+            value -= dec!(1);
+            out = rsi.next(value);
+        }
+        assert_eq!(out, dec!(0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut filter = LaguerreFilter::default();
+        filter.next(dec!(10));
+        filter.next(dec!(20));
+        filter.reset();
+        assert_eq!(filter.next(dec!(7)), dec!(7));
+
+        let mut rsi = LaguerreRsi::default();
+        rsi.next(dec!(10));
+        rsi.next(dec!(20));
+        rsi.reset();
+        assert_eq!(rsi.next(dec!(7)), dec!(0.5));
+    }
+
+    #[test]
+    fn test_default() {
+        LaguerreFilter::default();
+        LaguerreRsi::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", LaguerreFilter::default()), "LAGUERRE(0.8)");
+        assert_eq!(format!("{}", LaguerreRsi::default()), "LRSI(0.8)");
+    }
+}
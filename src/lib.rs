@@ -1,10 +1,90 @@
+pub mod adaptive_rsi;
+pub mod adaptive_sma;
 pub mod average_true_range;
+pub mod baseline;
+pub mod bollinger_bands;
+#[cfg(feature = "bench")]
+pub mod benches;
+pub mod camarilla;
+pub mod cci;
+pub mod chaikin_volatility;
+pub mod chain;
+mod checks;
+pub mod choppiness;
+pub mod cumulative_delta;
 pub mod directional_movement_index;
+pub mod disparity;
+pub mod distance_from_extreme;
+pub mod divergence;
+pub mod drawdown;
+pub mod efficiency_ratio;
+pub mod ehlers;
+pub mod elder_impulse;
 pub mod ema;
+pub mod entropy;
+pub mod extremes;
+pub mod fibonacci;
+pub mod field;
+pub mod fisher;
+pub mod fractal;
+pub mod gann;
+pub mod gann_hilo;
+pub mod gap;
+pub mod gmma;
+pub mod hann_ma;
+pub mod heikin_ashi;
+pub mod higher_timeframe;
+pub mod hilbert;
+pub mod hurst;
+pub mod keltner_channel;
+pub mod lag;
+pub mod laguerre;
+pub mod linear_regression;
+pub mod macd;
+pub mod math;
+pub mod median_moving_average;
 pub mod model;
+pub mod moving_average;
+pub mod open_interest;
+pub mod portfolio;
+pub mod position_sizing;
+mod precision;
+pub mod price_oscillator;
+pub mod primitives;
+pub mod projection_bands;
+pub mod pvo;
+pub mod quantile;
+pub mod rainbow;
+pub mod relative_strength;
+pub mod risk;
+pub mod roc;
 pub mod rolling_moving_average;
+pub mod rsi;
+pub mod signal;
 pub mod simple_moving_average;
+pub mod spearman;
+pub mod special_k;
+pub mod squeeze_momentum;
+pub mod standard_deviation;
+pub mod stops;
+pub mod swing_index;
+#[cfg(test)]
+mod test_util;
+pub mod tii;
 mod true_range;
+pub mod tsi;
+pub mod volatility_stop;
+pub mod volume_bars;
+pub mod volume_policy;
+pub mod volume_profile;
+pub mod vwap;
+pub mod vwma;
+pub mod warmup;
+pub mod wavetrend;
+pub mod weighted_moving_average;
+pub mod woodies_cci;
+pub mod zlema;
+pub mod zscore;
 mod pivot;
 
 pub use pivot::*;
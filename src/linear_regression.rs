@@ -0,0 +1,172 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+/// Linear regression (LINREG).
+///
+/// Fits a least-squares line through the last _period_ values and returns the line's value
+/// at the most recent bar. The sums are updated incrementally, so each update is O(1).
+///
+/// # Formula
+///
+/// With x = 0 for the oldest value and x = period - 1 for the newest:
+///
+/// slope = (n * Σxy - Σx * Σy) / (n * Σx<sup>2</sup> - (Σx)<sup>2</sup>)
+///
+/// intercept = (Σy - slope * Σx) / n
+///
+/// LINREG = intercept + slope * (period - 1)
+///
+/// Returns `None` until _period_ values have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 1)
+#[doc(alias = "LINREG")]
+#[derive(Debug, Clone)]
+pub struct LinearRegression {
+    period: usize,
+    window: VecDeque<Decimal>,
+    sum_y: Decimal,
+    sum_xy: Decimal,
+}
+
+impl LinearRegression {
+    pub fn new(period: usize) -> Result<Self> {
+        if period < 2 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            sum_y: dec!(0),
+            sum_xy: dec!(0),
+        })
+    }
+
+    /// Slope of the line fitted by the last `next`, per bar. `None` until _period_ values
+    /// have been seen.
+    pub fn slope(&self) -> Option<Decimal> {
+        if self.window.len() < self.period {
+            return None;
+        }
+        let n = Decimal::from(self.period);
+        let sum_x = n * (n - dec!(1)) / dec!(2);
+        let sum_x_sq = (n - dec!(1)) * n * (dec!(2) * n - dec!(1)) / dec!(6);
+        Some((n * self.sum_xy - sum_x * self.sum_y) / (n * sum_x_sq - sum_x * sum_x))
+    }
+}
+
+impl Period for LinearRegression {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for LinearRegression {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        if self.window.len() == self.period {
+            // every remaining value moves one step left: x drops by one
+            let old = self.window.pop_front().unwrap();
+            self.sum_y -= old;
+            self.sum_xy -= self.sum_y;
+        }
+        self.sum_xy += Decimal::from(self.window.len()) * input;
+        self.sum_y += input;
+        self.window.push_back(input);
+
+        let slope = self.slope()?;
+        let n = Decimal::from(self.period);
+        let intercept = (self.sum_y - slope * n * (n - dec!(1)) / dec!(2)) / n;
+        Some(intercept + slope * (n - dec!(1)))
+    }
+}
+
+impl<T: Close> Next<&T> for LinearRegression {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for LinearRegression {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.sum_y = dec!(0);
+        self.sum_xy = dec!(0);
+    }
+}
+
+impl Default for LinearRegression {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for LinearRegression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LINREG({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(LinearRegression::new(0).is_err());
+        assert!(LinearRegression::new(1).is_err());
+        assert!(LinearRegression::new(2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut linreg = LinearRegression::new(3).unwrap();
+        assert_eq!(linreg.next(dec!(1)), None);
+        assert_eq!(linreg.next(dec!(2)), None);
+        // exact line
+        assert_eq!(linreg.next(dec!(3)), Some(dec!(3)));
+        // 2, 3, 1: slope -0.5, intercept 2.5 -> 1.5
+        assert_eq!(linreg.next(dec!(1)), Some(dec!(1.5)));
+        // 3, 1, 5: slope 1, intercept 2 -> 4
+        assert_eq!(linreg.next(dec!(5)), Some(dec!(4)));
+    }
+
+    #[test]
+    fn test_slope() {
+        let mut linreg = LinearRegression::new(3).unwrap();
+        linreg.next(dec!(2));
+        assert_eq!(linreg.slope(), None);
+        linreg.next(dec!(3));
+        linreg.next(dec!(1));
+        assert_eq!(linreg.slope(), Some(dec!(-0.5)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut linreg = LinearRegression::new(2).unwrap();
+        linreg.next(dec!(1));
+        linreg.next(dec!(2));
+
+        linreg.reset();
+        assert_eq!(linreg.next(dec!(4)), None);
+        assert_eq!(linreg.next(dec!(3)), Some(dec!(3)));
+    }
+
+    #[test]
+    fn test_default() {
+        LinearRegression::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", LinearRegression::new(5).unwrap()), "LINREG(5)");
+    }
+}
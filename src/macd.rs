@@ -0,0 +1,224 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::model::MacdOutput;
+use crate::moving_average::{MovingAverage, MovingAverageKind};
+
+/// Moving average converge divergence (MACD).
+///
+/// The gap between a fast and a slow EMA of price, together with an EMA of that gap (the
+/// signal line) and their difference (the histogram).
+///
+/// # Formula
+///
+/// MACD = EMA<sub>fast</sub>(price) - EMA<sub>slow</sub>(price)
+///
+/// Signal = EMA<sub>signal</sub>(MACD)
+///
+/// Histogram = MACD - Signal
+///
+/// `with_ma_kinds` swaps the EMAs for other moving averages.
+///
+/// # Parameters
+///
+/// * _fast_ - period of the fast EMA (greater than 0, less than _slow_)
+/// * _slow_ - period of the slow EMA
+/// * _signal_ - period of the signal line EMA (greater than 0)
+#[doc(alias = "MACD")]
+#[derive(Debug, Clone)]
+pub struct MovingAverageConvergenceDivergence {
+    fast: MovingAverage,
+    slow: MovingAverage,
+    signal: MovingAverage,
+}
+
+impl MovingAverageConvergenceDivergence {
+    pub fn new(fast: usize, slow: usize, signal: usize) -> Result<Self> {
+        Self::with_ma_kinds(
+            fast,
+            slow,
+            signal,
+            MovingAverageKind::Exponential,
+            MovingAverageKind::Exponential,
+        )
+    }
+
+    /// MACD whose fast and slow lines are `line_kind` averages and whose signal line is a
+    /// `signal_kind` average, e.g. `Simple` for an SMA-based MACD.
+    ///
+    /// Like the EMA, every kind outputs a value from the first input: until an SMA, RMA or
+    /// WMA has seen _period_ inputs it returns their plain mean.
+    pub fn with_ma_kinds(
+        fast: usize,
+        slow: usize,
+        signal: usize,
+        line_kind: MovingAverageKind,
+        signal_kind: MovingAverageKind,
+    ) -> Result<Self> {
+        if fast >= slow {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            fast: MovingAverage::with_partial_warmup(line_kind, fast)?,
+            slow: MovingAverage::with_partial_warmup(line_kind, slow)?,
+            signal: MovingAverage::with_partial_warmup(signal_kind, signal)?,
+        })
+    }
+}
+
+impl Next<Decimal> for MovingAverageConvergenceDivergence {
+    type Output = MacdOutput;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        // with partial warmup the averages never return `None`
+        let macd = self.fast.next(input).unwrap() - self.slow.next(input).unwrap();
+        let signal = self.signal.next(macd).unwrap();
+
+        MacdOutput {
+            macd,
+            signal,
+            histogram: macd - signal,
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for MovingAverageConvergenceDivergence {
+    type Output = MacdOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for MovingAverageConvergenceDivergence {
+    fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.signal.reset();
+    }
+}
+
+impl Default for MovingAverageConvergenceDivergence {
+    fn default() -> Self {
+        Self::new(12, 26, 9).unwrap()
+    }
+}
+
+impl fmt::Display for MovingAverageConvergenceDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MACD({}, {}, {}", self.fast.period(), self.slow.period(), self.signal.period())?;
+        let (line_kind, signal_kind) = (self.fast.kind(), self.signal.kind());
+        if (line_kind, signal_kind) != (MovingAverageKind::Exponential, MovingAverageKind::Exponential) {
+            write!(f, ", {:?}, {:?}", line_kind, signal_kind)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(MovingAverageConvergenceDivergence::new(0, 26, 9).is_err());
+        assert!(MovingAverageConvergenceDivergence::new(26, 12, 9).is_err());
+        assert!(MovingAverageConvergenceDivergence::new(12, 26, 0).is_err());
+        assert!(MovingAverageConvergenceDivergence::new(12, 26, 9).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut macd = MovingAverageConvergenceDivergence::new(1, 3, 1).unwrap();
+
+        let out = macd.next(dec!(10));
+        assert_eq!(out, MacdOutput { macd: dec!(0), signal: dec!(0), histogram: dec!(0) });
+
+        // fast = 20, slow = 0.5 * 20 + 0.5 * 10 = 15
+        let out = macd.next(dec!(20));
+        assert_eq!(out, MacdOutput { macd: dec!(5), signal: dec!(5), histogram: dec!(0) });
+
+        let mut macd = MovingAverageConvergenceDivergence::new(1, 3, 3).unwrap();
+        macd.next(dec!(10));
+        // signal = 0.5 * 5 + 0.5 * 0
+        let out = macd.next(dec!(20));
+        assert_eq!(out.signal, dec!(2.5));
+        assert_eq!(out.histogram, dec!(2.5));
+    }
+
+    #[test]
+    fn test_with_ma_kinds() {
+        use MovingAverageKind::*;
+        assert!(MovingAverageConvergenceDivergence::with_ma_kinds(26, 12, 9, Simple, Simple).is_err());
+        assert!(MovingAverageConvergenceDivergence::with_ma_kinds(12, 26, 0, Simple, Simple).is_err());
+
+        let mut default = MovingAverageConvergenceDivergence::new(3, 6, 2).unwrap();
+        let mut explicit =
+            MovingAverageConvergenceDivergence::with_ma_kinds(3, 6, 2, Exponential, Exponential).unwrap();
+        for kind in [Simple, Wilder, Weighted] {
+            let mut macd = MovingAverageConvergenceDivergence::with_ma_kinds(3, 6, 2, kind, kind).unwrap();
+            // every kind starts from the first input
+            assert_eq!(macd.next(dec!(10)).macd, dec!(0));
+        }
+        for i in 0..20 {
+            let input = Decimal::from((i * 7) % 5);
+            assert_eq!(default.next(input), explicit.next(input));
+        }
+    }
+
+    #[test]
+    fn test_sma_vs_ema_on_ramp() {
+        let mut ema = MovingAverageConvergenceDivergence::new(3, 7, 3).unwrap();
+        let simple = MovingAverageKind::Simple;
+        let mut sma = MovingAverageConvergenceDivergence::with_ma_kinds(3, 7, 3, simple, simple).unwrap();
+
+        // on a ramp of slope 1 both averages of period p lag by (p - 1) / 2, so both lines
+        // head for (7 - 3) / 2 = 2: the SMAs reach it once the slow window is full, the
+        // EMAs only approach it from below
+        let mut previous_ema = dec!(0);
+        for i in 0..40 {
+            let input = Decimal::from(i);
+            let (ema_out, sma_out) = (ema.next(input), sma.next(input));
+            if i >= 6 {
+                assert_eq!(sma_out.macd, dec!(2));
+            }
+            if i >= 8 {
+                assert_eq!(sma_out.histogram, dec!(0));
+            }
+            if i >= 1 {
+                assert!(ema_out.macd > previous_ema);
+                assert!(ema_out.macd < dec!(2));
+                assert!(ema_out.histogram > dec!(0));
+            }
+            previous_ema = ema_out.macd;
+        }
+        assert!(dec!(2) - previous_ema < dec!(0.001));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut macd = MovingAverageConvergenceDivergence::new(1, 3, 1).unwrap();
+        macd.next(dec!(10));
+        macd.next(dec!(20));
+
+        macd.reset();
+        assert_eq!(macd.next(dec!(30)).macd, dec!(0));
+    }
+
+    #[test]
+    fn test_default() {
+        MovingAverageConvergenceDivergence::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let macd = MovingAverageConvergenceDivergence::new(12, 26, 9).unwrap();
+        assert_eq!(format!("{}", macd), "MACD(12, 26, 9)");
+        let (simple, wilder) = (MovingAverageKind::Simple, MovingAverageKind::Wilder);
+        let macd = MovingAverageConvergenceDivergence::with_ma_kinds(12, 26, 9, simple, wilder).unwrap();
+        assert_eq!(format!("{}", macd), "MACD(12, 26, 9, Simple, Wilder)");
+    }
+}
@@ -0,0 +1,131 @@
+//! Transcendental helpers for `Decimal`.
+//!
+//! `rust_decimal` has no logarithms, exponentials, roots or trigonometry without extra
+//! features, so these go through `f64`. Results are accurate to about 15 significant
+//! digits (relative error around 1e-15), which is far below anything an indicator output
+//! can resolve, but they are not exact to the full 28 digits of a `Decimal`.
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+fn via_f64(x: Decimal, f: impl Fn(f64) -> f64) -> Decimal {
+    let value = f(x.to_f64().unwrap());
+    Decimal::from_f64(value).unwrap_or_else(|| panic!("{} is out of Decimal range", value))
+}
+
+/// Natural logarithm.
+///
+/// # Panics
+///
+/// If `x` is not positive.
+pub fn ln(x: Decimal) -> Decimal {
+    assert!(x > Decimal::ZERO, "ln of non-positive value {}", x);
+    via_f64(x, f64::ln)
+}
+
+/// Base 10 logarithm.
+///
+/// # Panics
+///
+/// If `x` is not positive.
+pub fn log10(x: Decimal) -> Decimal {
+    assert!(x > Decimal::ZERO, "log10 of non-positive value {}", x);
+    via_f64(x, f64::log10)
+}
+
+/// Exponential function, e<sup>x</sup>.
+///
+/// # Panics
+///
+/// If the result does not fit in a `Decimal` (roughly `x > 66`).
+pub fn exp(x: Decimal) -> Decimal {
+    via_f64(x, f64::exp)
+}
+
+/// Square root.
+///
+/// # Panics
+///
+/// If `x` is negative.
+pub fn sqrt(x: Decimal) -> Decimal {
+    assert!(x >= Decimal::ZERO, "sqrt of negative value {}", x);
+    via_f64(x, f64::sqrt)
+}
+
+/// Sine of `x` radians.
+pub fn sin(x: Decimal) -> Decimal {
+    via_f64(x, f64::sin)
+}
+
+/// Cosine of `x` radians.
+pub fn cos(x: Decimal) -> Decimal {
+    via_f64(x, f64::cos)
+}
+
+/// Arctangent in radians, between -π/2 and π/2.
+pub fn atan(x: Decimal) -> Decimal {
+    via_f64(x, f64::atan)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    #[test]
+    fn test_ln() {
+        assert_eq!(ln(dec!(2)).round_dp(12), dec!(0.693147180560));
+        assert_eq!(ln(dec!(1)), dec!(0));
+        assert_eq!(ln(dec!(0.5)).round_dp(12), dec!(-0.693147180560));
+    }
+
+    #[test]
+    fn test_log10() {
+        assert_eq!(log10(dec!(1000)).round_dp(12), dec!(3));
+        assert_eq!(log10(dec!(2)).round_dp(12), dec!(0.301029995664));
+        assert_eq!(log10(dec!(0.01)).round_dp(12), dec!(-2));
+    }
+
+    #[test]
+    fn test_exp() {
+        assert_eq!(exp(dec!(0)), dec!(1));
+        assert_eq!(exp(dec!(1)).round_dp(12), dec!(2.718281828459));
+        assert_eq!(exp(dec!(-2)).round_dp(12), dec!(0.135335283237));
+        assert_eq!(exp(ln(dec!(7.5))).round_dp(12), dec!(7.5));
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(sqrt(dec!(2)).round_dp(12), dec!(1.414213562373));
+        assert_eq!(sqrt(dec!(144)), dec!(12));
+        assert_eq!(sqrt(dec!(0)), dec!(0));
+    }
+
+    #[test]
+    fn test_trig() {
+        assert_eq!(sin(dec!(0)), dec!(0));
+        assert_eq!(cos(dec!(0)), dec!(1));
+        assert_eq!(sin(dec!(1)).round_dp(12), dec!(0.841470984808));
+        assert_eq!(cos(dec!(1)).round_dp(12), dec!(0.540302305868));
+        assert_eq!((dec!(4) * atan(dec!(1))).round_dp(12), dec!(3.141592653590));
+        assert_eq!(atan(dec!(-1)), -atan(dec!(1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ln_non_positive() {
+        ln(dec!(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sqrt_negative() {
+        sqrt(dec!(-1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_exp_overflow() {
+        exp(dec!(100));
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::primitives::{order_statistic, sorted_window};
+
+/// Median moving average (MEDMA).
+///
+/// The median of the last _period_ values. Unlike the simple moving average a single
+/// outlier can't drag it around, which makes it a robust choice for spiky data. For an
+/// even _period_ the two middle values are averaged.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use ta::Next;
+/// use technical_analysis::median_moving_average::MedianMovingAverage;
+///
+/// let mut medma = MedianMovingAverage::new(3).unwrap();
+/// assert_eq!(medma.next(dec!(10)), None);
+/// assert_eq!(medma.next(dec!(11)), None);
+/// assert_eq!(medma.next(dec!(1000)), Some(dec!(11)));
+/// ```
+#[doc(alias = "MEDMA")]
+#[derive(Debug, Clone)]
+pub struct MedianMovingAverage {
+    period: usize,
+    window: VecDeque<Decimal>,
+}
+
+impl MedianMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                window: VecDeque::with_capacity(period),
+            }),
+        }
+    }
+}
+
+impl Period for MedianMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for MedianMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(input);
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let sorted = sorted_window(&self.window);
+        Some(order_statistic(&sorted, Decimal::from(self.period - 1) / dec!(2)))
+    }
+}
+
+impl<T: Close> Next<&T> for MedianMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for MedianMovingAverage {
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl Default for MedianMovingAverage {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for MedianMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MEDMA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(MedianMovingAverage::new(0).is_err());
+        assert!(MedianMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next_odd() {
+        let mut medma = MedianMovingAverage::new(3).unwrap();
+        assert_eq!(medma.next(dec!(5)), None);
+        assert_eq!(medma.next(dec!(1)), None);
+        assert_eq!(medma.next(dec!(3)), Some(dec!(3)));
+        assert_eq!(medma.next(dec!(9)), Some(dec!(3)));
+        assert_eq!(medma.next(dec!(7)), Some(dec!(7)));
+    }
+
+    #[test]
+    fn test_next_even() {
+        let mut medma = MedianMovingAverage::new(4).unwrap();
+        medma.next(dec!(4));
+        medma.next(dec!(1));
+        medma.next(dec!(3));
+        assert_eq!(medma.next(dec!(10)), Some(dec!(3.5)));
+    }
+
+    #[test]
+    fn test_repeated_values() {
+        let mut medma = MedianMovingAverage::new(4).unwrap();
+        for value in [dec!(2), dec!(9), dec!(2), dec!(2)] {
+            medma.next(value);
+        }
+        assert_eq!(medma.next(dec!(9)), Some(dec!(5.5)));
+        assert_eq!(medma.next(dec!(2)), Some(dec!(2)));
+    }
+
+    #[test]
+    fn test_outlier_rejection() {
+        let mut medma = MedianMovingAverage::new(5).unwrap();
+        for value in [dec!(10), dec!(11), dec!(10), dec!(12)] {
+            medma.next(value);
+        }
+        assert_eq!(medma.next(dec!(1000)), Some(dec!(11)));
+        assert_eq!(medma.next(dec!(-500)), Some(dec!(11)));
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        fn bar(close: f64) -> DataItem {
+            DataItem::builder().high(close).low(close).close(close).open(close).volume(0.0).build().unwrap()
+        }
+
+        let mut medma = MedianMovingAverage::new(2).unwrap();
+        assert_eq!(medma.next(&bar(4.0)), None);
+        assert_eq!(medma.next(&bar(6.0)), Some(dec!(5)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut medma = MedianMovingAverage::new(2).unwrap();
+        medma.next(dec!(4));
+        assert!(medma.next(dec!(6)).is_some());
+
+        medma.reset();
+        assert_eq!(medma.next(dec!(99)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        MedianMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let medma = MedianMovingAverage::new(9).unwrap();
+        assert_eq!(format!("{}", medma), "MEDMA(9)");
+    }
+}
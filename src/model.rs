@@ -5,4 +5,280 @@ pub struct ADX {
     pub adx_opt: Option<Decimal>,
     pub di_plus_opt: Option<Decimal>,
     pub di_minus_opt: Option<Decimal>,
+    #[serde(default)]
+    pub adxr_opt: Option<Decimal>,
+}
+
+/// Intermediate values of the last [DirectionalMovementIndex](../directional_movement_index/struct.DirectionalMovementIndex.html)
+/// update. Each field is `None` until the bar it depends on has been seen.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq, Default)]
+pub struct DmiComponents {
+    /// High minus previous high.
+    pub up_move: Option<Decimal>,
+    /// Previous low minus low.
+    pub down_move: Option<Decimal>,
+    pub dm_plus: Option<Decimal>,
+    pub dm_minus: Option<Decimal>,
+    pub atr: Option<Decimal>,
+    pub di_plus: Option<Decimal>,
+    pub di_minus: Option<Decimal>,
+    /// |+DI - -DI| / (+DI + -DI) * 100, before ADX smoothing.
+    pub dx: Option<Decimal>,
+}
+
+/// Directional bias read from the sign of `+DI - -DI`.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum Bias {
+    Bullish,
+    Bearish,
+    Neutral,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum Trend {
+    Up,
+    Down,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct GannHiLoOutput {
+    pub value: Decimal,
+    pub direction: Trend,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct PvoOutput {
+    pub pvo: Decimal,
+    pub signal: Decimal,
+    pub histogram: Decimal,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct StopOutput {
+    pub stop: Decimal,
+    pub direction: Trend,
+}
+
+/// Fibonacci levels of a swing. `level_0` is the swing end and `level_1000` its start;
+/// the remaining fields are named after their ratio in thousandths.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct FibLevels {
+    pub level_0: Decimal,
+    pub level_236: Decimal,
+    pub level_382: Decimal,
+    pub level_500: Decimal,
+    pub level_618: Decimal,
+    pub level_786: Decimal,
+    pub level_1000: Decimal,
+    pub level_1272: Decimal,
+    pub level_1618: Decimal,
+}
+
+/// Camarilla levels for the next session, from the previous session's high, low and close.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct CamarillaLevels {
+    /// Breakout level above the range.
+    pub h4: Decimal,
+    /// Reversal level above the close.
+    pub h3: Decimal,
+    /// Reversal level below the close.
+    pub l3: Decimal,
+    /// Breakdown level below the range.
+    pub l4: Decimal,
+}
+
+/// Where a price sits relative to the [CamarillaLevels](struct.CamarillaLevels.html).
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum CamarillaSignal {
+    /// Above H4.
+    BreakoutLong,
+    /// Above H3, up to H4: fade the move back into the range.
+    ReversalShort,
+    /// Between L3 and H3.
+    None,
+    /// Below L3, down to L4: fade the move back into the range.
+    ReversalLong,
+    /// Below L4.
+    BreakoutShort,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct MacdOutput {
+    pub macd: Decimal,
+    pub signal: Decimal,
+    pub histogram: Decimal,
+}
+
+/// Elder Impulse System bar colour.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum Impulse {
+    Green,
+    Red,
+    Blue,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct BollingerBandsOutput {
+    pub average: Decimal,
+    pub upper: Decimal,
+    pub lower: Decimal,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct BollingerPercentBOutput {
+    /// Position of the close within the bands: 0 at the lower band, 1 at the upper.
+    pub percent_b: Decimal,
+    /// Band width as a fraction of the middle band.
+    pub bandwidth: Decimal,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct KeltnerChannelOutput {
+    pub average: Decimal,
+    pub upper: Decimal,
+    pub lower: Decimal,
+}
+
+/// Bollinger Bands position relative to the Keltner Channel.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum Squeeze {
+    /// Both bands inside the channel: volatility is compressed.
+    On,
+    /// Both bands outside the channel: the squeeze has released.
+    Off,
+    /// Neither of the above.
+    None,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct SqueezeMomentumOutput {
+    pub momentum: Decimal,
+    pub squeeze: Squeeze,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct DirectionalAtrOutput {
+    pub up_atr: Option<Decimal>,
+    pub down_atr: Option<Decimal>,
+}
+
+/// Disagreement between consecutive price pivots and the oscillator at the same bars.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum Divergence {
+    /// Lower price low, higher oscillator low.
+    BullishRegular,
+    /// Higher price high, lower oscillator high.
+    BearishRegular,
+    /// Higher price low, lower oscillator low.
+    BullishHidden,
+    /// Lower price high, higher oscillator high.
+    BearishHidden,
+    None,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct VwapBandsOutput {
+    pub vwap: Decimal,
+    pub upper: Decimal,
+    pub lower: Decimal,
+}
+
+/// Direction of the gap between a bar's open and the previous close.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum Gap {
+    Up,
+    Down,
+    None,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct GapOutput {
+    pub gap: Gap,
+    /// Open minus previous close.
+    pub size: Decimal,
+}
+
+/// Direction in which a value crossed a reference line on the latest bar.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum Cross {
+    /// Moved from at or below the line to above it.
+    Up,
+    /// Moved from at or above the line to below it.
+    Down,
+    None,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct DrawdownOutput {
+    /// Decline from the running peak, in percent.
+    pub current_dd: Decimal,
+    /// Largest `current_dd` seen so far.
+    pub max_dd: Decimal,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct ProjectionBandsOutput {
+    pub upper: Decimal,
+    pub lower: Decimal,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct WaveTrendOutput {
+    /// The smoothed channel index (TCI).
+    pub wt1: Decimal,
+    /// 4-bar SMA of `wt1`, the signal line.
+    pub wt2: Decimal,
+}
+
+/// Trend read by [WoodiesCci](../woodies_cci/struct.WoodiesCci.html).
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum WoodiesTrend {
+    Up,
+    Down,
+    /// No trend has been confirmed yet.
+    Neutral,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct WoodiesOutput {
+    pub cci: Decimal,
+    pub turbo_cci: Decimal,
+    pub trend: WoodiesTrend,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct TsiOutput {
+    pub tsi: Decimal,
+    pub signal: Decimal,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct FractalOutput {
+    /// High of the bar two bars back, when it is an up fractal.
+    pub up: Option<Decimal>,
+    /// Low of the bar two bars back, when it is a down fractal.
+    pub down: Option<Decimal>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct GmmaOutput {
+    /// EMAs of the traders' group, fastest first.
+    pub short: [Decimal; 6],
+    /// EMAs of the investors' group, fastest first.
+    pub long: [Decimal; 6],
+}
+
+/// Action suggested by a [SignalAggregator](../signal/struct.SignalAggregator.html).
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum Signal {
+    Buy,
+    Sell,
+    Hold,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct SignalOutput {
+    /// Weighted average of the votes, from -1 to 1.
+    pub score: Decimal,
+    pub signal: Signal,
 }
@@ -0,0 +1,262 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::errors::Result;
+use ta::{Close, Next, Period, Reset};
+
+use crate::ema::{ExponentialMovingAverage, WarmupExponentialMovingAverage};
+use crate::rolling_moving_average::RollingMovingAverage;
+use crate::simple_moving_average::SimpleMovingAverage;
+use crate::weighted_moving_average::WeightedMovingAverage;
+
+/// Kinds of moving average an indicator can be configured with.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MovingAverageKind {
+    Simple,
+    Exponential,
+    /// Wilder's smoothing, see [RollingMovingAverage](../rolling_moving_average/struct.RollingMovingAverage.html).
+    #[default]
+    Wilder,
+    Weighted,
+}
+
+/// A moving average whose kind is picked at runtime.
+///
+/// Whatever the kind, the output is `None` until _period_ inputs have been seen, so the
+/// kinds can be swapped inside composed indicators without changing their warmup.
+/// `with_partial_warmup` builds one that outputs from the first input instead.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct MovingAverage {
+    inner: Inner,
+    #[serde(default)]
+    partial_warmup: bool,
+    /// Number and sum of the warmup inputs, tracked for `partial_warmup`.
+    #[serde(default)]
+    count: usize,
+    #[serde(default)]
+    sum: Decimal,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+enum Inner {
+    Simple(SimpleMovingAverage, usize),
+    Exponential(WarmupExponentialMovingAverage),
+    Wilder(RollingMovingAverage),
+    Weighted(WeightedMovingAverage),
+}
+
+impl MovingAverage {
+    pub fn new(kind: MovingAverageKind, period: usize) -> Result<Self> {
+        let inner = match kind {
+            MovingAverageKind::Simple => Inner::Simple(SimpleMovingAverage::new(period)?, 0),
+            MovingAverageKind::Exponential => Inner::Exponential(ExponentialMovingAverage::warmup(period)?),
+            MovingAverageKind::Wilder => Inner::Wilder(RollingMovingAverage::new(period)?),
+            MovingAverageKind::Weighted => Inner::Weighted(WeightedMovingAverage::new(period)?),
+        };
+        Ok(Self {
+            inner,
+            partial_warmup: false,
+            count: 0,
+            sum: Decimal::ZERO,
+        })
+    }
+
+    /// Moving average that outputs a value from the first input. Until _period_ inputs
+    /// have been seen, the EMA returns its usual value seeded by the first input, as
+    /// [ExponentialMovingAverage](../ema/struct.ExponentialMovingAverage.html) does, and
+    /// the other kinds the mean of the inputs so far, as `WarmupPolicy::Partial`.
+    pub fn with_partial_warmup(kind: MovingAverageKind, period: usize) -> Result<Self> {
+        Ok(Self {
+            partial_warmup: true,
+            ..Self::new(kind, period)?
+        })
+    }
+
+    pub fn kind(&self) -> MovingAverageKind {
+        match self.inner {
+            Inner::Simple(..) => MovingAverageKind::Simple,
+            Inner::Exponential(_) => MovingAverageKind::Exponential,
+            Inner::Wilder(_) => MovingAverageKind::Wilder,
+            Inner::Weighted(_) => MovingAverageKind::Weighted,
+        }
+    }
+}
+
+impl Period for MovingAverage {
+    fn period(&self) -> usize {
+        match &self.inner {
+            Inner::Simple(sma, _) => sma.period(),
+            Inner::Exponential(ema) => ema.period(),
+            Inner::Wilder(rma) => rma.period(),
+            Inner::Weighted(wma) => wma.period(),
+        }
+    }
+}
+
+impl Next<Decimal> for MovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let output = match &mut self.inner {
+            Inner::Simple(sma, count) => {
+                let average = sma.next(input);
+                if *count < sma.period() {
+                    *count += 1;
+                }
+                if *count < sma.period() { None } else { Some(average) }
+            }
+            Inner::Exponential(ema) => ema.next(input),
+            Inner::Wilder(rma) => rma.next(input),
+            Inner::Weighted(wma) => wma.next(input),
+        };
+        if output.is_some() || !self.partial_warmup {
+            return output;
+        }
+
+        self.count += 1;
+        self.sum += input;
+        Some(match &self.inner {
+            Inner::Exponential(ema) => ema.current(),
+            _ => self.sum / Decimal::from(self.count),
+        })
+    }
+}
+
+impl<T: Close> Next<&T> for MovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for MovingAverage {
+    fn reset(&mut self) {
+        match &mut self.inner {
+            Inner::Simple(sma, count) => {
+                sma.reset();
+                *count = 0;
+            }
+            Inner::Exponential(ema) => ema.reset(),
+            Inner::Wilder(rma) => rma.reset(),
+            Inner::Weighted(wma) => wma.reset(),
+        }
+        self.count = 0;
+        self.sum = Decimal::ZERO;
+    }
+}
+
+impl Default for MovingAverage {
+    fn default() -> Self {
+        Self::new(MovingAverageKind::default(), 14).unwrap()
+    }
+}
+
+impl fmt::Display for MovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.inner {
+            Inner::Simple(sma, _) => sma.fmt(f),
+            Inner::Exponential(ema) => ema.fmt(f),
+            Inner::Wilder(rma) => rma.fmt(f),
+            Inner::Weighted(wma) => wma.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    const KINDS: [MovingAverageKind; 4] = [
+        MovingAverageKind::Simple,
+        MovingAverageKind::Exponential,
+        MovingAverageKind::Wilder,
+        MovingAverageKind::Weighted,
+    ];
+
+    #[test]
+    fn test_new() {
+        for kind in KINDS {
+            assert!(MovingAverage::new(kind, 0).is_err());
+            assert_eq!(MovingAverage::new(kind, 1).unwrap().kind(), kind);
+        }
+    }
+
+    #[test]
+    fn test_warmup() {
+        for kind in KINDS {
+            let mut ma = MovingAverage::new(kind, 3).unwrap();
+            assert_eq!(ma.next(dec!(6)), None);
+            assert_eq!(ma.next(dec!(6)), None);
+            assert_eq!(ma.next(dec!(6)), Some(dec!(6)), "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn test_next() {
+        let inputs = [dec!(2), dec!(4), dec!(6), dec!(14)];
+        let outputs: Vec<Option<Decimal>> = KINDS
+            .iter()
+            .map(|kind| {
+                let mut ma = MovingAverage::new(*kind, 3).unwrap();
+                inputs.iter().map(|input| ma.next(*input)).last().unwrap()
+            })
+            .collect();
+
+        assert_eq!(outputs[0], Some(dec!(8)));
+        assert_eq!(outputs[1], Some(dec!(9.25)));
+        assert_eq!(outputs[2], Some(dec!(22) / dec!(3)));
+        assert_eq!(outputs[3], Some(dec!(58) / dec!(6)));
+    }
+
+    #[test]
+    fn test_partial_warmup() {
+        let inputs = [dec!(2), dec!(4), dec!(6), dec!(14), dec!(10)];
+        for kind in KINDS {
+            let mut partial = MovingAverage::with_partial_warmup(kind, 4).unwrap();
+            let mut plain = MovingAverage::new(kind, 4).unwrap();
+            let outputs: Vec<Option<Decimal>> = inputs.iter().map(|input| partial.next(*input)).collect();
+            let expected: Vec<Option<Decimal>> = inputs.iter().map(|input| plain.next(*input)).collect();
+            // the same once warm
+            assert_eq!(outputs[3..], expected[3..], "{:?}", kind);
+            let warmup = if kind == MovingAverageKind::Exponential {
+                // seeded by the first input, k = 0.4
+                [Some(dec!(2)), Some(dec!(2.8)), Some(dec!(4.08))]
+            } else {
+                [Some(dec!(2)), Some(dec!(3)), Some(dec!(4))]
+            };
+            assert_eq!(outputs[..3], warmup, "{:?}", kind);
+        }
+
+        let mut wma = MovingAverage::with_partial_warmup(MovingAverageKind::Weighted, 3).unwrap();
+        wma.next(dec!(2));
+        wma.next(dec!(4));
+        wma.reset();
+        assert_eq!(wma.next(dec!(10)), Some(dec!(10)));
+    }
+
+    #[test]
+    fn test_reset() {
+        for kind in KINDS {
+            let mut ma = MovingAverage::new(kind, 2).unwrap();
+            ma.next(dec!(4));
+            assert!(ma.next(dec!(6)).is_some());
+
+            ma.reset();
+            assert_eq!(ma.next(dec!(99)), None);
+        }
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(MovingAverage::default().kind(), MovingAverageKind::Wilder);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", MovingAverage::new(MovingAverageKind::Simple, 5).unwrap()), "SMA(5)");
+        assert_eq!(format!("{}", MovingAverage::new(MovingAverageKind::Exponential, 5).unwrap()), "EMA(5)");
+        assert_eq!(format!("{}", MovingAverage::new(MovingAverageKind::Wilder, 5).unwrap()), "RMA(5)");
+        assert_eq!(format!("{}", MovingAverage::new(MovingAverageKind::Weighted, 5).unwrap()), "WMA(5)");
+    }
+}
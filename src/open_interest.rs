@@ -0,0 +1,205 @@
+//! Open interest input for futures data, which `ta::DataItem` does not carry.
+
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use ta::errors::Result;
+use ta::{Close, DataItem, High, Low, Next, Open, Period, Reset, Volume};
+
+use crate::ema::{ExponentialMovingAverage, WarmupExponentialMovingAverage};
+
+/// Open interest of a bar, alongside `ta`'s `Close`, `Volume` and friends.
+pub trait OpenInterest {
+    fn open_interest(&self) -> f64;
+}
+
+/// OHLCV bar with open interest.
+///
+/// Implements the `ta` input traits by delegating to the wrapped `DataItem`, so it can be
+/// fed to any indicator taking `&T: Close` and the like. Indicators that take `&DataItem`
+/// directly can be given `item()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataItemWithOi {
+    item: DataItem,
+    open_interest: f64,
+}
+
+impl DataItemWithOi {
+    pub fn new(item: DataItem, open_interest: f64) -> Self {
+        Self { item, open_interest }
+    }
+
+    pub fn item(&self) -> &DataItem {
+        &self.item
+    }
+}
+
+impl OpenInterest for DataItemWithOi {
+    fn open_interest(&self) -> f64 {
+        self.open_interest
+    }
+}
+
+impl Open for DataItemWithOi {
+    fn open(&self) -> f64 {
+        self.item.open()
+    }
+}
+
+impl High for DataItemWithOi {
+    fn high(&self) -> f64 {
+        self.item.high()
+    }
+}
+
+impl Low for DataItemWithOi {
+    fn low(&self) -> f64 {
+        self.item.low()
+    }
+}
+
+impl Close for DataItemWithOi {
+    fn close(&self) -> f64 {
+        self.item.close()
+    }
+}
+
+impl Volume for DataItemWithOi {
+    fn volume(&self) -> f64 {
+        self.item.volume()
+    }
+}
+
+/// Open interest trend.
+///
+/// Smoothed bar-to-bar change in open interest. Positive while positions are being opened,
+/// negative while they are being closed.
+///
+/// # Formula
+///
+/// OIT = EMA(period) of (OI - OI<sub>prev</sub>)
+///
+/// Returns `None` until _period_ changes, i.e. _period_ + 1 bars, have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct OpenInterestTrend {
+    ema: WarmupExponentialMovingAverage,
+    prev: Option<Decimal>,
+}
+
+impl OpenInterestTrend {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            ema: ExponentialMovingAverage::warmup(period)?,
+            prev: None,
+        })
+    }
+}
+
+impl Period for OpenInterestTrend {
+    fn period(&self) -> usize {
+        self.ema.period()
+    }
+}
+
+impl<T: OpenInterest> Next<&T> for OpenInterestTrend {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let open_interest = Decimal::from_f64(input.open_interest()).unwrap();
+        let change = open_interest - self.prev.replace(open_interest)?;
+        self.ema.next(change)
+    }
+}
+
+impl Reset for OpenInterestTrend {
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.prev = None;
+    }
+}
+
+impl Default for OpenInterestTrend {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for OpenInterestTrend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "OIT({})", self.ema.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use crate::simple_moving_average::SimpleMovingAverage;
+    use super::*;
+
+    fn bar(close: f64, open_interest: f64) -> DataItemWithOi {
+        let item = DataItem::builder().open(close).high(close).low(close).close(close).volume(10.0).build().unwrap();
+        DataItemWithOi::new(item, open_interest)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(OpenInterestTrend::new(0).is_err());
+        assert!(OpenInterestTrend::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_data_item_with_oi() {
+        let di = bar(12.5, 1500.0);
+        assert_eq!(di.open_interest(), 1500.0);
+        assert_eq!(di.close(), 12.5);
+        assert_eq!(di.volume(), 10.0);
+
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        assert_eq!(sma.next(&di), dec!(12.5));
+    }
+
+    #[test]
+    fn test_next() {
+        let mut trend = OpenInterestTrend::new(2).unwrap();
+        assert_eq!(trend.next(&bar(10.0, 1000.0)), None);
+        assert_eq!(trend.next(&bar(10.0, 1100.0)), None);
+        // EMA(2) of changes 100, 200
+        assert_eq!(trend.next(&bar(10.0, 1300.0)).unwrap().round_dp(10), dec!(166.6666666667));
+    }
+
+    #[test]
+    fn test_trend_direction() {
+        let mut trend = OpenInterestTrend::new(3).unwrap();
+        let building = [1000.0, 1050.0, 1080.0, 1150.0, 1200.0, 1190.0, 1260.0];
+        let last = building.iter().map(|oi| trend.next(&bar(10.0, *oi))).last().unwrap();
+        assert!(last.unwrap() > dec!(0));
+
+        let unwinding = [1260.0, 1200.0, 1210.0, 1100.0, 1000.0, 950.0];
+        let last = unwinding.iter().map(|oi| trend.next(&bar(10.0, *oi))).last().unwrap();
+        assert!(last.unwrap() < dec!(0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut trend = OpenInterestTrend::new(1).unwrap();
+        trend.next(&bar(10.0, 1000.0));
+        assert!(trend.next(&bar(10.0, 1100.0)).is_some());
+
+        trend.reset();
+        assert_eq!(trend.next(&bar(10.0, 1100.0)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        OpenInterestTrend::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", OpenInterestTrend::new(5).unwrap()), "OIT(5)");
+    }
+}
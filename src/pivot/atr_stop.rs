@@ -0,0 +1,149 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{DataItem, Next, Reset};
+
+use crate::average_true_range::AverageTrueRange;
+use crate::pivot::pivot_points::{PivotPoints, PivotType};
+
+/// Stop below the latest pivot low, padded by a multiple of the ATR.
+///
+/// Runs [PivotPoints](struct.PivotPoints.html) and an
+/// [AverageTrueRange](../average_true_range/struct.AverageTrueRange.html) on the same bars
+/// and places a long stop under the most recently confirmed pivot low.
+///
+/// # Formula
+///
+/// Stop = pivot low - multiplier * ATR(atr_period)
+///
+/// Returns `None` until a pivot low has been confirmed and the ATR is ready. The pivot low
+/// is kept until a newer one is confirmed.
+///
+/// # Parameters
+///
+/// * _lookback_ - number of bars on each side of a pivot
+/// * _num_pivots_ - number of pivots kept by the underlying pivot detector
+/// * _atr_period_ - ATR period (integer greater than 0)
+/// * _multiplier_ - ATR multiple below the pivot (not negative)
+#[derive(Debug, Clone)]
+pub struct PivotAtrStop {
+    pivot_points: PivotPoints,
+    atr: AverageTrueRange,
+    multiplier: Decimal,
+    lookback: usize,
+    pivot_low: Option<Decimal>,
+}
+
+impl PivotAtrStop {
+    pub fn new(lookback: usize, num_pivots: usize, atr_period: usize, multiplier: Decimal) -> Result<Self> {
+        if lookback == 0 || multiplier < dec!(0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            pivot_points: PivotPoints::new(lookback, num_pivots)?,
+            atr: AverageTrueRange::new(atr_period)?,
+            multiplier,
+            lookback,
+            pivot_low: None,
+        })
+    }
+}
+
+impl Next<&DataItem> for PivotAtrStop {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &DataItem) -> Self::Output {
+        self.pivot_points.next(input);
+        if let Some(pivot) = self
+            .pivot_points
+            .confirmed()
+            .iter()
+            .find(|pivot| pivot.pivot_type() == &PivotType::Low)
+        {
+            self.pivot_low = Some(pivot.price());
+        }
+
+        let atr = self.atr.next(input)?;
+        Some(self.pivot_low? - self.multiplier * atr)
+    }
+}
+
+impl Reset for PivotAtrStop {
+    fn reset(&mut self) {
+        self.pivot_points.reset();
+        self.atr.reset();
+        self.pivot_low = None;
+    }
+}
+
+impl Default for PivotAtrStop {
+    fn default() -> Self {
+        Self::new(3, 5, 14, dec!(2)).unwrap()
+    }
+}
+
+impl fmt::Display for PivotAtrStop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PIVOT_ATR_STOP({}, {}, {})", self.lookback, self.atr, self.multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder()
+            .open(close)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(PivotAtrStop::new(0, 5, 14, dec!(2)).is_err());
+        assert!(PivotAtrStop::new(3, 5, 0, dec!(2)).is_err());
+        assert!(PivotAtrStop::new(3, 5, 14, dec!(-1)).is_err());
+        assert!(PivotAtrStop::new(3, 5, 14, dec!(0)).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut stop = PivotAtrStop::new(1, 5, 2, dec!(2)).unwrap();
+        assert_eq!(stop.next(&bar(12.0, 10.0, 11.0)), None);
+        // ATR is ready from here, but no pivot low yet
+        assert_eq!(stop.next(&bar(11.0, 9.0, 10.0)), None);
+        assert_eq!(stop.next(&bar(10.0, 8.0, 9.0)), None);
+        // confirms the pivot low at 8, TR 3 lifts the ATR to 2.5
+        assert_eq!(stop.next(&bar(12.0, 9.0, 11.5)), Some(dec!(3)));
+        // pivot low is kept, TR 2 brings the ATR to 2.25
+        assert_eq!(stop.next(&bar(12.5, 10.5, 12.0)), Some(dec!(3.5)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut stop = PivotAtrStop::new(1, 5, 2, dec!(2)).unwrap();
+        for (high, low, close) in [(12.0, 10.0, 11.0), (11.0, 9.0, 10.0), (10.0, 8.0, 9.0), (12.0, 9.0, 11.5)] {
+            stop.next(&bar(high, low, close));
+        }
+
+        stop.reset();
+        assert_eq!(stop.next(&bar(12.0, 10.0, 11.0)), None);
+        assert_eq!(stop.next(&bar(12.0, 10.0, 11.0)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        PivotAtrStop::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", PivotAtrStop::default()), "PIVOT_ATR_STOP(3, ATR(14), 2)");
+    }
+}
@@ -1,3 +1,11 @@
+pub mod atr_stop;
 pub mod pivot_points;
+pub mod scalar_pivots;
+pub mod structure;
+pub mod timed;
 
-pub use pivot_points::*;
\ No newline at end of file
+pub use atr_stop::*;
+pub use pivot_points::*;
+pub use scalar_pivots::*;
+pub use structure::*;
+pub use timed::*;
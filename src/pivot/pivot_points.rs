@@ -3,24 +3,58 @@ use rust_decimal::Decimal;
 use rust_decimal::prelude::FromPrimitive;
 use rust_decimal_macros::dec;
 use ta::errors::{Result, TaError};
-use ta::{DataItem, High, Low, Next};
+use ta::{DataItem, High, Low, Next, Reset};
 use crate::pivot::pivot_points::PivotType::Unknown;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub struct PivotPoints {
     lookback_period: usize,
     num_pivots: usize,
     pivots: VecDeque<Pivot>,
-    bars: VecDeque<DataItem>,
+    /// High and low of the bars in the detection window.
+    bars: VecDeque<(f64, f64)>,
+    confirmed: Vec<Pivot>,
+    #[serde(default)]
+    mode: PivotMode,
+    #[serde(default)]
+    price_smoothing: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub struct Pivot {
     price: Decimal,
-    pivot_type: PivotType
+    pivot_type: PivotType,
+    #[serde(default)]
+    provisional: bool,
 }
 
-#[derive(Debug, Clone)]
+/// Which pivots `PivotPoints::next` reports.
+///
+/// A pivot is only confirmed _lookback_ bars after it forms. A backtest that treats it as
+/// known at the pivot bar sees the future; `Provisional` instead shows live what could be
+/// a pivot at the current bar, so strategies can react without peeking.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PivotMode {
+    /// Confirmed pivots only.
+    #[default]
+    ConfirmedOnly,
+    /// Confirmed pivots, followed by candidates at the current bar. The current bar is a
+    /// candidate when its high (or low) ends a strictly rising (falling) run over the
+    /// previous _lookback_ bars, the half of the pivot rule that can already be checked.
+    /// A candidate's `pivot_type` is `Unknown` and `is_provisional` is true. Candidates
+    /// are not kept: the ones that hold up are reported again, confirmed, _lookback_ bars
+    /// later.
+    Provisional,
+}
+
+/// Support/resistance zone made of pivots clustered around `price`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    pub price: Decimal,
+    pub strength: usize,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 #[derive(PartialEq)]
 pub enum PivotType {
     High,
@@ -35,11 +69,111 @@ impl PivotPoints {
             _ => Ok(Self {
                 lookback_period,
                 num_pivots,
-                pivots: VecDeque::from(vec![Pivot{price: dec!(0), pivot_type: Unknown}; num_pivots]),
-                bars: VecDeque::from(vec![default_bar()?; lookback_period * 2 + 1]),
+                pivots: VecDeque::from(vec![Pivot::new(dec!(0), Unknown); num_pivots]),
+                bars: VecDeque::from(vec![(0.0, 0.0); lookback_period * 2 + 1]),
+                confirmed: Vec::new(),
+                mode: PivotMode::default(),
+                price_smoothing: 0,
             }),
         }
     }
+
+    pub fn with_mode(lookback_period: usize, num_pivots: usize, mode: PivotMode) -> Result<Self> {
+        Ok(Self {
+            mode,
+            ..Self::new(lookback_period, num_pivots)?
+        })
+    }
+
+    /// Reports each confirmed pivot's price as the mean of the pivot bar's extreme and the
+    /// same extreme of `bars` neighbours on each side, so a single spike wick does not set
+    /// the level on its own. 0, the default, keeps the raw extreme. Fails if `bars` is
+    /// larger than the lookback period, as later neighbours are not known yet.
+    pub fn with_price_smoothing(self, bars: usize) -> Result<Self> {
+        if bars > self.lookback_period {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            price_smoothing: bars,
+            ..self
+        })
+    }
+
+    /// Price of a pivot at the middle of the window, from the bar values picked by `value`.
+    fn pivot_price(&self, value: impl Fn(&(f64, f64)) -> f64) -> Decimal {
+        let bars = self
+            .bars
+            .range(self.lookback_period - self.price_smoothing..=self.lookback_period + self.price_smoothing);
+        let sum: Decimal = bars.map(|bar| Decimal::from_f64(value(bar)).unwrap()).sum();
+        sum / Decimal::from(2 * self.price_smoothing + 1)
+    }
+
+    /// Clusters the stored pivots into support/resistance levels, lowest price first.
+    ///
+    /// Pivots are grouped while each one stays within `tolerance` of the lowest pivot in its
+    /// group. A level's price is the mean of its pivots and its strength is how many pivots
+    /// it contains.
+    pub fn levels(&self, tolerance: Decimal) -> Vec<Level> {
+        let mut prices: Vec<Decimal> = self
+            .pivots
+            .iter()
+            .filter(|pivot| pivot.pivot_type != Unknown)
+            .map(|pivot| pivot.price)
+            .collect();
+        prices.sort();
+
+        let mut clusters: Vec<Vec<Decimal>> = Vec::new();
+        for price in prices {
+            match clusters.last_mut() {
+                Some(cluster) if price - cluster[0] <= tolerance => cluster.push(price),
+                _ => clusters.push(vec![price]),
+            }
+        }
+
+        clusters
+            .into_iter()
+            .map(|cluster| Level {
+                price: cluster.iter().sum::<Decimal>() / Decimal::from(cluster.len()),
+                strength: cluster.len(),
+            })
+            .collect()
+    }
+
+    /// Pivots confirmed by the most recent call to `next`, oldest first.
+    pub fn confirmed(&self) -> &[Pivot] {
+        &self.confirmed
+    }
+
+    /// Feeds a bar like `next`, but returns only the pivot confirmed on this bar, or `None`
+    /// when no new pivot formed. If the bar confirms both a high and a low, the low is
+    /// returned; `confirmed` still lists both.
+    pub fn next_event(&mut self, input: &DataItem) -> Option<Pivot> {
+        self.next(input);
+        self.confirmed.last().cloned()
+    }
+}
+
+impl Pivot {
+    pub(crate) fn new(price: Decimal, pivot_type: PivotType) -> Self {
+        Self { price, pivot_type, provisional: false }
+    }
+
+    fn provisional(price: f64) -> Self {
+        Self { price: Decimal::from_f64(price).unwrap(), pivot_type: Unknown, provisional: true }
+    }
+
+    pub fn price(&self) -> Decimal {
+        self.price
+    }
+
+    pub fn pivot_type(&self) -> &PivotType {
+        &self.pivot_type
+    }
+
+    /// Whether this is an unconfirmed candidate, see [PivotMode](enum.PivotMode.html).
+    pub fn is_provisional(&self) -> bool {
+        self.provisional
+    }
 }
 
 impl Next<&DataItem> for PivotPoints {
@@ -47,50 +181,75 @@ impl Next<&DataItem> for PivotPoints {
 
     fn next(&mut self, input: &DataItem) -> Self::Output {
         self.bars.pop_front();
-        self.bars.push_back(input.clone());
+        self.bars.push_back((input.high(), input.low()));
+        self.confirmed.clear();
 
-        if let Some(ph) = find_pivot_high(self.lookback_period, self.bars.clone()) {
+        if find_pivot_high(self.lookback_period, |i| self.bars[i].0).is_some() {
+            let pivot = Pivot::new(self.pivot_price(|bar| bar.0), PivotType::High);
             self.pivots.pop_front();
-            self.pivots.push_back(Pivot{price: Decimal::from_f64(ph).unwrap(), pivot_type: PivotType::High});
+            self.pivots.push_back(pivot.clone());
+            self.confirmed.push(pivot);
         }
 
-        if let Some(pl) = find_pivot_low(self.lookback_period, self.bars.clone()) {
+        if find_pivot_low(self.lookback_period, |i| self.bars[i].1).is_some() {
+            let pivot = Pivot::new(self.pivot_price(|bar| bar.1), PivotType::Low);
             self.pivots.pop_front();
-            self.pivots.push_back(Pivot{price: Decimal::from_f64(pl).unwrap(), pivot_type: PivotType::Low});
+            self.pivots.push_back(pivot.clone());
+            self.confirmed.push(pivot);
         }
-        
-        self.pivots.clone()
+
+        let mut pivots = self.pivots.clone();
+        if self.mode == PivotMode::Provisional {
+            // the left half of the pivot rule, ending at the current bar
+            let rising = (self.lookback_period..2 * self.lookback_period).all(|i| self.bars[i].0 < self.bars[i + 1].0);
+            let falling = (self.lookback_period..2 * self.lookback_period).all(|i| self.bars[i].1 > self.bars[i + 1].1);
+            if rising {
+                pivots.push_back(Pivot::provisional(input.high()));
+            }
+            if falling {
+                pivots.push_back(Pivot::provisional(input.low()));
+            }
+        }
+        pivots
     }
 }
 
-fn find_pivot_high(period: usize, b: VecDeque<DataItem>) -> Option<f64> {
+pub(crate) fn find_pivot_high<V: PartialOrd>(period: usize, value: impl Fn(usize) -> V) -> Option<V> {
     for i in 0..2 * period {
         if i >= period {
-            if b[i].high() <= b[i+1].high() {
+            if value(i) <= value(i+1) {
                 return None;
             };
         } else {
-            if b[i].high() >= b[i+1].high() {
+            if value(i) >= value(i+1) {
                 return None;
             };
         }
     }
-    Some(b[period].high())
+    Some(value(period))
 }
 
-fn find_pivot_low(period: usize, b: VecDeque<DataItem>) -> Option<f64> {
+pub(crate) fn find_pivot_low<V: PartialOrd>(period: usize, value: impl Fn(usize) -> V) -> Option<V> {
     for i in 0..2 * period {
         if i >= period {
-            if b[i].low() >= b[i+1].low() {
+            if value(i) >= value(i+1) {
                 return None;
             };
         } else {
-            if b[i].low() <= b[i+1].low() {
+            if value(i) <= value(i+1) {
                 return None;
             };
         }
     }
-    Some(b[period].low())
+    Some(value(period))
+}
+
+impl Reset for PivotPoints {
+    fn reset(&mut self) {
+        self.pivots = VecDeque::from(vec![Pivot::new(dec!(0), Unknown); self.num_pivots]);
+        self.bars = VecDeque::from(vec![(0.0, 0.0); self.lookback_period * 2 + 1]);
+        self.confirmed.clear();
+    }
 }
 
 impl Default for PivotPoints {
@@ -99,16 +258,6 @@ impl Default for PivotPoints {
     }
 }
 
-fn default_bar() -> Result<DataItem> {
-    DataItem::builder()
-        .open(0.0)
-        .close(0.0)
-        .high(0.0)
-        .low(0.0)
-        .volume(0.0)
-        .build()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,6 +303,118 @@ mod tests {
         assert_eq!(out.back().unwrap().pivot_type, High);
     }
 
+    #[test]
+    fn test_next_event() {
+        let mut pp = PivotPoints::new(2, 3).unwrap();
+
+        let events: Vec<Option<Pivot>> = [0.1, 0.2, 0.3, 0.2, 0.1, 0.1, 0.1]
+            .iter()
+            .map(|high| pp.next_event(&setup_di_highs(*high)))
+            .collect();
+
+        assert_eq!(events.iter().filter(|event| event.is_some()).count(), 1);
+        let pivot = events[4].as_ref().unwrap();
+        assert_eq!(pivot.price(), dec!(0.3));
+        assert_eq!(pivot.pivot_type(), &High);
+    }
+
+    #[test]
+    fn test_provisional() {
+        let highs = [0.1, 0.2, 0.3, 0.2, 0.1];
+        let mut confirmed_only = PivotPoints::new(2, 3).unwrap();
+        let mut provisional = PivotPoints::with_mode(2, 3, PivotMode::Provisional).unwrap();
+
+        let outputs: Vec<(VecDeque<Pivot>, VecDeque<Pivot>)> = highs
+            .iter()
+            .map(|high| (confirmed_only.next(&setup_di_highs(*high)), provisional.next(&setup_di_highs(*high))))
+            .collect();
+
+        // the 0.3 high is a candidate on its own bar, two bars before it is confirmed
+        let (plain, live) = &outputs[2];
+        assert_eq!(plain.len(), 3);
+        assert!(plain.iter().all(|pivot| !pivot.is_provisional()));
+        assert_eq!(live.len(), 4);
+        let candidate = live.back().unwrap();
+        assert!(candidate.is_provisional());
+        assert_eq!(candidate.price(), dec!(0.3));
+        assert_eq!(candidate.pivot_type(), &Unknown);
+
+        // falling bars are candidate lows instead
+        let candidate = outputs[4].1.back().unwrap();
+        assert!(candidate.is_provisional());
+        assert_eq!(candidate.price(), dec!(0.1));
+
+        // on confirmation both modes report the same confirmed pivot
+        let (plain, live) = &outputs[4];
+        let confirmed: Vec<&Pivot> = live.iter().filter(|pivot| !pivot.is_provisional()).collect();
+        assert_eq!(confirmed.len(), plain.len());
+        assert_eq!(plain.back().unwrap().price(), dec!(0.3));
+        assert_eq!(confirmed.last().unwrap().price(), dec!(0.3));
+        assert_eq!(confirmed.last().unwrap().pivot_type(), &High);
+        assert_eq!(provisional.confirmed().len(), 1);
+    }
+
+    #[test]
+    fn test_price_smoothing() {
+        assert!(PivotPoints::new(2, 3).unwrap().with_price_smoothing(3).is_err());
+
+        let highs = [1.0, 2.0, 9.0, 3.0, 1.5];
+        let mut raw = PivotPoints::new(2, 3).unwrap();
+        let mut smoothed = PivotPoints::new(2, 3).unwrap().with_price_smoothing(1).unwrap();
+        for high in highs {
+            raw.next(&setup_di_highs(high));
+            smoothed.next(&setup_di_highs(high));
+        }
+
+        // the 9.0 spike sets the raw price, the smoothed one averages it with 2.0 and 3.0
+        assert_eq!(raw.confirmed()[0].price(), dec!(9));
+        assert_eq!(smoothed.confirmed()[0].price(), dec!(14) / dec!(3));
+        assert_eq!(smoothed.confirmed()[0].pivot_type(), &High);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut pp = PivotPoints::new(1, 4).unwrap();
+        for price in [1.0, 3.0, 2.0, 4.0] {
+            pp.next(&setup_di_highs(price));
+        }
+
+        let json = serde_json::to_string(&pp).unwrap();
+        let mut restored: PivotPoints = serde_json::from_str(&json).unwrap();
+        for price in [1.0, 5.0, 4.0, 6.0, 2.0] {
+            let di = setup_di_highs(price);
+            let (expected, actual) = (pp.next(&di), restored.next(&di));
+            assert_eq!(
+                actual.iter().map(|p| (p.price, p.pivot_type.clone())).collect::<Vec<_>>(),
+                expected.iter().map(|p| (p.price, p.pivot_type.clone())).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_levels() {
+        let mut pp = PivotPoints::new(1, 10).unwrap();
+        for price in [5.0, 10.0, 5.05, 10.1, 5.0, 9.95, 7.0] {
+            pp.next(&setup_di_highs(price));
+        }
+
+        let levels = pp.levels(dec!(0.2));
+        assert_eq!(levels.len(), 2);
+        assert_eq!(levels[0], Level { price: dec!(5.025), strength: 2 });
+        assert_eq!(levels[1].price.round_dp(4), dec!(10.0167));
+        assert_eq!(levels[1].strength, 3);
+
+        let levels = pp.levels(dec!(0.01));
+        assert_eq!(levels.len(), 5);
+        assert!(levels.iter().all(|level| level.strength == 1));
+    }
+
+    #[test]
+    fn test_levels_empty() {
+        let pp = PivotPoints::new(2, 3).unwrap();
+        assert!(pp.levels(dec!(1)).is_empty());
+    }
+
     #[test]
     fn test_pivot_points_low() {
         let mut pp = PivotPoints::new(2, 3).unwrap();
@@ -0,0 +1,168 @@
+use std::collections::VecDeque;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Next, Reset};
+use crate::pivot::pivot_points::{find_pivot_high, find_pivot_low, Pivot, PivotType};
+
+/// Pivot detection on a single stream of values.
+///
+/// Works like [PivotPoints](struct.PivotPoints.html), but instead of looking at the highs
+/// and lows of a bar it finds local maxima and minima of one series, such as closes or
+/// the output of another indicator. A value is a pivot high when it is strictly greater than
+/// the _lookback_ values on each side of it, and a pivot low when strictly smaller; it is
+/// confirmed _lookback_ values later.
+#[derive(Debug, Clone)]
+pub struct ScalarPivots {
+    lookback_period: usize,
+    num_pivots: usize,
+    pivots: VecDeque<Pivot>,
+    values: VecDeque<Decimal>,
+    confirmed: Vec<Pivot>,
+}
+
+impl ScalarPivots {
+    pub fn new(lookback_period: usize, num_pivots: usize) -> Result<Self> {
+        match (lookback_period, num_pivots) {
+            (0, 0) => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                lookback_period,
+                num_pivots,
+                pivots: empty_pivots(num_pivots),
+                values: VecDeque::with_capacity(lookback_period * 2 + 1),
+                confirmed: Vec::new(),
+            }),
+        }
+    }
+
+    /// Pivots confirmed by the most recent call to `next`, oldest first.
+    pub fn confirmed(&self) -> &[Pivot] {
+        &self.confirmed
+    }
+}
+
+impl Next<Decimal> for ScalarPivots {
+    type Output = VecDeque<Pivot>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        if self.values.len() == self.lookback_period * 2 + 1 {
+            self.values.pop_front();
+        }
+        self.values.push_back(input);
+        self.confirmed.clear();
+        // nothing is compared until both sides of the middle value are real inputs
+        if self.values.len() < self.lookback_period * 2 + 1 {
+            return self.pivots.clone();
+        }
+
+        if let Some(ph) = find_pivot_high(self.lookback_period, |i| self.values[i]) {
+            self.confirmed.push(Pivot::new(ph, PivotType::High));
+        }
+
+        if let Some(pl) = find_pivot_low(self.lookback_period, |i| self.values[i]) {
+            self.confirmed.push(Pivot::new(pl, PivotType::Low));
+        }
+
+        for pivot in self.confirmed.iter() {
+            self.pivots.pop_front();
+            self.pivots.push_back(pivot.clone());
+        }
+
+        self.pivots.clone()
+    }
+}
+
+impl Reset for ScalarPivots {
+    fn reset(&mut self) {
+        self.pivots = empty_pivots(self.num_pivots);
+        self.values.clear();
+        self.confirmed.clear();
+    }
+}
+
+impl Default for ScalarPivots {
+    fn default() -> Self {
+        Self::new(3, 5).unwrap()
+    }
+}
+
+fn empty_pivots(num_pivots: usize) -> VecDeque<Pivot> {
+    VecDeque::from(vec![Pivot::new(dec!(0), PivotType::Unknown); num_pivots])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(ScalarPivots::new(0, 0).is_err());
+        assert!(ScalarPivots::new(2, 3).is_ok());
+    }
+
+    #[test]
+    fn test_zig_zag() {
+        let mut sp = ScalarPivots::new(1, 4).unwrap();
+        let mut out = VecDeque::new();
+        for value in [dec!(1), dec!(3), dec!(2), dec!(4), dec!(1), dec!(5), dec!(4)] {
+            out = sp.next(value);
+        }
+
+        let pivots: Vec<(Decimal, PivotType)> = out.iter().map(|p| (p.price(), p.pivot_type().clone())).collect();
+        assert_eq!(
+            pivots,
+            vec![
+                (dec!(2), PivotType::Low),
+                (dec!(4), PivotType::High),
+                (dec!(1), PivotType::Low),
+                (dec!(5), PivotType::High),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_confirmation_delay() {
+        let mut sp = ScalarPivots::new(2, 3).unwrap();
+        sp.next(dec!(0.1));
+        sp.next(dec!(0.2));
+        sp.next(dec!(0.3));
+        let out = sp.next(dec!(0.2));
+        assert_eq!(out.back().unwrap().pivot_type(), &PivotType::Unknown);
+        assert!(sp.confirmed().is_empty());
+
+        let out = sp.next(dec!(0.1));
+        assert_eq!(out.back().unwrap().price(), dec!(0.3));
+        assert_eq!(out.back().unwrap().pivot_type(), &PivotType::High);
+        assert_eq!(sp.confirmed().len(), 1);
+    }
+
+    #[test]
+    fn test_negative_series() {
+        let mut sp = ScalarPivots::new(1, 2).unwrap();
+        sp.next(dec!(-5));
+        let out = sp.next(dec!(-3));
+        assert!(out.iter().all(|p| p.pivot_type() == &PivotType::Unknown));
+        assert!(sp.confirmed().is_empty());
+
+        let out = sp.next(dec!(-4));
+        assert_eq!(out.back().unwrap().price(), dec!(-3));
+        assert_eq!(out.back().unwrap().pivot_type(), &PivotType::High);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sp = ScalarPivots::new(1, 2).unwrap();
+        for value in [dec!(1), dec!(3), dec!(2)] {
+            sp.next(value);
+        }
+
+        sp.reset();
+        let out = sp.next(dec!(1));
+        assert!(out.iter().all(|p| p.pivot_type() == &PivotType::Unknown));
+    }
+
+    #[test]
+    fn test_default() {
+        ScalarPivots::default();
+    }
+}
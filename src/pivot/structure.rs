@@ -0,0 +1,157 @@
+use rust_decimal::Decimal;
+use ta::errors::Result;
+use ta::{DataItem, Next, Reset};
+use crate::pivot::pivot_points::{PivotPoints, PivotType};
+
+/// Swing structure detector built on [PivotPoints](struct.PivotPoints.html).
+///
+/// Every time a pivot is confirmed it is compared against the previous pivot of the same
+/// type: a pivot high above the last one is a higher high, below it a lower high, and
+/// likewise for pivot lows. Bars that confirm no pivot, or confirm the first pivot of its
+/// type, emit `StructureEvent::None`.
+///
+/// If a single bar confirms both a high and a low, the low's event is reported.
+///
+/// # Parameters
+///
+/// * _lookback_ - number of bars on each side of a pivot
+/// * _num_pivots_ - number of pivots kept by the underlying `PivotPoints`
+#[derive(Debug, Clone)]
+pub struct MarketStructure {
+    pivot_points: PivotPoints,
+    last_high: Option<Decimal>,
+    last_low: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StructureEvent {
+    HigherHigh,
+    LowerHigh,
+    HigherLow,
+    LowerLow,
+    None,
+}
+
+impl MarketStructure {
+    pub fn new(lookback: usize, num_pivots: usize) -> Result<Self> {
+        Ok(Self {
+            pivot_points: PivotPoints::new(lookback, num_pivots)?,
+            last_high: None,
+            last_low: None,
+        })
+    }
+}
+
+impl Next<&DataItem> for MarketStructure {
+    type Output = StructureEvent;
+
+    fn next(&mut self, input: &DataItem) -> Self::Output {
+        self.pivot_points.next(input);
+
+        let mut event = StructureEvent::None;
+        for pivot in self.pivot_points.confirmed() {
+            let price = pivot.price();
+            event = match pivot.pivot_type() {
+                PivotType::High => {
+                    let prev = self.last_high.replace(price);
+                    match prev {
+                        Some(prev) if price > prev => StructureEvent::HigherHigh,
+                        Some(prev) if price < prev => StructureEvent::LowerHigh,
+                        _ => StructureEvent::None,
+                    }
+                }
+                PivotType::Low => {
+                    let prev = self.last_low.replace(price);
+                    match prev {
+                        Some(prev) if price > prev => StructureEvent::HigherLow,
+                        Some(prev) if price < prev => StructureEvent::LowerLow,
+                        _ => StructureEvent::None,
+                    }
+                }
+                PivotType::Unknown => StructureEvent::None,
+            };
+        }
+        event
+    }
+}
+
+impl Reset for MarketStructure {
+    fn reset(&mut self) {
+        self.pivot_points.reset();
+        self.last_high = None;
+        self.last_low = None;
+    }
+}
+
+impl Default for MarketStructure {
+    fn default() -> Self {
+        Self::new(3, 5).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StructureEvent::{HigherHigh, HigherLow, LowerHigh, LowerLow};
+
+    fn bar(price: f64) -> DataItem {
+        DataItem::builder()
+            .open(price)
+            .close(price)
+            .high(price)
+            .low(price)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    fn run(ms: &mut MarketStructure, prices: &[f64]) -> Vec<StructureEvent> {
+        prices.iter().map(|p| ms.next(&bar(*p))).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(MarketStructure::new(0, 0).is_err());
+        assert!(MarketStructure::new(1, 3).is_ok());
+    }
+
+    #[test]
+    fn test_uptrend_to_downtrend() {
+        let mut ms = MarketStructure::new(1, 5).unwrap();
+        let events = run(&mut ms, &[1.0, 3.0, 2.0, 4.0, 3.0, 5.0, 2.0, 4.0, 1.0, 3.0, 2.0]);
+
+        assert_eq!(
+            events,
+            vec![
+                StructureEvent::None,
+                StructureEvent::None,
+                StructureEvent::None,
+                StructureEvent::None,
+                HigherHigh,
+                HigherLow,
+                HigherHigh,
+                LowerLow,
+                LowerHigh,
+                LowerLow,
+                LowerHigh,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ms = MarketStructure::new(1, 5).unwrap();
+        run(&mut ms, &[1.0, 3.0, 2.0, 4.0, 3.0]);
+
+        ms.reset();
+        let events = run(&mut ms, &[1.0, 3.0, 2.0, 4.0, 3.0]);
+        assert_eq!(events[2], StructureEvent::None);
+        assert_eq!(events[3], StructureEvent::None);
+        assert_eq!(events[4], HigherHigh);
+    }
+
+    #[test]
+    fn test_default() {
+        MarketStructure::default();
+    }
+}
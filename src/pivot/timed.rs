@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+use rust_decimal::Decimal;
+use ta::errors::Result;
+use ta::{DataItem, Next, Reset};
+use crate::pivot::pivot_points::{PivotPoints, PivotType};
+
+/// [PivotPoints](struct.PivotPoints.html) that tags every pivot with the timestamp of the
+/// bar it formed on.
+///
+/// `DataItem` carries no time, so the timestamp is passed alongside each bar. A pivot is
+/// confirmed _lookback_ bars after it forms, and the timestamp attached is the one given
+/// with the pivot bar itself, not the confirming bar.
+///
+/// # Parameters
+///
+/// * _lookback_ - number of bars on each side of a pivot
+/// * _num_pivots_ - number of pivots kept
+#[derive(Debug, Clone)]
+pub struct TimedPivotPoints<Ts: Clone> {
+    pivot_points: PivotPoints,
+    lookback: usize,
+    num_pivots: usize,
+    timestamps: VecDeque<Option<Ts>>,
+    pivots: VecDeque<TimedPivot<Ts>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedPivot<Ts> {
+    pub price: Decimal,
+    pub pivot_type: PivotType,
+    pub ts: Ts,
+}
+
+impl<Ts: Clone> TimedPivotPoints<Ts> {
+    pub fn new(lookback: usize, num_pivots: usize) -> Result<Self> {
+        Ok(Self {
+            pivot_points: PivotPoints::new(lookback, num_pivots)?,
+            lookback,
+            num_pivots,
+            timestamps: VecDeque::from(vec![None; lookback * 2 + 1]),
+            pivots: VecDeque::with_capacity(num_pivots),
+        })
+    }
+
+    /// Feeds a bar and its timestamp, returning the most recent pivots, oldest first.
+    pub fn next(&mut self, input: &DataItem, ts: Ts) -> VecDeque<TimedPivot<Ts>> {
+        self.timestamps.pop_front();
+        self.timestamps.push_back(Some(ts));
+        self.pivot_points.next(input);
+
+        if let Some(ts) = &self.timestamps[self.lookback] {
+            for pivot in self.pivot_points.confirmed() {
+                if self.pivots.len() == self.num_pivots {
+                    self.pivots.pop_front();
+                }
+                if self.num_pivots > 0 {
+                    self.pivots.push_back(TimedPivot {
+                        price: pivot.price(),
+                        pivot_type: pivot.pivot_type().clone(),
+                        ts: ts.clone(),
+                    });
+                }
+            }
+        }
+        self.pivots.clone()
+    }
+}
+
+impl<Ts: Clone> Reset for TimedPivotPoints<Ts> {
+    fn reset(&mut self) {
+        self.pivot_points.reset();
+        self.timestamps = VecDeque::from(vec![None; self.lookback * 2 + 1]);
+        self.pivots.clear();
+    }
+}
+
+impl<Ts: Clone> Default for TimedPivotPoints<Ts> {
+    fn default() -> Self {
+        Self::new(3, 5).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    fn bar(price: f64) -> DataItem {
+        DataItem::builder()
+            .open(price)
+            .close(price)
+            .high(price)
+            .low(price)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(TimedPivotPoints::<u64>::new(0, 0).is_err());
+        assert!(TimedPivotPoints::<u64>::new(2, 3).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut pp = TimedPivotPoints::new(2, 3).unwrap();
+        let prices = [1.0, 2.0, 3.0, 2.0, 1.0, 2.0, 3.0];
+
+        let mut out = VecDeque::new();
+        for (i, price) in prices.iter().enumerate() {
+            out = pp.next(&bar(*price), 1000 + i as u64);
+            if i == 4 {
+                // confirmed on the fifth bar, formed on the third
+                assert_eq!(
+                    out.back(),
+                    Some(&TimedPivot { price: dec!(3), pivot_type: PivotType::High, ts: 1002 })
+                );
+            }
+        }
+
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[1], TimedPivot { price: dec!(1), pivot_type: PivotType::Low, ts: 1004 });
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut pp = TimedPivotPoints::new(1, 3).unwrap();
+        pp.next(&bar(1.0), 1);
+        pp.next(&bar(2.0), 2);
+        assert_eq!(pp.next(&bar(1.0), 3).len(), 1);
+
+        pp.reset();
+        assert!(pp.next(&bar(1.0), 4).is_empty());
+    }
+
+    #[test]
+    fn test_default() {
+        TimedPivotPoints::<u64>::default();
+    }
+}
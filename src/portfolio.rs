@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use ta::{DataItem, Next, Reset};
+
+/// One indicator per key, for running the same indicator over many instruments.
+///
+/// Holds an instance of the indicator for each key, typically a symbol, built by the
+/// factory the first time the key is seen. Each instance only sees the bars passed with
+/// its own key, so symbols never leak into each other's state.
+///
+/// # Example
+///
+/// ```
+/// use ta::DataItem;
+/// use technical_analysis::portfolio::IndicatorMap;
+/// use technical_analysis::simple_moving_average::SimpleMovingAverage;
+///
+/// let bar = |close: f64| {
+///     DataItem::builder().open(close).high(close).low(close).close(close).volume(1.0).build().unwrap()
+/// };
+/// let mut smas = IndicatorMap::new(|| SimpleMovingAverage::new(2).unwrap());
+/// smas.next("AAPL", &bar(10.0));
+/// smas.next("MSFT", &bar(300.0));
+/// assert_eq!(smas.next("AAPL", &bar(12.0)).to_string(), "11");
+/// assert_eq!(smas.len(), 2);
+/// ```
+pub struct IndicatorMap<K, I> {
+    factory: Box<dyn Fn() -> I>,
+    indicators: HashMap<K, I>,
+}
+
+impl<K: Eq + Hash, I> IndicatorMap<K, I> {
+    pub fn new(factory: impl Fn() -> I + 'static) -> Self {
+        Self {
+            factory: Box::new(factory),
+            indicators: HashMap::new(),
+        }
+    }
+
+    /// Feeds `input` to the indicator of `key`, building it first if the key is new.
+    pub fn next<O>(&mut self, key: K, input: &DataItem) -> O
+    where
+        I: for<'a> Next<&'a DataItem, Output = O>,
+    {
+        let factory = &self.factory;
+        self.indicators.entry(key).or_insert_with(factory).next(input)
+    }
+
+    /// The indicator of `key`, if it has seen any input.
+    pub fn get(&self, key: &K) -> Option<&I> {
+        self.indicators.get(key)
+    }
+
+    /// Resets the indicator of `key`. Unknown keys are ignored.
+    pub fn reset(&mut self, key: &K)
+    where
+        I: Reset,
+    {
+        if let Some(indicator) = self.indicators.get_mut(key) {
+            indicator.reset();
+        }
+    }
+
+    /// Drops the indicator of `key`, returning it if there was one.
+    pub fn remove(&mut self, key: &K) -> Option<I> {
+        self.indicators.remove(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.indicators.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indicators.is_empty()
+    }
+}
+
+impl<K: fmt::Debug, I: fmt::Debug> fmt::Debug for IndicatorMap<K, I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IndicatorMap").field("indicators", &self.indicators).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use crate::simple_moving_average::SimpleMovingAverage;
+    use super::*;
+
+    fn bar(close: f64) -> DataItem {
+        DataItem::builder()
+            .high(close)
+            .low(close)
+            .close(close)
+            .open(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    fn smas() -> IndicatorMap<&'static str, SimpleMovingAverage> {
+        IndicatorMap::new(|| SimpleMovingAverage::new(3).unwrap())
+    }
+
+    #[test]
+    fn test_independent_symbols() {
+        let mut map = smas();
+        assert!(map.is_empty());
+        assert_eq!(map.next("AAPL", &bar(10.0)), dec!(10));
+        assert_eq!(map.next("MSFT", &bar(300.0)), dec!(300));
+        assert_eq!(map.next("AAPL", &bar(12.0)), dec!(11));
+        assert_eq!(map.next("AAPL", &bar(14.0)), dec!(12));
+        assert_eq!(map.next("MSFT", &bar(306.0)), dec!(303));
+        assert_eq!(map.len(), 2);
+
+        // the MSFT bars don't reach the AAPL average
+        assert_eq!(map.next("AAPL", &bar(16.0)), dec!(14));
+    }
+
+    #[test]
+    fn test_lazy() {
+        let mut map = smas();
+        assert!(map.get(&"AAPL").is_none());
+        map.next("AAPL", &bar(10.0));
+        assert!(map.get(&"AAPL").is_some());
+        assert!(map.get(&"MSFT").is_none());
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut map = smas();
+        map.next("AAPL", &bar(10.0));
+        map.next("MSFT", &bar(300.0));
+
+        map.reset(&"AAPL");
+        map.reset(&"GOOG");
+        assert_eq!(map.next("AAPL", &bar(20.0)), dec!(20));
+        assert_eq!(map.next("MSFT", &bar(306.0)), dec!(303));
+        assert!(map.get(&"GOOG").is_none());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut map = smas();
+        map.next("AAPL", &bar(10.0));
+
+        assert!(map.remove(&"AAPL").is_some());
+        assert!(map.is_empty());
+        assert_eq!(map.next("AAPL", &bar(20.0)), dec!(20));
+    }
+}
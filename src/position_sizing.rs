@@ -0,0 +1,84 @@
+//! Position sizing helpers.
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// Number of units to trade so that an adverse move of `atr_multiple` ATRs loses
+/// `risk_percent` of `account_equity`, e.g. the distance to an ATR-based stop.
+///
+/// The size only depends on the risk, so with a tight stop it can exceed what the equity
+/// buys outright; pass it through [cap_to_equity](fn.cap_to_equity.html) when the account
+/// can't use leverage. The size is not rounded: round it down to the instrument's lot
+/// size. Returns 0 when the stop distance `atr_multiple * atr` is not positive, as no
+/// size bounds the risk then.
+///
+/// # Formula
+///
+/// units = equity * risk_percent / 100 / (atr_multiple * ATR)
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use technical_analysis::position_sizing::atr_position_size;
+///
+/// // risking 1% of 100 000 with a stop 2 ATRs of 2.5 away
+/// let units = atr_position_size(dec!(100000), dec!(1), dec!(2.5), dec!(2));
+/// assert_eq!(units, dec!(200));
+/// ```
+pub fn atr_position_size(account_equity: Decimal, risk_percent: Decimal, atr: Decimal, atr_multiple: Decimal) -> Decimal {
+    let stop_distance = atr_multiple * atr;
+    if stop_distance <= dec!(0) {
+        return dec!(0);
+    }
+    account_equity * risk_percent / dec!(100) / stop_distance
+}
+
+/// Caps `units` at what `account_equity` buys at `price` without leverage. A price that is
+/// not positive leaves `units` unchanged.
+///
+/// # Formula
+///
+/// min(units, equity / price)
+pub fn cap_to_equity(units: Decimal, account_equity: Decimal, price: Decimal) -> Decimal {
+    if price > dec!(0) {
+        units.min(account_equity / price)
+    } else {
+        units
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atr_position_size() {
+        // 1% of 100 000 = 1 000 at risk over 2 * 2.5 = 5 per unit
+        assert_eq!(atr_position_size(dec!(100000), dec!(1), dec!(2.5), dec!(2)), dec!(200));
+        // 0.5% of 25 000 = 125 at risk over 3 * 0.25 = 0.75 per unit
+        assert_eq!(atr_position_size(dec!(25000), dec!(0.5), dec!(0.25), dec!(3)).round_dp(6), dec!(166.666667));
+        // 2% of 10 000 = 200 at risk over 1.5 * 4 = 6 per unit
+        assert_eq!(atr_position_size(dec!(10000), dec!(2), dec!(4), dec!(1.5)).round_dp(6), dec!(33.333333));
+    }
+
+    #[test]
+    fn test_tight_stop_is_not_capped() {
+        // 1 000 at risk over 0.01 is 100 000 units, more than the equity buys at 100
+        let units = atr_position_size(dec!(100000), dec!(1), dec!(0.01), dec!(1));
+        assert_eq!(units, dec!(100000));
+        assert_eq!(cap_to_equity(units, dec!(100000), dec!(100)), dec!(1000));
+    }
+
+    #[test]
+    fn test_cap_to_equity() {
+        assert_eq!(cap_to_equity(dec!(200), dec!(100000), dec!(50)), dec!(200));
+        assert_eq!(cap_to_equity(dec!(200), dec!(100000), dec!(0)), dec!(200));
+    }
+
+    #[test]
+    fn test_zero_atr() {
+        assert_eq!(atr_position_size(dec!(100000), dec!(1), dec!(0), dec!(2)), dec!(0));
+        assert_eq!(atr_position_size(dec!(100000), dec!(1), dec!(2.5), dec!(0)), dec!(0));
+    }
+}
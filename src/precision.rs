@@ -0,0 +1,98 @@
+//! Keeps volume-weighted sums inside `Decimal`'s range.
+//!
+//! `Decimal` holds about 28 significant digits and panics once a value passes roughly
+//! 7.9e28, which a running Σ(price<sup>2</sup> * volume) reaches within a few bars when
+//! volumes are quoted in small units (e.g. 1e22 base units of a token). A volume-weighted
+//! average does not depend on the unit of volume, so instead of overflowing the indicators
+//! switch to a coarser one: the stored sums and volumes are divided by 10<sup>6</sup> and
+//! later volumes are converted to the same unit.
+//!
+//! Until a sum would overflow, everything is computed on the exact inputs. The trade-off
+//! comes with the first rescale: from then on volumes are rounded to `SCALE` decimal
+//! places of the coarser unit, so any volume below 10<sup>-SCALE</sup> of it counts as
+//! zero. That doesn't matter at the magnitudes that trigger a rescale.
+//!
+//! The unit stops at 10<sup>24</sup>, the coarsest one a further step would not take past
+//! `Decimal`'s 28 decimal places. Sums that overflow even then make the bar an error.
+
+use rust_decimal::Decimal;
+
+/// Decimal places volumes are rounded to once the unit has been coarsened.
+pub(crate) const SCALE: u32 = 12;
+
+/// Power of ten the volume unit grows by on each rescale.
+const STEP: u32 = 6;
+
+/// Largest power of ten `Decimal` can divide by.
+const MAX_EXPONENT: u32 = 28;
+
+/// Unit volumes are counted in, as a power of ten.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct VolumeUnit {
+    exponent: u32,
+}
+
+impl VolumeUnit {
+    /// `volume` expressed in this unit. Exact until the unit has been coarsened.
+    pub(crate) fn convert(self, volume: Decimal) -> Decimal {
+        if self.exponent == 0 {
+            return volume;
+        }
+        (volume * inverse_power_of_ten(self.exponent)).round_dp(SCALE)
+    }
+
+    /// Switches to the next coarser unit, or returns false and stays put if there is none.
+    /// Values stored in the old unit must be passed through `rescale`.
+    #[must_use]
+    pub(crate) fn coarsen(&mut self) -> bool {
+        if self.exponent + STEP > MAX_EXPONENT {
+            return false;
+        }
+        self.exponent += STEP;
+        true
+    }
+
+    /// A value stored in the unit before the last `coarsen`, in the current one.
+    pub(crate) fn rescale(value: Decimal) -> Decimal {
+        (value * inverse_power_of_ten(STEP)).round_dp(SCALE)
+    }
+}
+
+/// 10<sup>-exponent</sup>, for an exponent up to `MAX_EXPONENT`.
+fn inverse_power_of_ten(exponent: u32) -> Decimal {
+    Decimal::new(1, exponent)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    #[test]
+    fn test_volume_unit() {
+        let mut unit = VolumeUnit::default();
+        assert_eq!(unit.convert(dec!(2500000)), dec!(2500000));
+        // not rounded before the first coarsen
+        assert_eq!(unit.convert(dec!(0.0000000000001)), dec!(0.0000000000001));
+
+        assert!(unit.coarsen());
+        assert_eq!(unit.convert(dec!(2500000)), dec!(2.5));
+        assert_eq!(VolumeUnit::rescale(dec!(2500000)), dec!(2.5));
+        assert!(unit.coarsen());
+        assert_eq!(unit.convert(dec!(2500000)), dec!(0.0000025));
+    }
+
+    #[test]
+    fn test_coarsest_unit() {
+        let mut unit = VolumeUnit::default();
+        for _ in 0..4 {
+            assert!(unit.coarsen());
+        }
+        assert_eq!(unit.convert(dec!(25000000000000000000000000)), dec!(25));
+
+        // 10^30 is past Decimal's scale: the unit stays at 10^24
+        assert!(!unit.coarsen());
+        assert!(!unit.coarsen());
+        assert_eq!(unit.convert(dec!(25000000000000000000000000)), dec!(25));
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::moving_average::{MovingAverage, MovingAverageKind};
+
+/// Price Oscillator (PO).
+///
+/// The gap between a fast and a slow moving average of price, in price units. Unlike the
+/// PPO it is not divided by the slow average, so it is positive when the fast average
+/// leads upwards and negative when it leads downwards.
+///
+/// # Formula
+///
+/// PO = MA<sub>fast</sub>(close) - MA<sub>slow</sub>(close)
+///
+/// Returns `None` until _slow_ values have been seen.
+///
+/// # Parameters
+///
+/// * _fast_ - period of the fast average (greater than 0, less than _slow_)
+/// * _slow_ - period of the slow average
+/// * _ma_kind_ - kind of both averages
+#[doc(alias = "PO")]
+#[derive(Debug, Clone)]
+pub struct PriceOscillator {
+    fast: MovingAverage,
+    slow: MovingAverage,
+}
+
+impl PriceOscillator {
+    pub fn new(fast: usize, slow: usize, ma_kind: MovingAverageKind) -> Result<Self> {
+        if fast >= slow {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            fast: MovingAverage::new(ma_kind, fast)?,
+            slow: MovingAverage::new(ma_kind, slow)?,
+        })
+    }
+
+    pub fn ma_kind(&self) -> MovingAverageKind {
+        self.fast.kind()
+    }
+}
+
+impl Next<Decimal> for PriceOscillator {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let fast = self.fast.next(input);
+        let slow = self.slow.next(input)?;
+        Some(fast? - slow)
+    }
+}
+
+impl<T: Close> Next<&T> for PriceOscillator {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for PriceOscillator {
+    fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+    }
+}
+
+impl Default for PriceOscillator {
+    fn default() -> Self {
+        Self::new(10, 20, MovingAverageKind::Simple).unwrap()
+    }
+}
+
+impl fmt::Display for PriceOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PO({},{})", self.fast.period(), self.slow.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(PriceOscillator::new(0, 20, MovingAverageKind::Simple).is_err());
+        assert!(PriceOscillator::new(20, 10, MovingAverageKind::Simple).is_err());
+        assert!(PriceOscillator::new(10, 10, MovingAverageKind::Simple).is_err());
+        assert!(PriceOscillator::new(10, 20, MovingAverageKind::Exponential).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut po = PriceOscillator::new(2, 3, MovingAverageKind::Simple).unwrap();
+        assert_eq!(po.next(dec!(1)), None);
+        assert_eq!(po.next(dec!(2)), None);
+        // 2.5 - 2
+        assert_eq!(po.next(dec!(3)), Some(dec!(0.5)));
+        // 2 - 2
+        assert_eq!(po.next(dec!(1)), Some(dec!(0)));
+    }
+
+    #[test]
+    fn test_fast_leads() {
+        for kind in [MovingAverageKind::Simple, MovingAverageKind::Exponential, MovingAverageKind::Weighted] {
+            let mut po = PriceOscillator::new(3, 8, kind).unwrap();
+            let rising: Vec<_> = (0..20).map(|i| po.next(Decimal::from(100 + i))).collect();
+            assert!(rising[7..].iter().all(|out| out.unwrap() > dec!(0)), "{:?}", kind);
+
+            let falling: Vec<_> = (0..20).map(|i| po.next(Decimal::from(120 - 2 * i))).collect();
+            assert!(falling[10..].iter().all(|out| out.unwrap() < dec!(0)), "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut po = PriceOscillator::new(1, 2, MovingAverageKind::Simple).unwrap();
+        po.next(dec!(1));
+        assert!(po.next(dec!(2)).is_some());
+
+        po.reset();
+        assert_eq!(po.next(dec!(3)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(PriceOscillator::default().ma_kind(), MovingAverageKind::Simple);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", PriceOscillator::default()), "PO(10,20)");
+    }
+}
@@ -0,0 +1,158 @@
+//! Building blocks shared by the windowed indicators.
+
+use std::collections::VecDeque;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+/// The values of `window`, sorted ascending. Equal values keep their relative order.
+pub fn sorted_window(window: &VecDeque<Decimal>) -> Vec<Decimal> {
+    let mut sorted: Vec<Decimal> = window.iter().copied().collect();
+    sorted.sort();
+    sorted
+}
+
+/// Value at the 0-based `rank` of the ascending `sorted`, interpolating linearly between
+/// the two neighbouring order statistics when `rank` is fractional.
+///
+/// Rank (n - 1) / 2 is the median, which for an even n is the mean of the two middle
+/// values, and rank q * (n - 1) is the _q_-th quantile. Equal values are not
+/// deduplicated, so a rank inside a run of ties returns the tied value.
+///
+/// # Formula
+///
+/// x<sub>(⌊rank⌋)</sub> + (rank - ⌊rank⌋) * (x<sub>(⌊rank⌋+1)</sub> - x<sub>(⌊rank⌋)</sub>)
+///
+/// # Panics
+///
+/// If `sorted` is empty or `rank` is outside 0..=n - 1. In debug builds, also if `sorted`
+/// is not in ascending order.
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use technical_analysis::primitives::order_statistic;
+///
+/// let sorted = [dec!(1), dec!(3), dec!(5), dec!(7)];
+/// assert_eq!(order_statistic(&sorted, dec!(0)), dec!(1));
+/// assert_eq!(order_statistic(&sorted, dec!(1.5)), dec!(4));
+/// assert_eq!(order_statistic(&sorted, dec!(3)), dec!(7));
+/// ```
+pub fn order_statistic(sorted: &[Decimal], rank: Decimal) -> Decimal {
+    debug_assert!(sorted.windows(2).all(|w| w[0] <= w[1]), "values are not sorted");
+    assert!(
+        rank >= Decimal::ZERO && rank <= Decimal::from(sorted.len()) - Decimal::ONE,
+        "rank {} is outside a window of {}",
+        rank,
+        sorted.len()
+    );
+    let lower = rank.floor();
+    let index = lower.to_usize().unwrap();
+    if index + 1 == sorted.len() {
+        return sorted[index];
+    }
+    sorted[index] + (rank - lower) * (sorted[index + 1] - sorted[index])
+}
+
+/// Share of the values in `window` that are less than or equal to `value`, from 0 to 1.
+///
+/// With `window` holding the values before `value`, this is the percent rank of `value`:
+/// 0 at a new low of the window and 1 at a new high.
+///
+/// # Panics
+///
+/// If `window` is empty.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::VecDeque;
+/// use rust_decimal_macros::dec;
+/// use technical_analysis::primitives::percent_rank;
+///
+/// let window = VecDeque::from(vec![dec!(4), dec!(1), dec!(3), dec!(2)]);
+/// assert_eq!(percent_rank(&window, dec!(3)), dec!(0.75));
+/// assert_eq!(percent_rank(&window, dec!(0)), dec!(0));
+/// ```
+pub fn percent_rank(window: &VecDeque<Decimal>, value: Decimal) -> Decimal {
+    assert!(!window.is_empty(), "percent rank over an empty window");
+    let below = window.iter().filter(|x| **x <= value).count();
+    Decimal::from(below) / Decimal::from(window.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    fn median(values: &[Decimal]) -> Decimal {
+        let sorted = sorted_window(&values.iter().copied().collect());
+        order_statistic(&sorted, Decimal::from(sorted.len() - 1) / dec!(2))
+    }
+
+    #[test]
+    fn test_sorted_window() {
+        let window = VecDeque::from(vec![dec!(3), dec!(-1), dec!(2), dec!(-1)]);
+        assert_eq!(sorted_window(&window), [dec!(-1), dec!(-1), dec!(2), dec!(3)]);
+        assert!(sorted_window(&VecDeque::new()).is_empty());
+    }
+
+    #[test]
+    fn test_odd_window() {
+        assert_eq!(median(&[dec!(9), dec!(1), dec!(5)]), dec!(5));
+        let sorted = [dec!(1), dec!(5), dec!(9)];
+        assert_eq!(order_statistic(&sorted, dec!(0.5)), dec!(3));
+        assert_eq!(order_statistic(&sorted, dec!(2)), dec!(9));
+    }
+
+    #[test]
+    fn test_even_window() {
+        assert_eq!(median(&[dec!(4), dec!(1), dec!(3), dec!(10)]), dec!(3.5));
+        assert_eq!(median(&[dec!(2), dec!(1)]), dec!(1.5));
+        // a quarter of the way between 1 and 3
+        assert_eq!(order_statistic(&[dec!(1), dec!(3), dec!(4), dec!(10)], dec!(0.75)), dec!(2.5));
+    }
+
+    #[test]
+    fn test_repeated_values() {
+        let sorted = [dec!(1), dec!(2), dec!(2), dec!(2), dec!(8)];
+        assert_eq!(order_statistic(&sorted, dec!(1)), dec!(2));
+        assert_eq!(order_statistic(&sorted, dec!(2.5)), dec!(2));
+        // between the last tie and the next value
+        assert_eq!(order_statistic(&sorted, dec!(3.5)), dec!(5));
+        assert_eq!(median(&[dec!(7), dec!(7), dec!(7), dec!(7)]), dec!(7));
+    }
+
+    #[test]
+    fn test_single_value() {
+        assert_eq!(order_statistic(&[dec!(4)], dec!(0)), dec!(4));
+    }
+
+    #[test]
+    fn test_percent_rank() {
+        let window = VecDeque::from(vec![dec!(5), dec!(1), dec!(3), dec!(3)]);
+        assert_eq!(percent_rank(&window, dec!(0.5)), dec!(0));
+        assert_eq!(percent_rank(&window, dec!(1)), dec!(0.25));
+        // ties count as below
+        assert_eq!(percent_rank(&window, dec!(3)), dec!(0.75));
+        assert_eq!(percent_rank(&window, dec!(6)), dec!(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "empty window")]
+    fn test_percent_rank_empty() {
+        percent_rank(&VecDeque::new(), dec!(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside a window of 3")]
+    fn test_rank_out_of_range() {
+        order_statistic(&[dec!(1), dec!(2), dec!(3)], dec!(2.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "not sorted")]
+    fn test_unsorted() {
+        order_statistic(&[dec!(3), dec!(1)], dec!(0));
+    }
+}
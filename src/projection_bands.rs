@@ -0,0 +1,238 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::Result;
+use ta::{Close, High, Low, Next, Period, Reset};
+
+use crate::linear_regression::LinearRegression;
+use crate::model::ProjectionBandsOutput;
+
+/// Projection bands, after Mel Widner.
+///
+/// Every high of the last _period_ bars is carried forward to the current bar along the
+/// regression slope of the highs, and likewise for the lows. The bands are the extremes of
+/// those projections, so they follow the trend of the channel instead of lagging it.
+///
+/// # Formula
+///
+/// Upper = max(high<sub>i bars ago</sub> + slope<sub>high</sub> * i)
+///
+/// Lower = min(low<sub>i bars ago</sub> + slope<sub>low</sub> * i)
+///
+/// For i = 0 to period - 1, where the slopes come from a
+/// [linear regression](../linear_regression/struct.LinearRegression.html) of the highs and
+/// lows over _period_. Returns `None` until _period_ bars have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 1)
+#[derive(Debug, Clone)]
+pub struct ProjectionBands {
+    high_regression: LinearRegression,
+    low_regression: LinearRegression,
+    window: VecDeque<(Decimal, Decimal)>,
+}
+
+impl ProjectionBands {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            high_regression: LinearRegression::new(period)?,
+            low_regression: LinearRegression::new(period)?,
+            window: VecDeque::with_capacity(period),
+        })
+    }
+}
+
+impl Period for ProjectionBands {
+    fn period(&self) -> usize {
+        self.high_regression.period()
+    }
+}
+
+impl<T: High + Low> Next<&T> for ProjectionBands {
+    type Output = Option<ProjectionBandsOutput>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let high = Decimal::from_f64(input.high()).unwrap();
+        let low = Decimal::from_f64(input.low()).unwrap();
+        self.high_regression.next(high);
+        self.low_regression.next(low);
+
+        if self.window.len() == self.period() {
+            self.window.pop_back();
+        }
+        self.window.push_front((high, low));
+
+        let (high_slope, low_slope) = (self.high_regression.slope()?, self.low_regression.slope()?);
+        let (upper, lower) = self.window.iter().enumerate().fold(
+            (Decimal::MIN, Decimal::MAX),
+            |(upper, lower), (bars_ago, (high, low))| {
+                let bars_ago = Decimal::from(bars_ago);
+                (upper.max(high + high_slope * bars_ago), lower.min(low + low_slope * bars_ago))
+            },
+        );
+        Some(ProjectionBandsOutput { upper, lower })
+    }
+}
+
+impl Reset for ProjectionBands {
+    fn reset(&mut self) {
+        self.high_regression.reset();
+        self.low_regression.reset();
+        self.window.clear();
+    }
+}
+
+impl Default for ProjectionBands {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for ProjectionBands {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PB({})", self.period())
+    }
+}
+
+/// Projection oscillator.
+///
+/// Where the close sits within the [projection bands](struct.ProjectionBands.html), from 0
+/// at the lower band to 100 at the upper one.
+///
+/// # Formula
+///
+/// PO = 100 * (close - lower) / (upper - lower)
+///
+/// Returns `None` until the bands are ready, and when they have no width.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 1)
+#[derive(Debug, Clone)]
+pub struct ProjectionOscillator {
+    bands: ProjectionBands,
+}
+
+impl ProjectionOscillator {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            bands: ProjectionBands::new(period)?,
+        })
+    }
+}
+
+impl Period for ProjectionOscillator {
+    fn period(&self) -> usize {
+        self.bands.period()
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for ProjectionOscillator {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let ProjectionBandsOutput { upper, lower } = self.bands.next(input)?;
+        if upper == lower {
+            return None;
+        }
+        let close = Decimal::from_f64(input.close()).unwrap();
+        Some(dec!(100) * (close - lower) / (upper - lower))
+    }
+}
+
+impl Reset for ProjectionOscillator {
+    fn reset(&mut self) {
+        self.bands.reset();
+    }
+}
+
+impl Default for ProjectionOscillator {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for ProjectionOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PO({})", self.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder()
+            .open(close)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(ProjectionBands::new(1).is_err());
+        assert!(ProjectionBands::new(2).is_ok());
+        assert!(ProjectionOscillator::new(1).is_err());
+    }
+
+    #[test]
+    fn test_trending_channel() {
+        let mut bands = ProjectionBands::new(4).unwrap();
+        let mut oscillator = ProjectionOscillator::new(4).unwrap();
+        for i in 0..8 {
+            // a channel rising 1 per bar projects every bar onto the current one
+            let di = bar(10.0 + i as f64, 8.0 + i as f64, 8.5 + i as f64);
+            let (out, position) = (bands.next(&di), oscillator.next(&di));
+            if i < 3 {
+                assert_eq!(out, None);
+                assert_eq!(position, None);
+            } else {
+                let expected = ProjectionBandsOutput { upper: Decimal::from(10 + i), lower: Decimal::from(8 + i) };
+                assert_eq!(out, Some(expected));
+                assert_eq!(position, Some(dec!(25)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_projected_extremes() {
+        let mut bands = ProjectionBands::new(3).unwrap();
+        bands.next(&bar(14.0, 8.0, 10.0));
+        bands.next(&bar(11.0, 9.0, 10.0));
+        // high slope -1.5: 14 projects to 11, 11 to 9.5, 11 stays
+        // low slope 1: 8 projects to 10, 9 to 10, 10 stays
+        let out = bands.next(&bar(11.0, 10.0, 10.5)).unwrap();
+        assert_eq!(out, ProjectionBandsOutput { upper: dec!(11), lower: dec!(10) });
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut oscillator = ProjectionOscillator::new(2).unwrap();
+        oscillator.next(&bar(10.0, 8.0, 9.0));
+        assert!(oscillator.next(&bar(11.0, 9.0, 10.0)).is_some());
+
+        oscillator.reset();
+        assert_eq!(oscillator.next(&bar(11.0, 9.0, 10.0)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        ProjectionBands::default();
+        ProjectionOscillator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", ProjectionBands::default()), "PB(14)");
+        assert_eq!(format!("{}", ProjectionOscillator::default()), "PO(14)");
+    }
+}
@@ -0,0 +1,232 @@
+use std::fmt;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Next, Period, Reset, Volume};
+
+use crate::ema::ExponentialMovingAverage;
+use crate::model::PvoOutput;
+use crate::volume_policy::VolumePolicy;
+
+/// Percentage Volume Oscillator (PVO).
+///
+/// The MACD applied to volume: the gap between a fast and a slow EMA of volume, expressed
+/// as a percentage of the slow EMA.
+///
+/// # Formula
+///
+/// PVO = 100 * (EMA<sub>fast</sub>(volume) - EMA<sub>slow</sub>(volume)) / EMA<sub>slow</sub>(volume)
+///
+/// Signal = EMA<sub>signal</sub>(PVO)
+///
+/// Histogram = PVO - Signal
+///
+/// Bars without volume are counted with a volume of 0 unless another `VolumePolicy` is set.
+///
+/// # Parameters
+///
+/// * _fast_ - period of the fast volume EMA (greater than 0, less than _slow_)
+/// * _slow_ - period of the slow volume EMA
+/// * _signal_ - period of the signal line EMA (greater than 0)
+#[doc(alias = "PVO")]
+#[derive(Debug, Clone)]
+pub struct PercentageVolumeOscillator {
+    fast_ema: ExponentialMovingAverage,
+    slow_ema: ExponentialMovingAverage,
+    signal_ema: ExponentialMovingAverage,
+    volume_policy: VolumePolicy,
+    last: PvoOutput,
+}
+
+impl PercentageVolumeOscillator {
+    pub fn new(fast: usize, slow: usize, signal: usize) -> Result<Self> {
+        Self::with_volume_policy(fast, slow, signal, VolumePolicy::default())
+    }
+
+    /// Like `new`, with `volume_policy` deciding how bars with zero or missing volume are
+    /// handled.
+    pub fn with_volume_policy(fast: usize, slow: usize, signal: usize, volume_policy: VolumePolicy) -> Result<Self> {
+        if fast >= slow {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            fast_ema: ExponentialMovingAverage::new(fast)?,
+            slow_ema: ExponentialMovingAverage::new(slow)?,
+            signal_ema: ExponentialMovingAverage::new(signal)?,
+            volume_policy,
+            last: Self::zero(),
+        })
+    }
+
+    /// `next` that returns an error instead of panicking on a bar rejected by
+    /// `VolumePolicy::Error` or with a volume beyond `Decimal`'s range.
+    pub fn try_next<T: Volume>(&mut self, input: &T) -> Result<PvoOutput> {
+        let Some(volume) = self.volume_policy.volume(input.volume())? else {
+            return Ok(self.last.clone());
+        };
+        let fast = self.fast_ema.next(volume);
+        let slow = self.slow_ema.next(volume);
+
+        let pvo = if slow.is_zero() {
+            dec!(0)
+        } else {
+            dec!(100) * (fast - slow) / slow
+        };
+        let signal = self.signal_ema.next(pvo);
+
+        self.last = PvoOutput {
+            pvo,
+            signal,
+            histogram: pvo - signal,
+        };
+        Ok(self.last.clone())
+    }
+
+    fn zero() -> PvoOutput {
+        PvoOutput {
+            pvo: dec!(0),
+            signal: dec!(0),
+            histogram: dec!(0),
+        }
+    }
+}
+
+impl<T: Volume> Next<&T> for PercentageVolumeOscillator {
+    type Output = PvoOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.try_next(input).expect("invalid volume")
+    }
+}
+
+impl Reset for PercentageVolumeOscillator {
+    fn reset(&mut self) {
+        self.fast_ema.reset();
+        self.slow_ema.reset();
+        self.signal_ema.reset();
+        self.last = Self::zero();
+    }
+}
+
+impl Default for PercentageVolumeOscillator {
+    fn default() -> Self {
+        Self::new(12, 26, 9).unwrap()
+    }
+}
+
+impl fmt::Display for PercentageVolumeOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "PVO({}, {}, {})",
+            self.fast_ema.period(),
+            self.slow_ema.period(),
+            self.signal_ema.period()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(volume: f64) -> DataItem {
+        DataItem::builder()
+            .high(10.0)
+            .low(9.0)
+            .close(9.5)
+            .open(9.5)
+            .volume(volume)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(PercentageVolumeOscillator::new(0, 26, 9).is_err());
+        assert!(PercentageVolumeOscillator::new(26, 12, 9).is_err());
+        assert!(PercentageVolumeOscillator::new(12, 12, 9).is_err());
+        assert!(PercentageVolumeOscillator::new(12, 26, 0).is_err());
+        assert!(PercentageVolumeOscillator::new(12, 26, 9).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut pvo = PercentageVolumeOscillator::new(1, 3, 1).unwrap();
+
+        let out = pvo.next(&bar(100.0));
+        assert_eq!(out.pvo, dec!(0));
+
+        // fast = 200, slow = 0.5 * 200 + 0.5 * 100 = 150
+        let out = pvo.next(&bar(200.0));
+        assert_eq!(out.pvo.round_dp(6), (dec!(100) * dec!(50) / dec!(150)).round_dp(6));
+        assert_eq!(out.signal, out.pvo);
+        assert_eq!(out.histogram, dec!(0));
+    }
+
+    #[test]
+    fn test_volume_surge() {
+        let mut pvo = PercentageVolumeOscillator::default();
+        for _ in 0..40 {
+            let out = pvo.next(&bar(1000.0));
+            assert_eq!(out.pvo, dec!(0));
+        }
+
+        let out = pvo.next(&bar(5000.0));
+        assert!(out.pvo > dec!(10));
+        assert!(out.histogram > dec!(0));
+    }
+
+    #[test]
+    fn test_zero_volume() {
+        let mut pvo = PercentageVolumeOscillator::default();
+        assert_eq!(pvo.next(&bar(0.0)).pvo, dec!(0));
+    }
+
+    #[test]
+    fn test_volume_policy() {
+        let series = [bar(100.0), bar(0.0), bar(200.0)];
+        let run = |policy| {
+            let mut pvo = PercentageVolumeOscillator::with_volume_policy(1, 3, 1, policy).unwrap();
+            series.iter().map(|bar| pvo.try_next(bar).map(|out| out.pvo.round_dp(6))).collect::<Vec<_>>()
+        };
+
+        // slow = 50 after the zero bar, then 125 against a fast of 200
+        assert_eq!(run(VolumePolicy::TreatAsZero), [Ok(dec!(0)), Ok(dec!(-100)), Ok(dec!(60))]);
+        // as if the zero bar never happened: slow = 150
+        let skipped = (dec!(100) * dec!(50) / dec!(150)).round_dp(6);
+        assert_eq!(run(VolumePolicy::Skip), [Ok(dec!(0)), Ok(dec!(0)), Ok(skipped)]);
+        assert_eq!(
+            run(VolumePolicy::Error),
+            [Ok(dec!(0)), Err(TaError::DataItemInvalid), Ok(skipped)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid volume")]
+    fn test_volume_policy_error_panics_in_next() {
+        let mut pvo = PercentageVolumeOscillator::with_volume_policy(12, 26, 9, VolumePolicy::Error).unwrap();
+        pvo.next(&bar(0.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut pvo = PercentageVolumeOscillator::new(1, 3, 1).unwrap();
+        pvo.next(&bar(100.0));
+        pvo.next(&bar(200.0));
+
+        pvo.reset();
+        assert_eq!(pvo.next(&bar(500.0)).pvo, dec!(0));
+    }
+
+    #[test]
+    fn test_default() {
+        PercentageVolumeOscillator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let pvo = PercentageVolumeOscillator::new(12, 26, 9).unwrap();
+        assert_eq!(format!("{}", pvo), "PVO(12, 26, 9)");
+    }
+}
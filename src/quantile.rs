@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::primitives::{order_statistic, sorted_window};
+
+/// Rolling quantile.
+///
+/// The _q_-th quantile of the last _period_ values, interpolating linearly between the two
+/// nearest order statistics as [order_statistic](../primitives/fn.order_statistic.html)
+/// does. `q = 0.5` gives the median, `0` the minimum and `1` the maximum. Useful for
+/// thresholds that adapt to the recent distribution of a series.
+///
+/// # Formula
+///
+/// With x<sub>(0)</sub> ≤ … ≤ x<sub>(n-1)</sub> the sorted window and h = q * (n - 1):
+///
+/// Q = x<sub>(⌊h⌋)</sub> + (h - ⌊h⌋) * (x<sub>(⌊h⌋+1)</sub> - x<sub>(⌊h⌋)</sub>)
+///
+/// Returns `None` until _period_ values have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+/// * _q_ - quantile (between 0 and 1 inclusive)
+#[derive(Debug, Clone)]
+pub struct RollingQuantile {
+    period: usize,
+    q: Decimal,
+    window: VecDeque<Decimal>,
+}
+
+impl RollingQuantile {
+    pub fn new(period: usize, q: Decimal) -> Result<Self> {
+        if period == 0 || q < dec!(0) || q > dec!(1) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            period,
+            q,
+            window: VecDeque::with_capacity(period),
+        })
+    }
+}
+
+impl Period for RollingQuantile {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for RollingQuantile {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(input);
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let sorted = sorted_window(&self.window);
+        Some(order_statistic(&sorted, self.q * Decimal::from(self.period - 1)))
+    }
+}
+
+impl<T: Close> Next<&T> for RollingQuantile {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for RollingQuantile {
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl Default for RollingQuantile {
+    fn default() -> Self {
+        Self::new(20, dec!(0.5)).unwrap()
+    }
+}
+
+impl fmt::Display for RollingQuantile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "QUANTILE({}, {})", self.period, self.q)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last(quantile: &mut RollingQuantile, values: &[Decimal]) -> Option<Decimal> {
+        values.iter().map(|&value| quantile.next(value)).last().unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(RollingQuantile::new(0, dec!(0.5)).is_err());
+        assert!(RollingQuantile::new(5, dec!(-0.1)).is_err());
+        assert!(RollingQuantile::new(5, dec!(1.1)).is_err());
+        assert!(RollingQuantile::new(5, dec!(0)).is_ok());
+        assert!(RollingQuantile::new(5, dec!(1)).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let values = [dec!(7), dec!(1), dec!(5), dec!(3)];
+
+        let mut min = RollingQuantile::new(4, dec!(0)).unwrap();
+        assert_eq!(last(&mut min, &values), Some(dec!(1)));
+
+        let mut median = RollingQuantile::new(4, dec!(0.5)).unwrap();
+        assert_eq!(last(&mut median, &values), Some(dec!(4)));
+
+        let mut max = RollingQuantile::new(4, dec!(1)).unwrap();
+        assert_eq!(last(&mut max, &values), Some(dec!(7)));
+
+        // h = 0.25 * 3 = 0.75: 1 + 0.75 * (3 - 1)
+        let mut lower_quartile = RollingQuantile::new(4, dec!(0.25)).unwrap();
+        assert_eq!(last(&mut lower_quartile, &values), Some(dec!(2.5)));
+    }
+
+    #[test]
+    fn test_rolling_window() {
+        let mut median = RollingQuantile::new(3, dec!(0.5)).unwrap();
+        assert_eq!(median.next(dec!(5)), None);
+        assert_eq!(median.next(dec!(1)), None);
+        assert_eq!(median.next(dec!(3)), Some(dec!(3)));
+        assert_eq!(median.next(dec!(9)), Some(dec!(3)));
+        assert_eq!(median.next(dec!(7)), Some(dec!(7)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut quantile = RollingQuantile::new(2, dec!(0.5)).unwrap();
+        quantile.next(dec!(4));
+        assert!(quantile.next(dec!(6)).is_some());
+
+        quantile.reset();
+        assert_eq!(quantile.next(dec!(99)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingQuantile::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let quantile = RollingQuantile::new(20, dec!(0.9)).unwrap();
+        assert_eq!(format!("{}", quantile), "QUANTILE(20, 0.9)");
+    }
+}
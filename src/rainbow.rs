@@ -0,0 +1,150 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::simple_moving_average::SimpleMovingAverage;
+
+/// Rainbow moving average.
+///
+/// A stack of _depth_ simple moving averages of the same _period_, each one averaging the
+/// output of the layer before it. The first layer averages price. Every call returns all
+/// layers, from the least smoothed to the most smoothed.
+///
+/// A layer only starts averaging once the layer under it has a full window, so layer _k_
+/// (counting from 1) reports `None` for its first _k * (period - 1)_ inputs.
+///
+/// # Parameters
+///
+/// * _period_ - period of every SMA layer (integer greater than 0)
+/// * _depth_ - number of layers (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct RainbowMovingAverage {
+    period: usize,
+    layers: Vec<(SimpleMovingAverage, usize)>,
+}
+
+impl RainbowMovingAverage {
+    pub fn new(period: usize, depth: usize) -> Result<Self> {
+        if depth == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        let layers = (0..depth)
+            .map(|_| SimpleMovingAverage::new(period).map(|sma| (sma, 0)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { period, layers })
+    }
+}
+
+impl Period for RainbowMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for RainbowMovingAverage {
+    type Output = Vec<Option<Decimal>>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let mut value = Some(input);
+        let mut output = Vec::with_capacity(self.layers.len());
+        for (sma, count) in self.layers.iter_mut() {
+            value = value.and_then(|value| {
+                let average = sma.next(value);
+                if *count < self.period {
+                    *count += 1;
+                }
+                if *count < self.period { None } else { Some(average) }
+            });
+            output.push(value);
+        }
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for RainbowMovingAverage {
+    type Output = Vec<Option<Decimal>>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for RainbowMovingAverage {
+    fn reset(&mut self) {
+        for (sma, count) in self.layers.iter_mut() {
+            sma.reset();
+            *count = 0;
+        }
+    }
+}
+
+impl Default for RainbowMovingAverage {
+    fn default() -> Self {
+        Self::new(2, 10).unwrap()
+    }
+}
+
+impl fmt::Display for RainbowMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RAINBOW({}, {})", self.period, self.layers.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(RainbowMovingAverage::new(0, 3).is_err());
+        assert!(RainbowMovingAverage::new(2, 0).is_err());
+        assert!(RainbowMovingAverage::new(2, 3).is_ok());
+    }
+
+    #[test]
+    fn test_warmup() {
+        let mut rainbow = RainbowMovingAverage::new(2, 3).unwrap();
+
+        assert_eq!(rainbow.next(dec!(1)), vec![None, None, None]);
+        assert_eq!(rainbow.next(dec!(2)), vec![Some(dec!(1.5)), None, None]);
+        assert_eq!(rainbow.next(dec!(3)), vec![Some(dec!(2.5)), Some(dec!(2)), None]);
+        assert_eq!(rainbow.next(dec!(4)), vec![Some(dec!(3.5)), Some(dec!(3)), Some(dec!(2.5))]);
+    }
+
+    #[test]
+    fn test_layer_ordering() {
+        let mut rainbow = RainbowMovingAverage::new(3, 5).unwrap();
+        let mut out = Vec::new();
+        for i in 0..20 {
+            out = rainbow.next(Decimal::from(i));
+        }
+
+        // each layer lags a linear ramp by one more bar than the layer before it
+        let values: Vec<Decimal> = out.into_iter().map(|v| v.unwrap()).collect();
+        assert_eq!(values, vec![dec!(18), dec!(17), dec!(16), dec!(15), dec!(14)]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rainbow = RainbowMovingAverage::new(2, 2).unwrap();
+        rainbow.next(dec!(1));
+        rainbow.next(dec!(2));
+        rainbow.next(dec!(3));
+
+        rainbow.reset();
+        assert_eq!(rainbow.next(dec!(10)), vec![None, None]);
+    }
+
+    #[test]
+    fn test_default() {
+        RainbowMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let rainbow = RainbowMovingAverage::new(2, 10).unwrap();
+        assert_eq!(format!("{}", rainbow), "RAINBOW(2, 10)");
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Period, Reset};
+
+/// Relative strength of one instrument against another.
+///
+/// Ratio of the two prices, indexed to 100 as of _period_ bars ago. Above 100 the base
+/// instrument has outperformed the comparison over the window, below 100 it has lagged.
+///
+/// # Formula
+///
+/// RS = 100 * (base / base<sub>period bars ago</sub>) / (comparison / comparison<sub>period bars ago</sub>)
+///
+/// Returns `None` until _period_ + 1 pairs have been seen, and when either price at the
+/// start of the window or the current comparison price is zero.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct RelativeStrength {
+    period: usize,
+    base: VecDeque<Decimal>,
+    comparison: VecDeque<Decimal>,
+}
+
+impl RelativeStrength {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                base: VecDeque::with_capacity(period + 1),
+                comparison: VecDeque::with_capacity(period + 1),
+            }),
+        }
+    }
+
+    /// Feeds one price of each instrument, taken at the same bar.
+    pub fn next(&mut self, base: Decimal, comparison: Decimal) -> Option<Decimal> {
+        if self.base.len() > self.period {
+            self.base.pop_front();
+            self.comparison.pop_front();
+        }
+        self.base.push_back(base);
+        self.comparison.push_back(comparison);
+        if self.base.len() <= self.period {
+            return None;
+        }
+
+        let (base_start, comparison_start) = (self.base[0], self.comparison[0]);
+        if base_start.is_zero() || comparison_start.is_zero() || comparison.is_zero() {
+            return None;
+        }
+        Some(dec!(100) * (base / base_start) / (comparison / comparison_start))
+    }
+}
+
+impl Period for RelativeStrength {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Reset for RelativeStrength {
+    fn reset(&mut self) {
+        self.base.clear();
+        self.comparison.clear();
+    }
+}
+
+impl Default for RelativeStrength {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for RelativeStrength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RS({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(RelativeStrength::new(0).is_err());
+        assert!(RelativeStrength::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut rs = RelativeStrength::new(2).unwrap();
+        assert_eq!(rs.next(dec!(100), dec!(50)), None);
+        assert_eq!(rs.next(dec!(105), dec!(50)), None);
+        // base +10%, comparison +0%
+        assert_eq!(rs.next(dec!(110), dec!(50)), Some(dec!(110)));
+        // both +10% against the window start
+        assert_eq!(rs.next(dec!(115.5), dec!(55)), Some(dec!(100)));
+    }
+
+    #[test]
+    fn test_leader_and_laggard() {
+        let mut leader = RelativeStrength::new(5).unwrap();
+        let mut laggard = RelativeStrength::new(5).unwrap();
+        let mut fast = dec!(100);
+        let mut slow = dec!(100);
+
+        for i in 0..15 {
+            fast *= dec!(1.02);
+            slow *= dec!(1.005);
+            let (leading, lagging) = (leader.next(fast, slow), laggard.next(slow, fast));
+            if i < 5 {
+                assert_eq!((leading, lagging), (None, None));
+            } else {
+                assert!(leading.unwrap() > dec!(100));
+                assert!(lagging.unwrap() < dec!(100));
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_price() {
+        let mut rs = RelativeStrength::new(1).unwrap();
+        rs.next(dec!(10), dec!(0));
+        assert_eq!(rs.next(dec!(10), dec!(5)), None);
+        assert_eq!(rs.next(dec!(10), dec!(0)), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rs = RelativeStrength::new(1).unwrap();
+        rs.next(dec!(10), dec!(10));
+        assert!(rs.next(dec!(11), dec!(10)).is_some());
+
+        rs.reset();
+        assert_eq!(rs.next(dec!(11), dec!(10)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(RelativeStrength::default().period(), 20);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", RelativeStrength::new(10).unwrap()), "RS(10)");
+    }
+}
@@ -0,0 +1,293 @@
+//! Risk-adjusted return ratios over a rolling window of per-bar returns.
+
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Next, Period, Reset};
+
+use crate::math::sqrt;
+use crate::standard_deviation::StandardDeviation;
+
+/// Rolling Sharpe ratio.
+///
+/// Mean excess return over the last _period_ returns divided by their standard deviation.
+/// Inputs are per-bar returns (e.g. `0.01` for 1%), not prices.
+///
+/// # Formula
+///
+/// Sharpe = (mean(r) - r<sub>f</sub>) / SD(r) * sqrt(periods_per_year)
+///
+/// The annualization factor is 1 unless built with `with_annualization`. Returns `None`
+/// until _period_ returns have been seen, and when the window has no dispersion.
+///
+/// # Parameters
+///
+/// * _period_ - number of returns (integer greater than 0)
+/// * _risk_free_ - per-bar risk-free return
+#[derive(Debug, Clone)]
+pub struct RollingSharpe {
+    sd: StandardDeviation,
+    risk_free: Decimal,
+    scale: Decimal,
+}
+
+impl RollingSharpe {
+    pub fn new(period: usize, risk_free: Decimal) -> Result<Self> {
+        Ok(Self {
+            sd: StandardDeviation::new(period)?,
+            risk_free,
+            scale: dec!(1),
+        })
+    }
+
+    /// Sharpe ratio scaled by `sqrt(periods_per_year)`, e.g. 252 for daily returns.
+    pub fn with_annualization(period: usize, risk_free: Decimal, periods_per_year: usize) -> Result<Self> {
+        if periods_per_year == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            scale: sqrt(Decimal::from(periods_per_year)),
+            ..Self::new(period, risk_free)?
+        })
+    }
+}
+
+impl Period for RollingSharpe {
+    fn period(&self) -> usize {
+        self.sd.period()
+    }
+}
+
+impl Next<Decimal> for RollingSharpe {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let sd = self.sd.next(input)?;
+        if sd.is_zero() {
+            return None;
+        }
+        let mean = self.sd.mean()?;
+        Some((mean - self.risk_free) / sd * self.scale)
+    }
+}
+
+impl Reset for RollingSharpe {
+    fn reset(&mut self) {
+        self.sd.reset();
+    }
+}
+
+impl Default for RollingSharpe {
+    fn default() -> Self {
+        Self::new(20, dec!(0)).unwrap()
+    }
+}
+
+impl fmt::Display for RollingSharpe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SHARPE({})", self.sd.period())
+    }
+}
+
+/// Rolling Sortino ratio.
+///
+/// Like [RollingSharpe](struct.RollingSharpe.html), but divides by the downside deviation
+/// so only returns below the risk-free rate count as risk.
+///
+/// # Formula
+///
+/// DD = sqrt(Σmin(r<sub>i</sub> - r<sub>f</sub>, 0)<sup>2</sup> / period)
+///
+/// Sortino = (mean(r) - r<sub>f</sub>) / DD * sqrt(periods_per_year)
+///
+/// Returns `None` until _period_ returns have been seen, and when no return in the window
+/// falls below the risk-free rate.
+///
+/// # Parameters
+///
+/// * _period_ - number of returns (integer greater than 0)
+/// * _risk_free_ - per-bar risk-free return, also the downside threshold
+#[derive(Debug, Clone)]
+pub struct RollingSortino {
+    period: usize,
+    risk_free: Decimal,
+    scale: Decimal,
+    window: VecDeque<Decimal>,
+    sum: Decimal,
+    downside_sq_sum: Decimal,
+}
+
+impl RollingSortino {
+    pub fn new(period: usize, risk_free: Decimal) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                risk_free,
+                scale: dec!(1),
+                window: VecDeque::with_capacity(period),
+                sum: dec!(0),
+                downside_sq_sum: dec!(0),
+            }),
+        }
+    }
+
+    /// Sortino ratio scaled by `sqrt(periods_per_year)`, e.g. 252 for daily returns.
+    pub fn with_annualization(period: usize, risk_free: Decimal, periods_per_year: usize) -> Result<Self> {
+        if periods_per_year == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            scale: sqrt(Decimal::from(periods_per_year)),
+            ..Self::new(period, risk_free)?
+        })
+    }
+
+    fn downside_sq(&self, value: Decimal) -> Decimal {
+        let shortfall = (value - self.risk_free).min(dec!(0));
+        shortfall * shortfall
+    }
+}
+
+impl Period for RollingSortino {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for RollingSortino {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        if self.window.len() == self.period {
+            let old = self.window.pop_front().unwrap();
+            self.sum -= old;
+            self.downside_sq_sum -= self.downside_sq(old);
+        }
+        self.window.push_back(input);
+        self.sum += input;
+        self.downside_sq_sum += self.downside_sq(input);
+
+        if self.window.len() < self.period || self.downside_sq_sum <= dec!(0) {
+            return None;
+        }
+        let period = Decimal::from(self.period);
+        let downside_dev = sqrt(self.downside_sq_sum / period);
+        Some((self.sum / period - self.risk_free) / downside_dev * self.scale)
+    }
+}
+
+impl Reset for RollingSortino {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.sum = dec!(0);
+        self.downside_sq_sum = dec!(0);
+    }
+}
+
+impl Default for RollingSortino {
+    fn default() -> Self {
+        Self::new(20, dec!(0)).unwrap()
+    }
+}
+
+impl fmt::Display for RollingSortino {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SORTINO({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sharpe_new() {
+        assert!(RollingSharpe::new(0, dec!(0)).is_err());
+        assert!(RollingSharpe::with_annualization(4, dec!(0), 0).is_err());
+        assert!(RollingSharpe::new(4, dec!(0)).is_ok());
+    }
+
+    #[test]
+    fn test_sharpe_next() {
+        // mean 0.02, SD 0.01
+        let returns = [dec!(0.01), dec!(0.03), dec!(0.01), dec!(0.03)];
+
+        let mut sharpe = RollingSharpe::new(4, dec!(0)).unwrap();
+        let out: Vec<_> = returns.iter().map(|r| sharpe.next(*r)).collect();
+        assert_eq!(&out[..3], &[None, None, None]);
+        assert_eq!(out[3].unwrap().round_dp(10), dec!(2));
+
+        let mut sharpe = RollingSharpe::new(4, dec!(0.01)).unwrap();
+        let out = returns.iter().map(|r| sharpe.next(*r)).last().unwrap();
+        assert_eq!(out.unwrap().round_dp(10), dec!(1));
+
+        let mut sharpe = RollingSharpe::with_annualization(4, dec!(0), 4).unwrap();
+        let out = returns.iter().map(|r| sharpe.next(*r)).last().unwrap();
+        assert_eq!(out.unwrap().round_dp(10), dec!(4));
+    }
+
+    #[test]
+    fn test_sharpe_no_dispersion() {
+        let mut sharpe = RollingSharpe::new(2, dec!(0)).unwrap();
+        sharpe.next(dec!(0.01));
+        assert_eq!(sharpe.next(dec!(0.01)), None);
+    }
+
+    #[test]
+    fn test_sortino_new() {
+        assert!(RollingSortino::new(0, dec!(0)).is_err());
+        assert!(RollingSortino::with_annualization(4, dec!(0), 0).is_err());
+        assert!(RollingSortino::new(4, dec!(0)).is_ok());
+    }
+
+    #[test]
+    fn test_sortino_next() {
+        let mut sortino = RollingSortino::new(4, dec!(0)).unwrap();
+        assert_eq!(sortino.next(dec!(0.02)), None);
+        assert_eq!(sortino.next(dec!(-0.01)), None);
+        assert_eq!(sortino.next(dec!(0.03)), None);
+        // mean 0.0025, DD = sqrt((0.0001 + 0.0009) / 4) = 0.0158113883
+        assert_eq!(sortino.next(dec!(-0.03)).unwrap().round_dp(8), dec!(0.15811388));
+
+        // window -0.01, 0.03, -0.03, 0.05: mean 0.01, DD unchanged
+        assert_eq!(sortino.next(dec!(0.05)).unwrap().round_dp(8), dec!(0.63245553));
+    }
+
+    #[test]
+    fn test_sortino_no_downside() {
+        let mut sortino = RollingSortino::new(2, dec!(0)).unwrap();
+        sortino.next(dec!(-0.01));
+        assert!(sortino.next(dec!(0.02)).is_some());
+        assert_eq!(sortino.next(dec!(0.03)), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sharpe = RollingSharpe::new(2, dec!(0)).unwrap();
+        let mut sortino = RollingSortino::new(2, dec!(0)).unwrap();
+        for r in [dec!(0.01), dec!(-0.02), dec!(0.03)] {
+            sharpe.next(r);
+            sortino.next(r);
+        }
+
+        sharpe.reset();
+        sortino.reset();
+        assert_eq!(sharpe.next(dec!(0.01)), None);
+        assert_eq!(sortino.next(dec!(-0.01)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingSharpe::default();
+        RollingSortino::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", RollingSharpe::new(30, dec!(0)).unwrap()), "SHARPE(30)");
+        assert_eq!(format!("{}", RollingSortino::new(30, dec!(0)).unwrap()), "SORTINO(30)");
+    }
+}
@@ -0,0 +1,312 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::lag::Lag;
+
+/// Rate of change (ROC).
+///
+/// Percentage change between the current value and the value _period_ bars ago.
+///
+/// # Formula
+///
+/// ROC = (value - value<sub>period bars ago</sub>) / value<sub>period bars ago</sub> * 100
+///
+/// Returns `None` until _period_ + 1 values have been seen, and when the earlier value is
+/// zero.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[doc(alias = "ROC")]
+#[derive(Debug, Clone)]
+pub struct RateOfChange {
+    lag: Lag,
+}
+
+impl RateOfChange {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            lag: Lag::new(period)?,
+        })
+    }
+}
+
+impl Period for RateOfChange {
+    fn period(&self) -> usize {
+        self.lag.period()
+    }
+}
+
+impl Next<Decimal> for RateOfChange {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let previous = self.lag.next(input)?;
+        if previous.is_zero() {
+            return None;
+        }
+        Some((input - previous) / previous * dec!(100))
+    }
+}
+
+impl<T: Close> Next<&T> for RateOfChange {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for RateOfChange {
+    fn reset(&mut self) {
+        self.lag.reset();
+    }
+}
+
+impl Default for RateOfChange {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for RateOfChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ROC({})", self.lag.period())
+    }
+}
+
+/// Rate of change over several lookbacks at once.
+///
+/// Runs one [RateOfChange](struct.RateOfChange.html) per period and returns their values in
+/// the order the periods were given. Each element warms up independently.
+///
+/// # Parameters
+///
+/// * _periods_ - lookbacks (non-empty, each greater than 0)
+#[derive(Debug, Clone)]
+pub struct RocFan {
+    rocs: Vec<RateOfChange>,
+}
+
+impl RocFan {
+    pub fn new(periods: Vec<usize>) -> Result<Self> {
+        if periods.is_empty() {
+            return Err(TaError::InvalidParameter);
+        }
+        let rocs = periods
+            .into_iter()
+            .map(RateOfChange::new)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { rocs })
+    }
+
+    pub fn periods(&self) -> Vec<usize> {
+        self.rocs.iter().map(|roc| roc.period()).collect()
+    }
+}
+
+impl Next<Decimal> for RocFan {
+    type Output = Vec<Option<Decimal>>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        self.rocs.iter_mut().map(|roc| roc.next(input)).collect()
+    }
+}
+
+impl<T: Close> Next<&T> for RocFan {
+    type Output = Vec<Option<Decimal>>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for RocFan {
+    fn reset(&mut self) {
+        self.rocs.iter_mut().for_each(|roc| roc.reset());
+    }
+}
+
+impl fmt::Display for RocFan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let periods: Vec<String> = self.rocs.iter().map(|roc| roc.period().to_string()).collect();
+        write!(f, "ROC_FAN({})", periods.join(", "))
+    }
+}
+
+/// Weighted sum of rates of change over several lookbacks.
+///
+/// The building block of momentum composites such as KST and the Coppock curve, which
+/// combine ROCs of different lengths before smoothing the result.
+///
+/// # Formula
+///
+/// WROC = Σ weight<sub>i</sub> * ROC(period<sub>i</sub>)
+///
+/// Returns `None` until every component ROC is ready, i.e. until the longest period + 1
+/// values have been seen, and whenever a component ROC is `None`.
+///
+/// # Parameters
+///
+/// * _specs_ - (period, weight) pairs (non-empty, each period greater than 0)
+#[derive(Debug, Clone)]
+pub struct WeightedRocSum {
+    components: Vec<(RateOfChange, Decimal)>,
+}
+
+impl WeightedRocSum {
+    pub fn new(specs: Vec<(usize, Decimal)>) -> Result<Self> {
+        if specs.is_empty() {
+            return Err(TaError::InvalidParameter);
+        }
+        let components = specs
+            .into_iter()
+            .map(|(period, weight)| Ok((RateOfChange::new(period)?, weight)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { components })
+    }
+}
+
+impl Next<Decimal> for WeightedRocSum {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        // every component sees every input, even once the sum is known to be `None`
+        self.components
+            .iter_mut()
+            .map(|(roc, weight)| roc.next(input).map(|value| value * *weight))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .sum()
+    }
+}
+
+impl<T: Close> Next<&T> for WeightedRocSum {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for WeightedRocSum {
+    fn reset(&mut self) {
+        self.components.iter_mut().for_each(|(roc, _)| roc.reset());
+    }
+}
+
+impl fmt::Display for WeightedRocSum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let specs: Vec<String> = self
+            .components
+            .iter()
+            .map(|(roc, weight)| format!("{}*{}", weight, roc.period()))
+            .collect();
+        write!(f, "WROC_SUM({})", specs.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(RateOfChange::new(0).is_err());
+        assert!(RateOfChange::new(1).is_ok());
+        assert!(RocFan::new(vec![]).is_err());
+        assert!(RocFan::new(vec![5, 0]).is_err());
+        assert!(RocFan::new(vec![5, 10]).is_ok());
+        assert!(WeightedRocSum::new(vec![]).is_err());
+        assert!(WeightedRocSum::new(vec![(3, dec!(1)), (0, dec!(2))]).is_err());
+        assert!(WeightedRocSum::new(vec![(3, dec!(1))]).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut roc = RateOfChange::new(2).unwrap();
+        assert_eq!(roc.next(dec!(10)), None);
+        assert_eq!(roc.next(dec!(11)), None);
+        assert_eq!(roc.next(dec!(12)), Some(dec!(20)));
+        assert_eq!(roc.next(dec!(8.8)), Some(dec!(-20)));
+    }
+
+    #[test]
+    fn test_next_zero_base() {
+        let mut roc = RateOfChange::new(1).unwrap();
+        roc.next(dec!(0));
+        assert_eq!(roc.next(dec!(5)), None);
+        assert_eq!(roc.next(dec!(10)), Some(dec!(100)));
+    }
+
+    #[test]
+    fn test_fan_matches_standalone() {
+        let periods = vec![5, 10, 20];
+        let mut fan = RocFan::new(periods.clone()).unwrap();
+        let mut standalone: Vec<RateOfChange> = periods.iter().map(|p| RateOfChange::new(*p).unwrap()).collect();
+
+        for i in 0..40 {
+            let input = Decimal::from(100 + (i * 7) % 13);
+            let expected: Vec<Option<Decimal>> = standalone.iter_mut().map(|roc| roc.next(input)).collect();
+            let out = fan.next(input);
+            assert_eq!(out.len(), 3);
+            assert_eq!(out, expected);
+            assert_eq!(out[0].is_some(), i >= 5);
+            assert_eq!(out[2].is_some(), i >= 20);
+        }
+    }
+
+    #[test]
+    fn test_weighted_sum_matches_standalone() {
+        let mut sum = WeightedRocSum::new(vec![(3, dec!(1)), (6, dec!(2.5))]).unwrap();
+        let mut short = RateOfChange::new(3).unwrap();
+        let mut long = RateOfChange::new(6).unwrap();
+
+        for i in 0..20 {
+            let input = Decimal::from(50 + (i * 5) % 9);
+            let expected = match (short.next(input), long.next(input)) {
+                (Some(short), Some(long)) => Some(short + dec!(2.5) * long),
+                _ => None,
+            };
+            assert_eq!(sum.next(input), expected);
+            assert_eq!(expected.is_some(), i >= 6);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut roc = RateOfChange::new(1).unwrap();
+        roc.next(dec!(10));
+        roc.reset();
+        assert_eq!(roc.next(dec!(20)), None);
+
+        let mut fan = RocFan::new(vec![1, 2]).unwrap();
+        fan.next(dec!(10));
+        fan.next(dec!(10));
+        fan.reset();
+        assert_eq!(fan.next(dec!(20)), vec![None, None]);
+
+        let mut sum = WeightedRocSum::new(vec![(1, dec!(1))]).unwrap();
+        sum.next(dec!(10));
+        sum.reset();
+        assert_eq!(sum.next(dec!(20)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        RateOfChange::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", RateOfChange::new(12).unwrap()), "ROC(12)");
+        assert_eq!(format!("{}", RocFan::new(vec![5, 10, 20]).unwrap()), "ROC_FAN(5, 10, 20)");
+        assert_eq!(
+            format!("{}", WeightedRocSum::new(vec![(10, dec!(1)), (15, dec!(2))]).unwrap()),
+            "WROC_SUM(1*10, 2*15)"
+        );
+    }
+}
@@ -3,8 +3,9 @@ use rust_decimal::Decimal;
 use ta::errors::{Result, TaError};
 use crate::simple_moving_average::SimpleMovingAverage;
 use ta::{Next, Period, Reset};
+use crate::warmup::{WarmupPolicy, WithWarmupPolicy};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub struct RollingMovingAverage {
     period: usize,
     opt_current: Option<Decimal>,
@@ -24,6 +25,23 @@ impl RollingMovingAverage {
             }),
         }
     }
+
+    /// RMA that starts from `first_value` as its already computed average, skipping the SMA
+    /// warmup: the first input is smoothed into it right away. `reset` drops the seed and
+    /// goes back to the SMA warmup.
+    pub fn with_seed(period: usize, first_value: Decimal) -> Result<Self> {
+        Ok(Self {
+            opt_current: Some(first_value),
+            no_invokes: period,
+            ..Self::new(period)?
+        })
+    }
+
+    /// Wraps the RMA to pick its warmup output. Unwrapped, it returns `None` until its SMA
+    /// seed is complete, which is `WarmupPolicy::None`; `Emit` keeps that behavior.
+    pub fn warmup_policy(self, policy: WarmupPolicy) -> WithWarmupPolicy<Self> {
+        WithWarmupPolicy::new(self, policy)
+    }
 }
 
 impl Period for RollingMovingAverage {
@@ -75,6 +93,7 @@ impl fmt::Display for RollingMovingAverage {
 #[cfg(test)]
 mod tests {
     use rust_decimal_macros::dec;
+    use crate::test_util::assert_full_reset;
     use super::*;
 
     #[test]
@@ -83,6 +102,12 @@ mod tests {
         assert!(RollingMovingAverage::new(1).is_ok());
     }
 
+    #[test]
+    fn test_full_reset() {
+        let series = [dec!(100), dec!(102), dec!(101), dec!(105), dec!(99), dec!(103)];
+        assert_full_reset(RollingMovingAverage::new(3).unwrap(), &series);
+    }
+
     #[test]
     fn test_next() {
         let mut rma = RollingMovingAverage::new(14).unwrap();
@@ -104,6 +129,50 @@ mod tests {
         assert_eq!(rma.next(dec!(115.0)).unwrap().round_dp(4), dec!(106.4439));
     }
 
+    #[test]
+    fn test_with_seed() {
+        assert!(RollingMovingAverage::with_seed(0, dec!(5)).is_err());
+
+        let mut seeded = RollingMovingAverage::with_seed(3, dec!(5)).unwrap();
+        let mut rma = RollingMovingAverage::new(3).unwrap();
+        for _ in 0..3 {
+            rma.next(dec!(5));
+        }
+
+        assert_eq!(seeded.next(dec!(8)), Some(dec!(6)));
+        assert_eq!(rma.next(dec!(8)), Some(dec!(6)));
+        for value in [dec!(2), dec!(9.5), dec!(4)] {
+            assert_eq!(seeded.next(value), rma.next(value));
+        }
+
+        seeded.reset();
+        assert_eq!(seeded.next(dec!(8)), None);
+    }
+
+    #[test]
+    fn test_warmup_policy() {
+        let outputs = |policy| {
+            let mut rma = RollingMovingAverage::new(3).unwrap().warmup_policy(policy);
+            [dec!(3), dec!(6), dec!(9), dec!(12)].map(|input| rma.next(input))
+        };
+        assert_eq!(outputs(WarmupPolicy::Emit), [None, None, Some(dec!(6)), Some(dec!(8))]);
+        assert_eq!(outputs(WarmupPolicy::None), [None, None, Some(dec!(6)), Some(dec!(8))]);
+        assert_eq!(outputs(WarmupPolicy::Partial), [Some(dec!(3)), Some(dec!(4.5)), Some(dec!(6)), Some(dec!(8))]);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut rma = RollingMovingAverage::new(3).unwrap();
+        rma.next(dec!(1));
+        rma.next(dec!(2));
+
+        let json = serde_json::to_string(&rma).unwrap();
+        let mut restored: RollingMovingAverage = serde_json::from_str(&json).unwrap();
+        for value in [dec!(3), dec!(10), dec!(4)] {
+            assert_eq!(restored.next(value), rma.next(value));
+        }
+    }
+
     // #[test]
     // fn test_reset() {
     //     let mut ema = ExponentialMovingAverage::new(5).unwrap();
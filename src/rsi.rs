@@ -0,0 +1,299 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::Result;
+use ta::{Close, Next, Period, Reset};
+
+use crate::checks::check_range;
+use crate::rolling_moving_average::RollingMovingAverage;
+use crate::zlema::ZeroLagExponentialMovingAverage;
+
+/// Relative strength index (RSI), Wilder's momentum oscillator.
+///
+/// Compares the average gain with the average loss over the last _period_ changes, on a
+/// scale of 0 to 100.
+///
+/// # Formula
+///
+/// RS = RMA(period) of gains / RMA(period) of losses
+///
+/// RSI = 100 - 100 / (1 + RS)
+///
+/// Where gains and losses are the positive and negative changes from the previous input.
+/// RSI is 100 when there are no losses, and 50 when the input has not moved at all.
+/// Returns `None` until _period_ changes, i.e. _period_ + 1 inputs, have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[doc(alias = "RSI")]
+#[derive(Debug, Clone)]
+pub struct RelativeStrengthIndex {
+    gains: RollingMovingAverage,
+    losses: RollingMovingAverage,
+    prev: Option<Decimal>,
+}
+
+impl RelativeStrengthIndex {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            gains: RollingMovingAverage::new(period)?,
+            losses: RollingMovingAverage::new(period)?,
+            prev: None,
+        })
+    }
+}
+
+impl Period for RelativeStrengthIndex {
+    fn period(&self) -> usize {
+        self.gains.period()
+    }
+}
+
+impl Next<Decimal> for RelativeStrengthIndex {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let change = input - self.prev.replace(input)?;
+        let gain = self.gains.next(change.max(dec!(0)));
+        let loss = self.losses.next((-change).max(dec!(0)));
+        let (gain, loss) = (gain?, loss?);
+
+        if loss.is_zero() {
+            return Some(if gain.is_zero() { dec!(50) } else { dec!(100) });
+        }
+        let rsi = dec!(100) - dec!(100) / (dec!(1) + gain / loss);
+        check_range("RSI", rsi, dec!(0), dec!(100));
+        Some(rsi)
+    }
+}
+
+impl<T: Close> Next<&T> for RelativeStrengthIndex {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for RelativeStrengthIndex {
+    fn reset(&mut self) {
+        self.gains.reset();
+        self.losses.reset();
+        self.prev = None;
+    }
+}
+
+impl Default for RelativeStrengthIndex {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for RelativeStrengthIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RSI({})", self.gains.period())
+    }
+}
+
+/// Zero-lag RSI, after Sylvain Vervoort.
+///
+/// [RelativeStrengthIndex](struct.RelativeStrengthIndex.html) smoothed with a
+/// [zero-lag EMA](../zlema/struct.ZeroLagExponentialMovingAverage.html), which filters the
+/// RSI's bar-to-bar noise while staying close to it when it trends.
+///
+/// # Formula
+///
+/// ZLRSI = ZLEMA(zlema_period) of RSI(rsi_period)
+///
+/// Returns `None` until both the RSI and the ZLEMA have warmed up. The ZLEMA extrapolates,
+/// so the output can briefly leave the 0 to 100 range after sharp moves.
+///
+/// # Parameters
+///
+/// * _rsi_period_ - RSI period (integer greater than 0)
+/// * _zlema_period_ - smoothing period (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct ZeroLagRsi {
+    rsi: RelativeStrengthIndex,
+    zlema: ZeroLagExponentialMovingAverage,
+}
+
+impl ZeroLagRsi {
+    pub fn new(rsi_period: usize, zlema_period: usize) -> Result<Self> {
+        Ok(Self {
+            rsi: RelativeStrengthIndex::new(rsi_period)?,
+            zlema: ZeroLagExponentialMovingAverage::new(zlema_period)?,
+        })
+    }
+}
+
+impl Next<Decimal> for ZeroLagRsi {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let rsi = self.rsi.next(input)?;
+        self.zlema.next(rsi)
+    }
+}
+
+impl<T: Close> Next<&T> for ZeroLagRsi {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for ZeroLagRsi {
+    fn reset(&mut self) {
+        self.rsi.reset();
+        self.zlema.reset();
+    }
+}
+
+impl Default for ZeroLagRsi {
+    fn default() -> Self {
+        Self::new(14, 9).unwrap()
+    }
+}
+
+impl fmt::Display for ZeroLagRsi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZLRSI({}, {})", self.rsi.period(), self.zlema.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(RelativeStrengthIndex::new(0).is_err());
+        assert!(RelativeStrengthIndex::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut rsi = RelativeStrengthIndex::new(3).unwrap();
+        assert_eq!(rsi.next(dec!(10)), None);
+        assert_eq!(rsi.next(dec!(12)), None);
+        assert_eq!(rsi.next(dec!(11)), None);
+        // gains 2, 0, 3 -> 5 / 3; losses 0, 1, 0 -> 1 / 3; RS = 5
+        assert_eq!(rsi.next(dec!(14)).unwrap().round_dp(10), dec!(83.3333333333));
+        // gain = (5 / 3 * 2 + 0) / 3 = 10 / 9, loss = (1 / 3 * 2 + 2) / 3 = 8 / 9; RS = 1.25
+        assert_eq!(rsi.next(dec!(12)).unwrap().round_dp(10), dec!(55.5555555556));
+    }
+
+    #[test]
+    fn test_one_sided() {
+        let mut rsi = RelativeStrengthIndex::new(2).unwrap();
+        rsi.next(dec!(1));
+        rsi.next(dec!(2));
+        assert_eq!(rsi.next(dec!(3)), Some(dec!(100)));
+
+        let mut rsi = RelativeStrengthIndex::new(2).unwrap();
+        rsi.next(dec!(3));
+        rsi.next(dec!(2));
+        assert_eq!(rsi.next(dec!(1)), Some(dec!(0)));
+
+        let mut rsi = RelativeStrengthIndex::new(2).unwrap();
+        rsi.next(dec!(3));
+        rsi.next(dec!(3));
+        assert_eq!(rsi.next(dec!(3)), Some(dec!(50)));
+    }
+
+    #[test]
+    #[cfg(feature = "debug-checks")]
+    #[should_panic(expected = "RSI output")]
+    fn test_debug_checks() {
+        let mut rsi = RelativeStrengthIndex::new(2).unwrap();
+        for value in [dec!(10), dec!(12), dec!(11)] {
+            rsi.next(value);
+        }
+        // a negative average loss cannot come from real inputs
+        rsi.losses.next(dec!(-5));
+        rsi.next(dec!(12));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rsi = RelativeStrengthIndex::new(1).unwrap();
+        rsi.next(dec!(1));
+        assert!(rsi.next(dec!(2)).is_some());
+
+        rsi.reset();
+        assert_eq!(rsi.next(dec!(2)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        RelativeStrengthIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", RelativeStrengthIndex::default()), "RSI(14)");
+    }
+
+    #[test]
+    fn test_zero_lag_new() {
+        assert!(ZeroLagRsi::new(0, 9).is_err());
+        assert!(ZeroLagRsi::new(14, 0).is_err());
+        assert!(ZeroLagRsi::new(14, 9).is_ok());
+    }
+
+    #[test]
+    fn test_zero_lag_responsiveness() {
+        use crate::ema::ExponentialMovingAverage;
+
+        let mut zlrsi = ZeroLagRsi::new(14, 9).unwrap();
+        let mut rsi = RelativeStrengthIndex::new(14).unwrap();
+        let mut ema = ExponentialMovingAverage::new(9).unwrap();
+
+        // choppy prices, then a steady climb from bar 30
+        let prices = (0..30).map(|i| 100 + i % 2).chain(102..116).map(Decimal::from);
+        let (mut choppy_rsi, mut choppy_zlrsi) = (Vec::new(), Vec::new());
+        for (i, price) in prices.enumerate() {
+            let plain = rsi.next(price);
+            let smoothed = plain.map(|plain| ema.next(plain));
+            let zero_lag = zlrsi.next(price);
+            let (Some(plain), Some(smoothed), Some(zero_lag)) = (plain, smoothed, zero_lag) else {
+                continue;
+            };
+
+            if i < 30 {
+                choppy_rsi.push(plain);
+                choppy_zlrsi.push(zero_lag);
+            } else {
+                // follows the RSI more closely than an EMA of the same length
+                assert!((plain - zero_lag).abs() < (plain - smoothed).abs(), "bar {}", i);
+            }
+            if i >= 36 {
+                assert!((plain - zero_lag).abs() < dec!(1), "bar {}", i);
+            }
+        }
+
+        // and is calmer than the RSI while prices chop
+        let range = |values: &[Decimal]| values.iter().max().unwrap() - values.iter().min().unwrap();
+        assert!(range(&choppy_zlrsi) < range(&choppy_rsi));
+    }
+
+    #[test]
+    fn test_zero_lag_reset() {
+        let mut zlrsi = ZeroLagRsi::new(2, 1).unwrap();
+        for price in [dec!(10), dec!(11), dec!(12)] {
+            zlrsi.next(price);
+        }
+        assert!(zlrsi.next(dec!(11)).is_some());
+
+        zlrsi.reset();
+        assert_eq!(zlrsi.next(dec!(11)), None);
+    }
+
+    #[test]
+    fn test_zero_lag_display() {
+        assert_eq!(format!("{}", ZeroLagRsi::default()), "ZLRSI(14, 9)");
+    }
+}
@@ -0,0 +1,239 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::Next;
+
+use crate::model::{Signal, SignalOutput};
+
+struct Vote<T> {
+    name: String,
+    weight: Decimal,
+    score: Box<dyn FnMut(&T) -> Decimal>,
+    last: Decimal,
+}
+
+/// Weighted vote over several indicators.
+///
+/// Each vote is a named closure that owns its indicators, feeds them the bar and returns a
+/// score from -1 (sell) to 1 (buy), with 0 for no opinion; a boolean condition is simply
+/// 1 or 0. Every bar is passed to all votes, and their scores are averaged by weight.
+/// Scores outside -1..1 are clamped.
+///
+/// # Formula
+///
+/// score = Σ(weight<sub>i</sub> * vote<sub>i</sub>) / Σweight<sub>i</sub>
+///
+/// Signal is `Buy` when score ≥ _buy_threshold_, `Sell` when score ≤ _sell_threshold_,
+/// and `Hold` otherwise. With no votes the score is 0.
+///
+/// The closures' state can't be reset from outside, so there is no `Reset`: build a new
+/// aggregator instead.
+///
+/// # Parameters
+///
+/// * _buy_threshold_ - score from which to buy (greater than _sell_threshold_)
+/// * _sell_threshold_ - score up to which to sell
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use ta::{DataItem, Next};
+/// use technical_analysis::model::Signal;
+/// use technical_analysis::rsi::RelativeStrengthIndex;
+/// use technical_analysis::signal::SignalAggregator;
+///
+/// let mut rsi = RelativeStrengthIndex::new(14).unwrap();
+/// let mut signals = SignalAggregator::new(dec!(0.5), dec!(-0.5))
+///     .unwrap()
+///     .vote("rsi", dec!(1), move |bar: &DataItem| match rsi.next(bar) {
+///         Some(rsi) if rsi < dec!(30) => dec!(1),
+///         Some(rsi) if rsi > dec!(70) => dec!(-1),
+///         _ => dec!(0),
+///     })
+///     .unwrap();
+///
+/// let bar = DataItem::builder().open(10.0).high(10.0).low(10.0).close(10.0).volume(1.0).build().unwrap();
+/// assert_eq!(signals.next(&bar).signal, Signal::Hold);
+/// ```
+pub struct SignalAggregator<T> {
+    buy_threshold: Decimal,
+    sell_threshold: Decimal,
+    votes: Vec<Vote<T>>,
+}
+
+impl<T> SignalAggregator<T> {
+    pub fn new(buy_threshold: Decimal, sell_threshold: Decimal) -> Result<Self> {
+        if sell_threshold >= buy_threshold {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            buy_threshold,
+            sell_threshold,
+            votes: Vec::new(),
+        })
+    }
+
+    /// Registers a vote. Fails if `weight` is not positive.
+    pub fn vote(mut self, name: &str, weight: Decimal, score: impl FnMut(&T) -> Decimal + 'static) -> Result<Self> {
+        if weight <= dec!(0) {
+            return Err(TaError::InvalidParameter);
+        }
+        self.votes.push(Vote {
+            name: name.to_string(),
+            weight,
+            score: Box::new(score),
+            last: dec!(0),
+        });
+        Ok(self)
+    }
+
+    /// Each vote's name and its clamped score on the last bar, in registration order.
+    pub fn last_votes(&self) -> Vec<(&str, Decimal)> {
+        self.votes.iter().map(|vote| (vote.name.as_str(), vote.last)).collect()
+    }
+}
+
+impl<T> Next<&T> for SignalAggregator<T> {
+    type Output = SignalOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let mut weighted = dec!(0);
+        let mut total_weight = dec!(0);
+        for vote in self.votes.iter_mut() {
+            vote.last = (vote.score)(input).clamp(dec!(-1), dec!(1));
+            weighted += vote.weight * vote.last;
+            total_weight += vote.weight;
+        }
+        let score = if total_weight.is_zero() {
+            dec!(0)
+        } else {
+            weighted / total_weight
+        };
+
+        let signal = if score >= self.buy_threshold {
+            Signal::Buy
+        } else if score <= self.sell_threshold {
+            Signal::Sell
+        } else {
+            Signal::Hold
+        };
+        SignalOutput { score, signal }
+    }
+}
+
+impl<T> fmt::Debug for SignalAggregator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SignalAggregator")
+            .field("buy_threshold", &self.buy_threshold)
+            .field("sell_threshold", &self.sell_threshold)
+            .field("votes", &self.last_votes())
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for SignalAggregator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<String> = self
+            .votes
+            .iter()
+            .map(|vote| format!("{}*{}", vote.weight, vote.name))
+            .collect();
+        write!(f, "SIGNAL({})", names.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use crate::macd::MovingAverageConvergenceDivergence;
+    use crate::rsi::RelativeStrengthIndex;
+    use super::*;
+
+    fn bar(close: f64) -> DataItem {
+        DataItem::builder()
+            .high(close)
+            .low(close)
+            .close(close)
+            .open(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    fn rsi_and_macd() -> SignalAggregator<DataItem> {
+        let mut rsi = RelativeStrengthIndex::new(14).unwrap();
+        let mut macd = MovingAverageConvergenceDivergence::new(3, 10, 4).unwrap();
+        let mut prev_histogram: Option<Decimal> = None;
+        SignalAggregator::new(dec!(0.75), dec!(-0.75))
+            .unwrap()
+            .vote("rsi_oversold", dec!(1), move |bar: &DataItem| match rsi.next(bar) {
+                Some(rsi) if rsi < dec!(30) => dec!(1),
+                Some(rsi) if rsi > dec!(70) => dec!(-1),
+                _ => dec!(0),
+            })
+            .unwrap()
+            .vote("macd_cross", dec!(2), move |bar: &DataItem| {
+                let histogram = macd.next(bar).histogram;
+                match prev_histogram.replace(histogram) {
+                    Some(prev) if prev <= dec!(0) && histogram > dec!(0) => dec!(1),
+                    Some(prev) if prev >= dec!(0) && histogram < dec!(0) => dec!(-1),
+                    _ => dec!(0),
+                }
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(SignalAggregator::<DataItem>::new(dec!(0.5), dec!(0.5)).is_err());
+        assert!(SignalAggregator::<DataItem>::new(dec!(-0.5), dec!(0.5)).is_err());
+        assert!(SignalAggregator::<DataItem>::new(dec!(0.5), dec!(-0.5)).is_ok());
+        let signals = SignalAggregator::<DataItem>::new(dec!(0.5), dec!(-0.5)).unwrap();
+        assert!(signals.vote("zero", dec!(0), |_| dec!(1)).is_err());
+    }
+
+    #[test]
+    fn test_combined_buy() {
+        let mut signals = rsi_and_macd();
+        let mut price = 100.0;
+        for _ in 0..30 {
+            price -= 1.0;
+            let out = signals.next(&bar(price));
+            assert_ne!(out.signal, Signal::Buy);
+        }
+        // RSI is deeply oversold and the rebound turns the MACD histogram positive
+        let out = signals.next(&bar(price + 0.5));
+        assert_eq!(signals.last_votes(), vec![("rsi_oversold", dec!(1)), ("macd_cross", dec!(1))]);
+        assert_eq!(out, SignalOutput { score: dec!(1), signal: Signal::Buy });
+
+        // the cross vote only fires on the crossing bar
+        let out = signals.next(&bar(price + 0.6));
+        assert_eq!(signals.last_votes()[1], ("macd_cross", dec!(0)));
+        assert_eq!(out.signal, Signal::Hold);
+    }
+
+    #[test]
+    fn test_weights_and_clamping() {
+        let mut signals = SignalAggregator::new(dec!(0.5), dec!(-0.5))
+            .unwrap()
+            .vote("buy", dec!(1), |_: &Decimal| dec!(5))
+            .unwrap()
+            .vote("sell", dec!(3), |_: &Decimal| dec!(-1))
+            .unwrap();
+        // (1 * 1 + 3 * -1) / 4
+        assert_eq!(signals.next(&dec!(0)), SignalOutput { score: dec!(-0.5), signal: Signal::Sell });
+    }
+
+    #[test]
+    fn test_no_votes() {
+        let mut signals = SignalAggregator::new(dec!(0.5), dec!(-0.5)).unwrap();
+        assert_eq!(signals.next(&dec!(1)), SignalOutput { score: dec!(0), signal: Signal::Hold });
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", rsi_and_macd()), "SIGNAL(1*rsi_oversold, 2*macd_cross)");
+    }
+}
@@ -4,6 +4,8 @@ use rust_decimal_macros::dec;
 use ta::errors::{Result, TaError};
 use ta::{Close, Next, Period, Reset};
 
+use crate::warmup::{WarmupPolicy, WithWarmupPolicy};
+
 /// Simple moving average (SMA).
 ///
 /// # Formula
@@ -20,6 +22,15 @@ use ta::{Close, Next, Period, Reset};
 ///
 /// * _period_ - number of periods (integer greater than 0)
 ///
+/// # Drift
+///
+/// The average keeps a running `sum`, adding each input and subtracting the one leaving
+/// the window. `Decimal` holds 28 significant digits, so when inputs of very different
+/// magnitudes meet in the sum their low digits are rounded away, and subtracting the large
+/// value later does not bring them back. Over long streams this leaves the sum slightly
+/// off the true window total. `recompute` rebuilds the sum from the window, and
+/// `with_recompute_interval` does so automatically every _interval_ updates.
+///
 /// # Example
 ///
 /// ```
@@ -38,13 +49,15 @@ use ta::{Close, Next, Period, Reset};
 /// * [Simple Moving Average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average#Simple_moving_average)
 ///
 #[doc(alias = "SMA")]
-#[derive(Debug, Clone)]
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub struct SimpleMovingAverage {
     period: usize,
     index: usize,
     count: usize,
     sum: Decimal,
     deque: Box<[Decimal]>,
+    recompute_interval: Option<usize>,
+    since_recompute: usize,
 }
 
 impl SimpleMovingAverage {
@@ -57,9 +70,47 @@ impl SimpleMovingAverage {
                 count: 0,
                 sum: dec!(0),
                 deque: vec![dec!(0); period].into_boxed_slice(),
+                recompute_interval: None,
+                since_recompute: 0,
             }),
         }
     }
+
+    /// SMA that calls `recompute` after every `interval` updates. Each recompute costs
+    /// O(period), so an interval of at least `period` keeps updates amortized O(1).
+    pub fn with_recompute_interval(period: usize, interval: usize) -> Result<Self> {
+        if interval == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            recompute_interval: Some(interval),
+            ..Self::new(period)?
+        })
+    }
+
+    /// Same average, but emits `None` until `min_periods` inputs have been seen. After that
+    /// it averages the available inputs, up to the last `period`.
+    pub fn with_min_periods(period: usize, min_periods: usize) -> Result<MinPeriodsSimpleMovingAverage> {
+        if min_periods == 0 || min_periods > period {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(MinPeriodsSimpleMovingAverage {
+            sma: SimpleMovingAverage::new(period)?,
+            min_periods,
+        })
+    }
+
+    /// Rebuilds the running sum from the window contents, discarding any rounding drift.
+    pub fn recompute(&mut self) {
+        self.sum = self.deque.iter().sum();
+        self.since_recompute = 0;
+    }
+
+    /// Wraps the SMA to pick its warmup output. Unwrapped, it averages whatever part of
+    /// the window is filled, which is `WarmupPolicy::Partial`.
+    pub fn warmup_policy(self, policy: WarmupPolicy) -> WithWarmupPolicy<Self> {
+        WithWarmupPolicy::new(self, policy)
+    }
 }
 
 impl Period for SimpleMovingAverage {
@@ -86,6 +137,13 @@ impl Next<Decimal> for SimpleMovingAverage {
         }
 
         self.sum = self.sum - old_val + input;
+
+        if let Some(interval) = self.recompute_interval {
+            self.since_recompute += 1;
+            if self.since_recompute >= interval {
+                self.recompute();
+            }
+        }
         self.sum / (Decimal::from(self.count))
     }
 }
@@ -103,6 +161,7 @@ impl Reset for SimpleMovingAverage {
         self.index = 0;
         self.count = 0;
         self.sum = dec!(0);
+        self.since_recompute = 0;
         for i in 0..self.period {
             self.deque[i] = dec!(0);
         }
@@ -121,8 +180,71 @@ impl fmt::Display for SimpleMovingAverage {
     }
 }
 
+/// Simple moving average with a minimum number of inputs before it emits.
+///
+/// Wraps [SimpleMovingAverage](struct.SimpleMovingAverage.html) and returns `None` until
+/// `min_periods` inputs have been seen, then the average of the partially or fully filled
+/// window. Create it with `SimpleMovingAverage::with_min_periods`.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct MinPeriodsSimpleMovingAverage {
+    sma: SimpleMovingAverage,
+    min_periods: usize,
+}
+
+impl MinPeriodsSimpleMovingAverage {
+    pub fn min_periods(&self) -> usize {
+        self.min_periods
+    }
+}
+
+impl Period for MinPeriodsSimpleMovingAverage {
+    fn period(&self) -> usize {
+        self.sma.period()
+    }
+}
+
+impl Next<Decimal> for MinPeriodsSimpleMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let current = self.sma.next(input);
+        if self.sma.count < self.min_periods {
+            None
+        } else {
+            Some(current)
+        }
+    }
+}
+
+impl<T: Close> Next<&T> for MinPeriodsSimpleMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for MinPeriodsSimpleMovingAverage {
+    fn reset(&mut self) {
+        self.sma.reset();
+    }
+}
+
+impl Default for MinPeriodsSimpleMovingAverage {
+    fn default() -> Self {
+        SimpleMovingAverage::with_min_periods(9, 9).unwrap()
+    }
+}
+
+impl fmt::Display for MinPeriodsSimpleMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SMA({}, min {})", self.sma.period(), self.min_periods)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::test_util::assert_full_reset;
     use super::*;
 
     #[test]
@@ -143,6 +265,61 @@ mod tests {
         assert_eq!(sma.next(dec!(2)), dec!(5.0));
     }
 
+    #[test]
+    fn test_next_only_depends_on_window() {
+        let mut a = SimpleMovingAverage::new(3).unwrap();
+        let mut b = SimpleMovingAverage::new(3).unwrap();
+
+        for value in [dec!(1000), dec!(-250.5), dec!(0.001), dec!(77)] {
+            a.next(value);
+        }
+        b.next(dec!(3));
+
+        a.next(dec!(10));
+        b.next(dec!(10));
+        a.next(dec!(20));
+        b.next(dec!(20));
+        assert_eq!(a.next(dec!(30)), dec!(20));
+        assert_eq!(b.next(dec!(30)), dec!(20));
+    }
+
+    // 10^15 plus 18 decimal places needs more than 28 digits, so the sum rounds them away
+    // and keeps the error after the large value leaves the window.
+    const LARGE: Decimal = dec!(1000000000000000);
+    const SMALL: Decimal = dec!(0.123456789012345678);
+
+    #[test]
+    fn test_recompute() {
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        sma.next(LARGE);
+        sma.next(SMALL);
+        let drifted = sma.next(dec!(0));
+        assert_ne!(drifted, SMALL / dec!(2));
+
+        sma.recompute();
+        assert_eq!(sma.sum, SMALL);
+        assert_eq!(sma.next(dec!(0)), dec!(0));
+    }
+
+    #[test]
+    fn test_recompute_interval() {
+        assert!(SimpleMovingAverage::with_recompute_interval(2, 0).is_err());
+        assert!(SimpleMovingAverage::with_recompute_interval(0, 10).is_err());
+
+        let mut plain = SimpleMovingAverage::new(2).unwrap();
+        let mut sma = SimpleMovingAverage::with_recompute_interval(2, 3).unwrap();
+        for _ in 0..100 {
+            for value in [LARGE, SMALL, dec!(0)] {
+                plain.next(value);
+                sma.next(value);
+            }
+        }
+
+        // the window is SMALL, 0 after every third update, right when the sum is rebuilt
+        assert_eq!(sma.sum, SMALL);
+        assert_ne!(plain.sum, SMALL);
+    }
+
     // #[test]
     // fn test_next_with_bars() {
     //     fn bar(close: f64) -> Bar {
@@ -156,6 +333,52 @@ mod tests {
     //     assert_eq!(sma.next(&bar(1.0)), 4.0);
     // }
 
+    #[test]
+    fn test_warmup_policy() {
+        let outputs = |policy| {
+            let mut sma = SimpleMovingAverage::new(3).unwrap().warmup_policy(policy);
+            [dec!(2), dec!(4), dec!(6), dec!(8)].map(|input| sma.next(input))
+        };
+        let partial = [Some(dec!(2)), Some(dec!(3)), Some(dec!(4)), Some(dec!(6))];
+        assert_eq!(outputs(WarmupPolicy::Emit), partial);
+        assert_eq!(outputs(WarmupPolicy::Partial), partial);
+        assert_eq!(outputs(WarmupPolicy::None), [None, None, Some(dec!(4)), Some(dec!(6))]);
+    }
+
+    #[test]
+    fn test_with_min_periods() {
+        assert!(SimpleMovingAverage::with_min_periods(3, 0).is_err());
+        assert!(SimpleMovingAverage::with_min_periods(3, 4).is_err());
+        assert!(SimpleMovingAverage::with_min_periods(0, 0).is_err());
+
+        let mut sma = SimpleMovingAverage::with_min_periods(4, 2).unwrap();
+        assert_eq!(sma.next(dec!(2)), None);
+        assert_eq!(sma.next(dec!(4)), Some(dec!(3)));
+        assert_eq!(sma.next(dec!(6)), Some(dec!(4)));
+        assert_eq!(sma.next(dec!(8)), Some(dec!(5)));
+        assert_eq!(sma.next(dec!(10)), Some(dec!(7)));
+
+        sma.reset();
+        assert_eq!(sma.next(dec!(1)), None);
+    }
+
+    #[test]
+    fn test_with_min_periods_full_window() {
+        let mut sma = SimpleMovingAverage::with_min_periods(3, 3).unwrap();
+        assert_eq!(sma.next(dec!(2)), None);
+        assert_eq!(sma.next(dec!(4)), None);
+        assert_eq!(sma.next(dec!(6)), Some(dec!(4)));
+        assert_eq!(sma.next(dec!(8)), Some(dec!(6)));
+        assert_eq!(format!("{}", sma), "SMA(3, min 3)");
+    }
+
+    #[test]
+    fn test_full_reset() {
+        let series = [dec!(4), dec!(5), dec!(6), dec!(2.5), dec!(9), dec!(1)];
+        assert_full_reset(SimpleMovingAverage::new(4).unwrap(), &series);
+        assert_full_reset(SimpleMovingAverage::with_recompute_interval(2, 3).unwrap(), &series);
+    }
+
     #[test]
     fn test_reset() {
         let mut sma = SimpleMovingAverage::new(4).unwrap();
@@ -167,6 +390,19 @@ mod tests {
         assert_eq!(sma.next(dec!(99)), dec!(99));
     }
 
+    #[test]
+    fn test_serde_round_trip() {
+        let mut sma = SimpleMovingAverage::new(3).unwrap();
+        sma.next(dec!(1));
+        sma.next(dec!(2.5));
+
+        let json = serde_json::to_string(&sma).unwrap();
+        let mut restored: SimpleMovingAverage = serde_json::from_str(&json).unwrap();
+        for value in [dec!(4), dec!(0.1), dec!(7)] {
+            assert_eq!(restored.next(value), sma.next(value));
+        }
+    }
+
     #[test]
     fn test_default() {
         SimpleMovingAverage::default();
@@ -0,0 +1,188 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Period, Reset};
+
+use crate::math::sqrt;
+
+/// Spearman rank correlation.
+///
+/// Correlation between the ranks of two series over the last _period_ pairs. It measures
+/// how consistently the series move in the same order rather than how linearly they are
+/// related, so a single outlier moves it far less than it moves Pearson's correlation.
+///
+/// # Formula
+///
+/// ρ = Pearson correlation of rank(x) and rank(y)
+///
+/// Tied values share the average of the ranks they span, which reduces to the classic
+/// 1 - 6Σd<sup>2</sup> / (n(n<sup>2</sup> - 1)) when there are no ties. Ranks are
+/// recomputed on every update, O(period<sup>2</sup>).
+///
+/// Returns `None` until _period_ pairs have been seen, and when either window holds a
+/// single repeated value.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 1)
+#[doc(alias = "SPEARMAN")]
+#[derive(Debug, Clone)]
+pub struct SpearmanCorrelation {
+    period: usize,
+    x: VecDeque<Decimal>,
+    y: VecDeque<Decimal>,
+}
+
+impl SpearmanCorrelation {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 | 1 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                x: VecDeque::with_capacity(period),
+                y: VecDeque::with_capacity(period),
+            }),
+        }
+    }
+
+    /// Feeds one value of each series, taken at the same bar.
+    pub fn next(&mut self, x: Decimal, y: Decimal) -> Option<Decimal> {
+        if self.x.len() == self.period {
+            self.x.pop_front();
+            self.y.pop_front();
+        }
+        self.x.push_back(x);
+        self.y.push_back(y);
+        if self.x.len() < self.period {
+            return None;
+        }
+
+        let (rank_x, rank_y) = (ranks(&self.x), ranks(&self.y));
+        // ranks always average to (n + 1) / 2
+        let mean = Decimal::from(self.period + 1) / dec!(2);
+        let (mut covariance, mut variance_x, mut variance_y) = (dec!(0), dec!(0), dec!(0));
+        for (rx, ry) in rank_x.iter().zip(rank_y.iter()) {
+            let (dx, dy) = (rx - mean, ry - mean);
+            covariance += dx * dy;
+            variance_x += dx * dx;
+            variance_y += dy * dy;
+        }
+        if variance_x.is_zero() || variance_y.is_zero() {
+            return None;
+        }
+        Some(covariance / sqrt(variance_x * variance_y))
+    }
+}
+
+/// 1-based rank of every value in the window, ties getting the mean of their ranks.
+fn ranks(values: &VecDeque<Decimal>) -> Vec<Decimal> {
+    values
+        .iter()
+        .map(|value| {
+            let below = values.iter().filter(|other| *other < value).count();
+            let equal = values.iter().filter(|other| *other == value).count();
+            Decimal::from(2 * below + equal + 1) / dec!(2)
+        })
+        .collect()
+}
+
+impl Period for SpearmanCorrelation {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Reset for SpearmanCorrelation {
+    fn reset(&mut self) {
+        self.x.clear();
+        self.y.clear();
+    }
+}
+
+impl Default for SpearmanCorrelation {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for SpearmanCorrelation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SPEARMAN({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(SpearmanCorrelation::new(1).is_err());
+        assert!(SpearmanCorrelation::new(2).is_ok());
+    }
+
+    #[test]
+    fn test_ranks() {
+        let values = VecDeque::from(vec![dec!(10), dec!(30), dec!(20), dec!(30)]);
+        assert_eq!(ranks(&values), vec![dec!(1), dec!(3.5), dec!(2), dec!(3.5)]);
+    }
+
+    #[test]
+    fn test_monotonic() {
+        let mut spearman = SpearmanCorrelation::new(5).unwrap();
+        let mut last = None;
+        for i in 0..10 {
+            // y grows non-linearly and ends on an outlier, but keeps the order of x
+            let x = Decimal::from(i);
+            let y = if i == 9 { dec!(10000) } else { Decimal::from(i * i) };
+            last = spearman.next(x, y);
+            if i < 4 {
+                assert_eq!(last, None);
+            }
+        }
+        assert_eq!(last.unwrap().round_dp(12), dec!(1));
+    }
+
+    #[test]
+    fn test_next() {
+        let mut spearman = SpearmanCorrelation::new(5).unwrap();
+        let pairs = [(1, 5), (2, 6), (3, 7), (4, 8), (5, 7)];
+        let out = pairs.iter().map(|(x, y)| spearman.next(Decimal::from(*x), Decimal::from(*y))).last().unwrap();
+        // y ranks 1, 2, 3.5, 5, 3.5
+        assert_eq!(out.unwrap().round_dp(6), dec!(0.820783));
+
+        let mut reversed = SpearmanCorrelation::new(3).unwrap();
+        reversed.next(dec!(1), dec!(3));
+        reversed.next(dec!(2), dec!(2));
+        assert_eq!(reversed.next(dec!(3), dec!(1)).unwrap().round_dp(12), dec!(-1));
+    }
+
+    #[test]
+    fn test_flat_series() {
+        let mut spearman = SpearmanCorrelation::new(2).unwrap();
+        spearman.next(dec!(1), dec!(4));
+        assert_eq!(spearman.next(dec!(2), dec!(4)), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut spearman = SpearmanCorrelation::new(2).unwrap();
+        spearman.next(dec!(1), dec!(1));
+        assert!(spearman.next(dec!(2), dec!(2)).is_some());
+
+        spearman.reset();
+        assert_eq!(spearman.next(dec!(3), dec!(3)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(SpearmanCorrelation::default().period(), 20);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", SpearmanCorrelation::new(10).unwrap()), "SPEARMAN(10)");
+    }
+}
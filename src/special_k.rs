@@ -0,0 +1,205 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::{Close, Next, Reset};
+
+use crate::roc::WeightedRocSum;
+use crate::simple_moving_average::{MinPeriodsSimpleMovingAverage, SimpleMovingAverage};
+
+/// (ROC period, SMA period, weight) of each of Pring's daily components.
+const COMPONENTS: [(usize, usize, u32); 12] = [
+    (10, 10, 1),
+    (15, 10, 2),
+    (20, 10, 3),
+    (30, 15, 4),
+    (40, 50, 1),
+    (65, 65, 2),
+    (75, 75, 3),
+    (100, 100, 4),
+    (195, 130, 1),
+    (265, 130, 2),
+    (390, 130, 3),
+    (530, 195, 4),
+];
+
+/// Pring's Special K.
+///
+/// A long-term momentum line built from twelve rates of change spanning short,
+/// intermediate and long cycles. Each ROC is smoothed by its own SMA and weighted before
+/// the results are summed, so the line peaks and troughs with the primary trend.
+///
+/// # Formula
+///
+/// Special K = Σ weight<sub>i</sub> * SMA<sub>sma<sub>i</sub></sub>(ROC<sub>roc<sub>i</sub></sub>)
+///
+/// with Pring's daily (roc, sma, weight) set:
+///
+/// (10, 10, 1), (15, 10, 2), (20, 10, 3), (30, 15, 4), (40, 50, 1), (65, 65, 2),
+/// (75, 75, 3), (100, 100, 4), (195, 130, 1), (265, 130, 2), (390, 130, 3), (530, 195, 4)
+///
+/// The SMA is linear, so ROCs sharing an SMA period are summed by a
+/// [WeightedRocSum](../roc/struct.WeightedRocSum.html) and smoothed once.
+///
+/// Returns `None` until every component is ready, i.e. for the first 724 values.
+///
+/// # Example
+///
+/// ```
+/// use technical_analysis::special_k::SpecialK;
+/// use ta::Next;
+/// use rust_decimal::Decimal;
+///
+/// let mut special_k = SpecialK::new();
+/// let out: Vec<_> = (0..725).map(|i| special_k.next(Decimal::from(100 + i))).collect();
+/// assert!(out[723].is_none());
+/// assert!(out[724].is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpecialK {
+    groups: Vec<(WeightedRocSum, MinPeriodsSimpleMovingAverage)>,
+}
+
+impl SpecialK {
+    pub fn new() -> Self {
+        let mut sma_periods: Vec<usize> = COMPONENTS.iter().map(|(_, sma, _)| *sma).collect();
+        sma_periods.dedup();
+        let groups = sma_periods
+            .into_iter()
+            .map(|sma_period| {
+                let specs = COMPONENTS
+                    .iter()
+                    .filter(|(_, sma, _)| *sma == sma_period)
+                    .map(|(roc, _, weight)| (*roc, Decimal::from(*weight)))
+                    .collect();
+                (
+                    WeightedRocSum::new(specs).unwrap(),
+                    SimpleMovingAverage::with_min_periods(sma_period, sma_period).unwrap(),
+                )
+            })
+            .collect();
+        Self { groups }
+    }
+}
+
+impl Next<Decimal> for SpecialK {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        // every group sees every input, even once the sum is known to be `None`
+        self.groups
+            .iter_mut()
+            .map(|(rocs, sma)| rocs.next(input).and_then(|sum| sma.next(sum)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .sum()
+    }
+}
+
+impl<T: Close> Next<&T> for SpecialK {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for SpecialK {
+    fn reset(&mut self) {
+        for (rocs, sma) in self.groups.iter_mut() {
+            rocs.reset();
+            sma.reset();
+        }
+    }
+}
+
+impl Default for SpecialK {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for SpecialK {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SPECIAL_K")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+    use crate::roc::RateOfChange;
+
+    #[test]
+    fn test_matches_separately_smoothed_rocs() {
+        let mut special_k = SpecialK::new();
+        let mut components: Vec<_> = COMPONENTS
+            .iter()
+            .map(|(roc, sma, weight)| {
+                (
+                    RateOfChange::new(*roc).unwrap(),
+                    SimpleMovingAverage::with_min_periods(*sma, *sma).unwrap(),
+                    Decimal::from(*weight),
+                )
+            })
+            .collect();
+
+        for i in 0..800 {
+            let input = Decimal::from(1000 + (i * 37) % 101);
+            let expected: Option<Decimal> = components
+                .iter_mut()
+                .map(|(roc, sma, weight)| {
+                    roc.next(input).and_then(|value| sma.next(value)).map(|value| value * *weight)
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .sum();
+            let out = special_k.next(input);
+            assert_eq!(out.is_some(), i >= 724);
+            if let (Some(out), Some(expected)) = (out, expected) {
+                assert_eq!(out.round_dp(12), expected.round_dp(12));
+            }
+        }
+    }
+
+    #[test]
+    fn test_turns_up_after_rally() {
+        let mut special_k = SpecialK::new();
+        let mut price = dec!(200);
+        let mut out = None;
+        // a long decline, then a durable rally
+        for _ in 0..800 {
+            price *= dec!(0.999);
+            out = special_k.next(price);
+        }
+        let trough = out.unwrap();
+        assert!(trough < dec!(0));
+
+        let mut last = trough;
+        for _ in 0..300 {
+            price *= dec!(1.002);
+            last = special_k.next(price).unwrap();
+        }
+        assert!(last > trough);
+        assert!(last > dec!(0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut special_k = SpecialK::new();
+        for i in 0..730 {
+            special_k.next(Decimal::from(100 + i));
+        }
+        special_k.reset();
+        assert_eq!(special_k.next(dec!(100)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        SpecialK::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", SpecialK::new()), "SPECIAL_K");
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::Result;
+use ta::{Close, High, Low, Next, Period, Reset};
+
+use crate::bollinger_bands::BollingerBands;
+use crate::keltner_channel::KeltnerChannel;
+use crate::linear_regression::LinearRegression;
+use crate::model::{Squeeze, SqueezeMomentumOutput};
+use crate::simple_moving_average::SimpleMovingAverage;
+
+/// Squeeze Momentum (SQZ), LazyBear's variant of John Carter's TTM Squeeze.
+///
+/// The squeeze state compares Bollinger Bands with a Keltner Channel: bands inside the
+/// channel mean volatility is compressed (`On`), bands outside it mean the squeeze has
+/// fired (`Off`). Momentum is the linear-regression value of the close's distance from
+/// the centre of its recent range.
+///
+/// # Formula
+///
+/// Squeeze = `On` if lower<sub>BB</sub> > lower<sub>KC</sub> and upper<sub>BB</sub> < upper<sub>KC</sub>,
+/// `Off` if lower<sub>BB</sub> < lower<sub>KC</sub> and upper<sub>BB</sub> > upper<sub>KC</sub>, otherwise `None`
+///
+/// Mid = ((HH + LL) / 2 + SMA(close)) / 2
+///
+/// Momentum = LINREG(close - Mid)
+///
+/// Where _HH_, _LL_, the SMA and the regression all span _kc_period_ bars. Returns `None`
+/// until both bands and the regression are available.
+///
+/// # Parameters
+///
+/// * _bb_period_, _bb_mult_ - Bollinger Bands period and standard deviation multiplier
+/// * _kc_period_, _kc_mult_ - Keltner Channel period and ATR multiplier (period greater than 1)
+#[doc(alias = "SQZ")]
+#[derive(Debug, Clone)]
+pub struct SqueezeMomentum {
+    bb: BollingerBands,
+    kc: KeltnerChannel,
+    sma: SimpleMovingAverage,
+    linreg: LinearRegression,
+    range: VecDeque<(Decimal, Decimal)>,
+}
+
+impl SqueezeMomentum {
+    pub fn new(bb_period: usize, bb_mult: Decimal, kc_period: usize, kc_mult: Decimal) -> Result<Self> {
+        Ok(Self {
+            bb: BollingerBands::new(bb_period, bb_mult)?,
+            kc: KeltnerChannel::new(kc_period, kc_mult)?,
+            sma: SimpleMovingAverage::new(kc_period)?,
+            linreg: LinearRegression::new(kc_period)?,
+            range: VecDeque::with_capacity(kc_period),
+        })
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for SqueezeMomentum {
+    type Output = Option<SqueezeMomentumOutput>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let close = Decimal::from_f64(input.close()).unwrap();
+        let bb = self.bb.next(close);
+        let kc = self.kc.next(input);
+        let sma = self.sma.next(close);
+
+        if self.range.len() == self.kc.period() {
+            self.range.pop_front();
+        }
+        self.range.push_back((
+            Decimal::from_f64(input.high()).unwrap(),
+            Decimal::from_f64(input.low()).unwrap(),
+        ));
+
+        let momentum = if self.range.len() == self.kc.period() {
+            let highest = self.range.iter().map(|(high, _)| *high).max().unwrap();
+            let lowest = self.range.iter().map(|(_, low)| *low).min().unwrap();
+            let mid = ((highest + lowest) / dec!(2) + sma) / dec!(2);
+            self.linreg.next(close - mid)
+        } else {
+            None
+        };
+
+        let (bb, kc, momentum) = (bb?, kc?, momentum?);
+        let squeeze = if bb.lower > kc.lower && bb.upper < kc.upper {
+            Squeeze::On
+        } else if bb.lower < kc.lower && bb.upper > kc.upper {
+            Squeeze::Off
+        } else {
+            Squeeze::None
+        };
+        Some(SqueezeMomentumOutput { momentum, squeeze })
+    }
+}
+
+impl Reset for SqueezeMomentum {
+    fn reset(&mut self) {
+        self.bb.reset();
+        self.kc.reset();
+        self.sma.reset();
+        self.linreg.reset();
+        self.range.clear();
+    }
+}
+
+impl Default for SqueezeMomentum {
+    fn default() -> Self {
+        Self::new(20, dec!(2.0), 20, dec!(1.5)).unwrap()
+    }
+}
+
+impl fmt::Display for SqueezeMomentum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SQZ({}, {}, {}, {})",
+            self.bb.period(),
+            self.bb.multiplier(),
+            self.kc.period(),
+            self.kc.multiplier()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder().open(close).high(high).low(low).close(close).volume(1.0).build().unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(SqueezeMomentum::new(0, dec!(2), 20, dec!(1.5)).is_err());
+        assert!(SqueezeMomentum::new(20, dec!(2), 1, dec!(1.5)).is_err());
+        assert!(SqueezeMomentum::new(20, dec!(0), 20, dec!(1.5)).is_err());
+        assert!(SqueezeMomentum::new(20, dec!(2), 20, dec!(1.5)).is_ok());
+    }
+
+    #[test]
+    fn test_warmup() {
+        let mut sqz = SqueezeMomentum::new(4, dec!(2), 4, dec!(1.5)).unwrap();
+        for _ in 0..6 {
+            assert_eq!(sqz.next(&bar(11.0, 9.0, 10.0)), None);
+        }
+        assert!(sqz.next(&bar(11.0, 9.0, 10.0)).is_some());
+    }
+
+    #[test]
+    fn test_squeeze_states() {
+        let mut sqz = SqueezeMomentum::new(4, dec!(2), 4, dec!(1.5)).unwrap();
+
+        // wide bars around a steady close: bands collapse inside the channel
+        let mut out = None;
+        for i in 0..10 {
+            let close = if i % 2 == 0 { 100.0 } else { 100.2 };
+            out = sqz.next(&bar(close + 2.0, close - 2.0, close));
+        }
+        let out = out.unwrap();
+        assert_eq!(out.squeeze, Squeeze::On);
+        assert!(out.momentum.abs() < dec!(1));
+
+        // tight bars with the close jumping: bands blow out past the channel
+        let mut out = None;
+        for i in 1..=8 {
+            let close = 100.0 + 3.0 * (i * i) as f64;
+            out = sqz.next(&bar(close + 0.1, close - 0.1, close));
+        }
+        let out = out.unwrap();
+        assert_eq!(out.squeeze, Squeeze::Off);
+        assert!(out.momentum > dec!(0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sqz = SqueezeMomentum::new(4, dec!(2), 4, dec!(1.5)).unwrap();
+        for _ in 0..8 {
+            sqz.next(&bar(11.0, 9.0, 10.0));
+        }
+
+        sqz.reset();
+        assert_eq!(sqz.next(&bar(11.0, 9.0, 10.0)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        SqueezeMomentum::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", SqueezeMomentum::default()), "SQZ(20, 2.0, 20, 1.5)");
+    }
+}
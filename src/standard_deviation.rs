@@ -0,0 +1,161 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::math::sqrt;
+
+/// Standard deviation (SD).
+///
+/// Population standard deviation of the last _period_ values, kept up to date with
+/// rolling sums so each update is O(1).
+///
+/// # Formula
+///
+/// SD = sqrt(Σp<sub>i</sub><sup>2</sup> / period - (Σp<sub>i</sub> / period)<sup>2</sup>)
+///
+/// The square root comes from the [math](../math/index.html) helpers.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[doc(alias = "SD")]
+#[derive(Debug, Clone)]
+pub struct StandardDeviation {
+    period: usize,
+    window: VecDeque<Decimal>,
+    sum: Decimal,
+    sum_sq: Decimal,
+}
+
+impl StandardDeviation {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                window: VecDeque::with_capacity(period),
+                sum: dec!(0),
+                sum_sq: dec!(0),
+            }),
+        }
+    }
+
+    /// Mean of the current window, `None` until it is full.
+    pub fn mean(&self) -> Option<Decimal> {
+        if self.window.len() < self.period {
+            return None;
+        }
+        Some(self.sum / Decimal::from(self.period))
+    }
+}
+
+impl Period for StandardDeviation {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for StandardDeviation {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        if self.window.len() == self.period {
+            let old = self.window.pop_front().unwrap();
+            self.sum -= old;
+            self.sum_sq -= old * old;
+        }
+        self.window.push_back(input);
+        self.sum += input;
+        self.sum_sq += input * input;
+
+        let mean = self.mean()?;
+        let variance = (self.sum_sq / Decimal::from(self.period) - mean * mean).max(dec!(0));
+        Some(sqrt(variance))
+    }
+}
+
+impl<T: Close> Next<&T> for StandardDeviation {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for StandardDeviation {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.sum = dec!(0);
+        self.sum_sq = dec!(0);
+    }
+}
+
+impl Default for StandardDeviation {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for StandardDeviation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SD({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(StandardDeviation::new(0).is_err());
+        assert!(StandardDeviation::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut sd = StandardDeviation::new(4).unwrap();
+        assert_eq!(sd.next(dec!(10)), None);
+        assert_eq!(sd.next(dec!(20)), None);
+        assert_eq!(sd.next(dec!(30)), None);
+        // mean 25, deviations -15, -5, 5, 15
+        assert_eq!(sd.next(dec!(40)).unwrap().round_dp(8), dec!(11.18033989));
+        assert_eq!(sd.mean(), Some(dec!(25)));
+        // window 20, 30, 40, 50
+        assert_eq!(sd.next(dec!(50)).unwrap().round_dp(8), dec!(11.18033989));
+        assert_eq!(sd.next(dec!(50)).unwrap().round_dp(8), dec!(8.29156198));
+    }
+
+    #[test]
+    fn test_constant_input() {
+        let mut sd = StandardDeviation::new(3).unwrap();
+        sd.next(dec!(7));
+        sd.next(dec!(7));
+        assert_eq!(sd.next(dec!(7)), Some(dec!(0)));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sd = StandardDeviation::new(2).unwrap();
+        sd.next(dec!(4));
+        assert!(sd.next(dec!(6)).is_some());
+
+        sd.reset();
+        assert_eq!(sd.next(dec!(99)), None);
+        assert_eq!(sd.next(dec!(99)), Some(dec!(0)));
+    }
+
+    #[test]
+    fn test_default() {
+        StandardDeviation::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let sd = StandardDeviation::new(20).unwrap();
+        assert_eq!(format!("{}", sd), "SD(20)");
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, High, Low, Next, Period, Reset};
+
+use crate::average_true_range::AverageTrueRange;
+use crate::model::Trend;
+
+/// ATR trailing stop that only tightens.
+///
+/// For a long position the stop sits _multiplier * ATR_ below the close, but it never
+/// moves down: each bar it is raised to the new level or stays where it was. A stop created
+/// with `short` mirrors this above the close and only ever moves down. Unlike
+/// [VolatilityStop](../volatility_stop/struct.VolatilityStop.html) it does not reverse when
+/// price crosses it; closing the position is left to the caller.
+///
+/// # Formula
+///
+/// Long: Stop<sub>t</sub> = max(Stop<sub>t-1</sub>, close - multiplier * ATR)
+///
+/// Short: Stop<sub>t</sub> = min(Stop<sub>t-1</sub>, close + multiplier * ATR)
+///
+/// Returns `None` until the ATR is available.
+///
+/// # Parameters
+///
+/// * _atr_period_ - ATR period (integer greater than 0)
+/// * _multiplier_ - ATR multiple for the stop distance (greater than 0)
+#[derive(Debug, Clone)]
+pub struct TrailingStop {
+    atr: AverageTrueRange,
+    multiplier: Decimal,
+    side: Trend,
+    stop: Option<Decimal>,
+}
+
+impl TrailingStop {
+    /// Trailing stop for a long position.
+    pub fn new(atr_period: usize, multiplier: Decimal) -> Result<Self> {
+        if multiplier <= dec!(0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            atr: AverageTrueRange::new(atr_period)?,
+            multiplier,
+            side: Trend::Up,
+            stop: None,
+        })
+    }
+
+    /// Trailing stop for a short position.
+    pub fn short(atr_period: usize, multiplier: Decimal) -> Result<Self> {
+        Ok(Self {
+            side: Trend::Down,
+            ..Self::new(atr_period, multiplier)?
+        })
+    }
+}
+
+impl Period for TrailingStop {
+    fn period(&self) -> usize {
+        self.atr.period()
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for TrailingStop {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let atr = self.atr.next(input)?;
+        let close = Decimal::from_f64(input.close()).unwrap();
+        let stop = match (self.side, self.stop) {
+            (Trend::Up, None) => close - self.multiplier * atr,
+            (Trend::Up, Some(prev)) => prev.max(close - self.multiplier * atr),
+            (Trend::Down, None) => close + self.multiplier * atr,
+            (Trend::Down, Some(prev)) => prev.min(close + self.multiplier * atr),
+        };
+        self.stop = Some(stop);
+        Some(stop)
+    }
+}
+
+impl Reset for TrailingStop {
+    fn reset(&mut self) {
+        self.atr.reset();
+        self.stop = None;
+    }
+}
+
+impl Default for TrailingStop {
+    fn default() -> Self {
+        Self::new(14, dec!(3.0)).unwrap()
+    }
+}
+
+impl fmt::Display for TrailingStop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let side = match self.side {
+            Trend::Up => "LONG",
+            Trend::Down => "SHORT",
+        };
+        write!(f, "TSTOP_{}({}, {})", side, self.atr.period(), self.multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use crate::test_util::assert_full_reset;
+    use super::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder().high(high).low(low).close(close).open(close).volume(1.0).build().unwrap()
+    }
+
+    fn series() -> Vec<DataItem> {
+        [
+            (10.0, 9.0, 9.5), (10.5, 9.5, 10.0), (11.5, 10.0, 11.0), (11.0, 9.0, 9.5),
+            (12.0, 9.5, 11.5), (13.0, 11.0, 12.5), (12.5, 10.0, 10.5), (11.0, 8.5, 9.0),
+            (10.0, 8.0, 8.5), (9.5, 7.0, 7.5), (10.0, 7.5, 9.5), (9.0, 7.0, 7.5),
+        ]
+        .iter()
+        .map(|&(high, low, close)| bar(high, low, close))
+        .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(TrailingStop::new(0, dec!(3)).is_err());
+        assert!(TrailingStop::new(14, dec!(0)).is_err());
+        assert!(TrailingStop::new(14, dec!(3)).is_ok());
+        assert!(TrailingStop::short(14, dec!(-1)).is_err());
+        assert!(TrailingStop::short(14, dec!(3)).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut stop = TrailingStop::new(1, dec!(2)).unwrap();
+        // ATR 1
+        assert_eq!(stop.next(&bar(10.0, 9.0, 9.5)), Some(dec!(7.5)));
+        // TR 1.5: 11 - 3 = 8
+        assert_eq!(stop.next(&bar(11.0, 9.5, 11.0)), Some(dec!(8)));
+        // TR 2: 9.5 - 4 = 5.5 would loosen it
+        assert_eq!(stop.next(&bar(11.0, 9.0, 9.5)), Some(dec!(8)));
+    }
+
+    #[test]
+    fn test_long_only_ratchets_up() {
+        let mut stop = TrailingStop::new(3, dec!(2)).unwrap();
+        let stops: Vec<Decimal> = series().iter().filter_map(|bar| stop.next(bar)).collect();
+        assert!(stops.windows(2).all(|pair| pair[1] >= pair[0]));
+        assert!(stops.windows(2).any(|pair| pair[1] > pair[0]));
+    }
+
+    #[test]
+    fn test_short_only_ratchets_down() {
+        let mut stop = TrailingStop::short(3, dec!(2)).unwrap();
+        let stops: Vec<Decimal> = series().iter().filter_map(|bar| stop.next(bar)).collect();
+        assert!(stops.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(stops.windows(2).any(|pair| pair[1] < pair[0]));
+    }
+
+    #[test]
+    fn test_reset() {
+        let bars = series();
+        let bars: Vec<&DataItem> = bars.iter().collect();
+        assert_full_reset(TrailingStop::new(3, dec!(2)).unwrap(), &bars);
+        assert_full_reset(TrailingStop::short(3, dec!(2)).unwrap(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        TrailingStop::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", TrailingStop::default()), "TSTOP_LONG(14, 3.0)");
+        assert_eq!(format!("{}", TrailingStop::short(10, dec!(2)).unwrap()), "TSTOP_SHORT(10, 2)");
+    }
+}
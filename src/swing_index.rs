@@ -0,0 +1,176 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, High, Low, Next, Open, Reset};
+
+/// Accumulative Swing Index (ASI).
+///
+/// Running total of Wilder's Swing Index, which scores each bar's move against the previous
+/// bar from all four prices. The first bar has no previous bar and scores zero.
+///
+/// # Formula
+///
+/// SI = 50 * (C - C<sub>y</sub> + 0.5 * (C - O) + 0.25 * (C<sub>y</sub> - O<sub>y</sub>)) / R * K / T
+///
+/// Where:
+///
+/// * _K_ - max(|H - C<sub>y</sub>|, |L - C<sub>y</sub>|)
+/// * _T_ - limit move
+/// * _R_ - depends on which of |H - C<sub>y</sub>|, |L - C<sub>y</sub>| and |H - L| is largest:
+///   * |H - C<sub>y</sub>|: |H - C<sub>y</sub>| - 0.5 * |L - C<sub>y</sub>| + 0.25 * |C<sub>y</sub> - O<sub>y</sub>|
+///   * |L - C<sub>y</sub>|: |L - C<sub>y</sub>| - 0.5 * |H - C<sub>y</sub>| + 0.25 * |C<sub>y</sub> - O<sub>y</sub>|
+///   * |H - L|: |H - L| + 0.25 * |C<sub>y</sub> - O<sub>y</sub>|
+///
+/// ASI<sub>t</sub> = ASI<sub>t-1</sub> + SI<sub>t</sub>
+///
+/// # Parameters
+///
+/// * _limit_move_ - maximum allowed price change in one direction (greater than 0)
+#[doc(alias = "ASI")]
+#[derive(Debug, Clone)]
+pub struct AccumulativeSwingIndex {
+    limit_move: Decimal,
+    prev: Option<(Decimal, Decimal)>,
+    asi: Decimal,
+}
+
+impl AccumulativeSwingIndex {
+    pub fn new(limit_move: Decimal) -> Result<Self> {
+        if limit_move <= dec!(0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            limit_move,
+            prev: None,
+            asi: dec!(0),
+        })
+    }
+}
+
+impl<T: Open + High + Low + Close> Next<&T> for AccumulativeSwingIndex {
+    type Output = Decimal;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let open = Decimal::from_f64(input.open()).unwrap();
+        let high = Decimal::from_f64(input.high()).unwrap();
+        let low = Decimal::from_f64(input.low()).unwrap();
+        let close = Decimal::from_f64(input.close()).unwrap();
+
+        if let Some((prev_open, prev_close)) = self.prev {
+            self.asi += swing_index(open, high, low, close, prev_open, prev_close, self.limit_move);
+        }
+        self.prev = Some((open, close));
+        self.asi
+    }
+}
+
+fn swing_index(
+    open: Decimal,
+    high: Decimal,
+    low: Decimal,
+    close: Decimal,
+    prev_open: Decimal,
+    prev_close: Decimal,
+    limit_move: Decimal,
+) -> Decimal {
+    let a = (high - prev_close).abs();
+    let b = (low - prev_close).abs();
+    let c = (high - low).abs();
+    let d = (prev_close - prev_open).abs();
+
+    let r = if a >= b && a >= c {
+        a - dec!(0.5) * b + dec!(0.25) * d
+    } else if b >= a && b >= c {
+        b - dec!(0.5) * a + dec!(0.25) * d
+    } else {
+        c + dec!(0.25) * d
+    };
+    if r.is_zero() {
+        return dec!(0);
+    }
+
+    let k = a.max(b);
+    let move_ = close - prev_close + dec!(0.5) * (close - open) + dec!(0.25) * (prev_close - prev_open);
+    dec!(50) * move_ / r * k / limit_move
+}
+
+impl Reset for AccumulativeSwingIndex {
+    fn reset(&mut self) {
+        self.prev = None;
+        self.asi = dec!(0);
+    }
+}
+
+impl Default for AccumulativeSwingIndex {
+    fn default() -> Self {
+        Self::new(dec!(1)).unwrap()
+    }
+}
+
+impl fmt::Display for AccumulativeSwingIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ASI")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder().open(open).high(high).low(low).close(close).volume(1.0).build().unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(AccumulativeSwingIndex::new(dec!(0)).is_err());
+        assert!(AccumulativeSwingIndex::new(dec!(-1)).is_err());
+        assert!(AccumulativeSwingIndex::new(dec!(2)).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut asi = AccumulativeSwingIndex::new(dec!(2)).unwrap();
+
+        assert_eq!(asi.next(&bar(10.0, 11.0, 9.0, 10.5)), dec!(0));
+
+        // A = 1.5, B = 0.1, C = 1.6, D = 0.5 -> R = 1.6 + 0.125 = 1.725, K = 1.5
+        // SI = 50 * (1.3 + 0.6 + 0.125) / 1.725 * 1.5 / 2 = 44.0217391304...
+        let value = asi.next(&bar(10.6, 12.0, 10.4, 11.8));
+        assert_eq!(value.round_dp(10), dec!(44.0217391304));
+
+        // a down bar pulls the index back
+        let next = asi.next(&bar(11.5, 11.6, 10.0, 10.2));
+        assert!(next < value);
+    }
+
+    #[test]
+    fn test_no_range() {
+        let mut asi = AccumulativeSwingIndex::new(dec!(1)).unwrap();
+        asi.next(&bar(10.0, 10.0, 10.0, 10.0));
+        assert_eq!(asi.next(&bar(10.0, 10.0, 10.0, 10.0)), dec!(0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut asi = AccumulativeSwingIndex::new(dec!(2)).unwrap();
+        asi.next(&bar(10.0, 11.0, 9.0, 10.5));
+        asi.next(&bar(10.6, 12.0, 10.4, 11.8));
+
+        asi.reset();
+        assert_eq!(asi.next(&bar(10.6, 12.0, 10.4, 11.8)), dec!(0));
+    }
+
+    #[test]
+    fn test_default() {
+        AccumulativeSwingIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", AccumulativeSwingIndex::default()), "ASI");
+    }
+}
@@ -0,0 +1,34 @@
+//! Helpers shared by the indicator tests.
+
+use std::fmt;
+use ta::{DataItem, Next, Reset};
+
+/// Asserts that `reset` returns `indicator` to its freshly constructed state.
+///
+/// Feeds `series`, resets, feeds it again and compares the two runs output by output, so a
+/// child indicator or running sum left out of `reset` shows up as a mismatch. `indicator`
+/// must be freshly constructed, and `series` long enough to get it past its warmup.
+pub(crate) fn assert_full_reset<I, In, O>(mut indicator: I, series: &[In])
+where
+    I: Next<In, Output = O> + Reset,
+    In: Copy,
+    O: PartialEq + fmt::Debug,
+{
+    let first: Vec<O> = series.iter().map(|&input| indicator.next(input)).collect();
+    indicator.reset();
+    for (i, (&input, expected)) in series.iter().zip(first).enumerate() {
+        assert_eq!(indicator.next(input), expected, "output {} differs after reset", i);
+    }
+}
+
+/// A bar with the given prices and volume, opening at its close.
+pub(crate) fn bar(high: f64, low: f64, close: f64, volume: f64) -> DataItem {
+    DataItem::builder()
+        .open(close)
+        .high(high)
+        .low(low)
+        .close(close)
+        .volume(volume)
+        .build()
+        .unwrap()
+}
@@ -0,0 +1,180 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::simple_moving_average::SimpleMovingAverage;
+
+/// Trend Intensity Index (TII).
+///
+/// Share of the recent deviations from the moving average that were to the upside. Each
+/// bar's deviation is its close minus the SMA of close on that bar; over the last
+/// _period / 2_ bars the positive deviations are summed and compared with the sum of all
+/// absolute deviations. Values near 100 signal a strong uptrend, near 0 a strong downtrend.
+///
+/// # Formula
+///
+/// TII = 100 * ΣDev<sup>+</sup> / (ΣDev<sup>+</sup> + ΣDev<sup>-</sup>)
+///
+/// Returns `None` until enough bars are seen, and when there is no deviation at all.
+///
+/// # Parameters
+///
+/// * _period_ - SMA period (integer greater than 1)
+#[doc(alias = "TII")]
+#[derive(Debug, Clone)]
+pub struct TrendIntensityIndex {
+    period: usize,
+    sma: SimpleMovingAverage,
+    count: usize,
+    deviations: VecDeque<Decimal>,
+}
+
+impl TrendIntensityIndex {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 | 1 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                sma: SimpleMovingAverage::new(period)?,
+                count: 0,
+                deviations: VecDeque::with_capacity(period / 2),
+            }),
+        }
+    }
+}
+
+impl Period for TrendIntensityIndex {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for TrendIntensityIndex {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let sma = self.sma.next(input);
+        if self.count < self.period {
+            self.count += 1;
+        }
+        if self.count < self.period {
+            return None;
+        }
+
+        if self.deviations.len() == self.period / 2 {
+            self.deviations.pop_front();
+        }
+        self.deviations.push_back(input - sma);
+        if self.deviations.len() < self.period / 2 {
+            return None;
+        }
+
+        let (pos, neg) = self.deviations.iter().fold((dec!(0), dec!(0)), |(pos, neg), dev| {
+            if dev.is_sign_positive() {
+                (pos + dev, neg)
+            } else {
+                (pos, neg - dev)
+            }
+        });
+        if (pos + neg).is_zero() {
+            return None;
+        }
+        Some(dec!(100) * pos / (pos + neg))
+    }
+}
+
+impl<T: Close> Next<&T> for TrendIntensityIndex {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for TrendIntensityIndex {
+    fn reset(&mut self) {
+        self.sma.reset();
+        self.count = 0;
+        self.deviations.clear();
+    }
+}
+
+impl Default for TrendIntensityIndex {
+    fn default() -> Self {
+        Self::new(30).unwrap()
+    }
+}
+
+impl fmt::Display for TrendIntensityIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TII({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(TrendIntensityIndex::new(0).is_err());
+        assert!(TrendIntensityIndex::new(1).is_err());
+        assert!(TrendIntensityIndex::new(2).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut tii = TrendIntensityIndex::new(4).unwrap();
+
+        assert_eq!(tii.next(dec!(10)), None);
+        assert_eq!(tii.next(dec!(12)), None);
+        assert_eq!(tii.next(dec!(14)), None);
+        // sma 13, dev +3
+        assert_eq!(tii.next(dec!(16)), None);
+        // sma 13, dev -3
+        assert_eq!(tii.next(dec!(10)), Some(dec!(50)));
+        // sma 15, dev +5
+        assert_eq!(tii.next(dec!(20)), Some(dec!(62.5)));
+    }
+
+    #[test]
+    fn test_uptrend() {
+        let mut tii = TrendIntensityIndex::new(30).unwrap();
+        let mut out = None;
+        for i in 0..60 {
+            out = tii.next(Decimal::from(100 + i));
+        }
+        assert_eq!(out, Some(dec!(100)));
+    }
+
+    #[test]
+    fn test_flat() {
+        let mut tii = TrendIntensityIndex::new(2).unwrap();
+        tii.next(dec!(5));
+        assert_eq!(tii.next(dec!(5)), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tii = TrendIntensityIndex::new(2).unwrap();
+        tii.next(dec!(4));
+        assert!(tii.next(dec!(6)).is_some());
+
+        tii.reset();
+        assert_eq!(tii.next(dec!(6)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        TrendIntensityIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let tii = TrendIntensityIndex::new(30).unwrap();
+        assert_eq!(format!("{}", tii), "TII(30)");
+    }
+}
@@ -48,7 +48,7 @@ use ta::{Close, High, Low, Next, Reset};
 ///     }
 /// }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
 pub struct TrueRange {
     prev_close: Option<Decimal>,
 }
@@ -84,24 +84,36 @@ impl Next<Decimal> for TrueRange {
     }
 }
 
-impl<T: High + Low + Close> Next<&T> for TrueRange {
-    type Output = Decimal;
+impl TrueRange {
+    /// Takes `close` as the previous close without producing a range, so the next bar is
+    /// measured against it.
+    pub(crate) fn seed(&mut self, close: Decimal) {
+        self.prev_close = Some(close);
+    }
 
-    fn next(&mut self, bar: &T) -> Self::Output {
+    /// `next` for a bar already given as decimals.
+    pub(crate) fn next_hlc(&mut self, high: Decimal, low: Decimal, close: Decimal) -> Decimal {
         let max_dist = match self.prev_close {
-            Some(prev_close) => {
-                let dist1 = Decimal::from_f64(bar.high() - bar.low()).unwrap();
-                let dist2 = (Decimal::from_f64(bar.high()).unwrap() - prev_close).abs();
-                let dist3 = (Decimal::from_f64(bar.low()).unwrap() - prev_close).abs();
-                max3(dist1, dist2, dist3)
-            }
-            None => Decimal::from_f64(bar.high() - bar.low()).unwrap(),
+            Some(prev_close) => max3(high - low, (high - prev_close).abs(), (low - prev_close).abs()),
+            None => high - low,
         };
-        self.prev_close = Some(Decimal::from_f64(bar.close()).unwrap());
+        self.prev_close = Some(close);
         max_dist
     }
 }
 
+impl<T: High + Low + Close> Next<&T> for TrueRange {
+    type Output = Decimal;
+
+    fn next(&mut self, bar: &T) -> Self::Output {
+        self.next_hlc(
+            Decimal::from_f64(bar.high()).unwrap(),
+            Decimal::from_f64(bar.low()).unwrap(),
+            Decimal::from_f64(bar.close()).unwrap(),
+        )
+    }
+}
+
 impl Reset for TrueRange {
     fn reset(&mut self) {
         self.prev_close = None;
@@ -0,0 +1,184 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::Result;
+use ta::{Close, Next, Period, Reset};
+
+use crate::ema::ExponentialMovingAverage;
+use crate::model::TsiOutput;
+
+/// True Strength Index (TSI).
+///
+/// A momentum oscillator: the bar-to-bar change in price, smoothed twice, as a percentage
+/// of the absolute change smoothed the same way. It ranges from -100 to 100.
+///
+/// # Formula
+///
+/// m = close - close<sub>prev</sub>
+///
+/// TSI = 100 * EMA<sub>short</sub>(EMA<sub>long</sub>(m)) / EMA<sub>short</sub>(EMA<sub>long</sub>(|m|))
+///
+/// Signal = EMA<sub>signal</sub>(TSI)
+///
+/// TSI is 0 while the price has not moved. Returns `None` on the first bar, which has no
+/// previous close.
+///
+/// # Parameters
+///
+/// * _long_ - period of the first smoothing EMA (integer greater than 0)
+/// * _short_ - period of the second smoothing EMA (integer greater than 0)
+/// * _signal_ - period of the signal line EMA (integer greater than 0)
+#[doc(alias = "TSI")]
+#[derive(Debug, Clone)]
+pub struct TrueStrengthIndex {
+    momentum_long: ExponentialMovingAverage,
+    momentum_short: ExponentialMovingAverage,
+    abs_long: ExponentialMovingAverage,
+    abs_short: ExponentialMovingAverage,
+    signal: ExponentialMovingAverage,
+    prev_close: Option<Decimal>,
+}
+
+impl TrueStrengthIndex {
+    pub fn new(long: usize, short: usize, signal: usize) -> Result<Self> {
+        Ok(Self {
+            momentum_long: ExponentialMovingAverage::new(long)?,
+            momentum_short: ExponentialMovingAverage::new(short)?,
+            abs_long: ExponentialMovingAverage::new(long)?,
+            abs_short: ExponentialMovingAverage::new(short)?,
+            signal: ExponentialMovingAverage::new(signal)?,
+            prev_close: None,
+        })
+    }
+}
+
+impl Next<Decimal> for TrueStrengthIndex {
+    type Output = Option<TsiOutput>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let prev_close = self.prev_close.replace(input)?;
+        let momentum = input - prev_close;
+
+        let smoothed = self.momentum_short.next(self.momentum_long.next(momentum));
+        let smoothed_abs = self.abs_short.next(self.abs_long.next(momentum.abs()));
+        let tsi = if smoothed_abs.is_zero() {
+            dec!(0)
+        } else {
+            dec!(100) * smoothed / smoothed_abs
+        };
+
+        Some(TsiOutput {
+            tsi,
+            signal: self.signal.next(tsi),
+        })
+    }
+}
+
+impl<T: Close> Next<&T> for TrueStrengthIndex {
+    type Output = Option<TsiOutput>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for TrueStrengthIndex {
+    fn reset(&mut self) {
+        self.momentum_long.reset();
+        self.momentum_short.reset();
+        self.abs_long.reset();
+        self.abs_short.reset();
+        self.signal.reset();
+        self.prev_close = None;
+    }
+}
+
+impl Default for TrueStrengthIndex {
+    fn default() -> Self {
+        Self::new(25, 13, 13).unwrap()
+    }
+}
+
+impl fmt::Display for TrueStrengthIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TSI({}, {}, {})",
+            self.momentum_long.period(),
+            self.momentum_short.period(),
+            self.signal.period()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(close: f64) -> DataItem {
+        DataItem::builder()
+            .high(close)
+            .low(close)
+            .close(close)
+            .open(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(TrueStrengthIndex::new(0, 13, 13).is_err());
+        assert!(TrueStrengthIndex::new(25, 0, 13).is_err());
+        assert!(TrueStrengthIndex::new(25, 13, 0).is_err());
+        assert!(TrueStrengthIndex::new(25, 13, 13).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut tsi = TrueStrengthIndex::new(3, 2, 2).unwrap();
+        assert_eq!(tsi.next(&bar(10.0)), None);
+
+        let out = tsi.next(&bar(12.0)).unwrap();
+        assert_eq!(out.tsi, dec!(100));
+        assert_eq!(out.signal, dec!(100));
+
+        // momentum -1: long EMAs 0.5 and 1.5, short EMAs 2/3*0.5 + 1/3*2 = 1 and 5/3
+        let out = tsi.next(&bar(11.0)).unwrap();
+        assert_eq!(out.tsi.round_dp(6), dec!(60));
+        assert_eq!(out.signal.round_dp(6), dec!(73.333333));
+
+        let out = tsi.next(&bar(14.0)).unwrap();
+        assert_eq!(out.tsi.round_dp(6), dec!(72.972973));
+        assert_eq!(out.signal.round_dp(6), dec!(73.093093));
+    }
+
+    #[test]
+    fn test_flat_prices() {
+        let mut tsi = TrueStrengthIndex::default();
+        tsi.next(dec!(5));
+        assert_eq!(tsi.next(dec!(5)), Some(TsiOutput { tsi: dec!(0), signal: dec!(0) }));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tsi = TrueStrengthIndex::new(3, 2, 2).unwrap();
+        tsi.next(dec!(10));
+        tsi.next(dec!(12));
+
+        tsi.reset();
+        assert_eq!(tsi.next(dec!(12)), None);
+        assert_eq!(tsi.next(dec!(11)).unwrap().tsi, dec!(-100));
+    }
+
+    #[test]
+    fn test_default() {
+        TrueStrengthIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", TrueStrengthIndex::default()), "TSI(25, 13, 13)");
+    }
+}
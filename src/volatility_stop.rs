@@ -0,0 +1,163 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, High, Low, Next, Period, Reset};
+
+use crate::average_true_range::AverageTrueRange;
+use crate::model::{StopOutput, Trend};
+
+/// Wilder's Volatility System stop.
+///
+/// A stop-and-reverse line that trails price by a multiple of the
+/// [average true range](../average_true_range/struct.AverageTrueRange.html). While long,
+/// the stop sits _multiplier * ATR_ below the highest high since the trade was entered;
+/// while short it sits the same distance above the lowest low. A close through the stop
+/// flips the direction and restarts the extreme from the current bar.
+///
+/// The system starts long on the first bar the ATR is available.
+///
+/// # Parameters
+///
+/// * _period_ - ATR period (integer greater than 0)
+/// * _multiplier_ - ATR multiple for the stop distance (greater than 0)
+#[derive(Debug, Clone)]
+pub struct VolatilityStop {
+    atr: AverageTrueRange,
+    multiplier: Decimal,
+    direction: Option<Trend>,
+    extreme: Decimal,
+}
+
+impl VolatilityStop {
+    pub fn new(period: usize, multiplier: Decimal) -> Result<Self> {
+        if multiplier <= dec!(0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            atr: AverageTrueRange::new(period)?,
+            multiplier,
+            direction: None,
+            extreme: dec!(0),
+        })
+    }
+}
+
+impl Period for VolatilityStop {
+    fn period(&self) -> usize {
+        self.atr.period()
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for VolatilityStop {
+    type Output = Option<StopOutput>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let atr = self.atr.next(input)?;
+        let high = Decimal::from_f64(input.high()).unwrap();
+        let low = Decimal::from_f64(input.low()).unwrap();
+        let close = Decimal::from_f64(input.close()).unwrap();
+        let distance = self.multiplier * atr;
+
+        let (direction, stop) = match self.direction {
+            None | Some(Trend::Up) => {
+                self.extreme = match self.direction {
+                    None => high,
+                    _ => self.extreme.max(high),
+                };
+                let stop = self.extreme - distance;
+                if close < stop {
+                    self.extreme = low;
+                    (Trend::Down, low + distance)
+                } else {
+                    (Trend::Up, stop)
+                }
+            }
+            Some(Trend::Down) => {
+                self.extreme = self.extreme.min(low);
+                let stop = self.extreme + distance;
+                if close > stop {
+                    self.extreme = high;
+                    (Trend::Up, high - distance)
+                } else {
+                    (Trend::Down, stop)
+                }
+            }
+        };
+        self.direction = Some(direction);
+
+        Some(StopOutput { stop, direction })
+    }
+}
+
+impl Reset for VolatilityStop {
+    fn reset(&mut self) {
+        self.atr.reset();
+        self.direction = None;
+        self.extreme = dec!(0);
+    }
+}
+
+impl Default for VolatilityStop {
+    fn default() -> Self {
+        Self::new(14, dec!(3.0)).unwrap()
+    }
+}
+
+impl fmt::Display for VolatilityStop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VSTOP({}, {})", self.atr.period(), self.multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder().high(high).low(low).close(close).open(close).volume(1.0).build().unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(VolatilityStop::new(0, dec!(3)).is_err());
+        assert!(VolatilityStop::new(14, dec!(0)).is_err());
+        assert!(VolatilityStop::new(14, dec!(3)).is_ok());
+    }
+
+    #[test]
+    fn test_trend_reversal() {
+        let mut vstop = VolatilityStop::new(2, dec!(1)).unwrap();
+
+        assert_eq!(vstop.next(&bar(10.0, 9.0, 9.5)), None);
+        assert_eq!(vstop.next(&bar(11.0, 10.0, 10.5)), Some(StopOutput { stop: dec!(9.75), direction: Trend::Up }));
+        assert_eq!(vstop.next(&bar(12.0, 11.0, 11.5)), Some(StopOutput { stop: dec!(10.625), direction: Trend::Up }));
+        assert_eq!(vstop.next(&bar(13.0, 12.0, 12.5)), Some(StopOutput { stop: dec!(11.5625), direction: Trend::Up }));
+
+        // tr = 4.5, atr = 2.96875, long stop 10.03125 is breached
+        assert_eq!(vstop.next(&bar(9.0, 8.0, 8.5)), Some(StopOutput { stop: dec!(10.96875), direction: Trend::Down }));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vstop = VolatilityStop::new(1, dec!(1)).unwrap();
+        assert!(vstop.next(&bar(10.0, 9.0, 9.5)).is_some());
+
+        vstop.reset();
+        let out = vstop.next(&bar(20.0, 18.0, 19.0)).unwrap();
+        assert_eq!(out, StopOutput { stop: dec!(18), direction: Trend::Up });
+    }
+
+    #[test]
+    fn test_default() {
+        VolatilityStop::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let vstop = VolatilityStop::new(14, dec!(3.0)).unwrap();
+        assert_eq!(format!("{}", vstop), "VSTOP(14, 3.0)");
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, DataItem, High, Low, Next, Open, Reset, Volume};
+
+/// Aggregates bars into volume bars, each holding exactly _volume_threshold_ of volume.
+///
+/// Input bars are merged, with the first open, the highest high, the lowest low and the
+/// last close, until their volume reaches the threshold. The bar that crosses it is split:
+/// just enough of its volume completes the current bar and the rest carries over to the
+/// next one. An input with several thresholds' worth of volume completes several bars at
+/// once; those after the first are built from that input's prices alone.
+///
+/// `next` returns the first bar completed by the input, or `None` if none was; `completed`
+/// lists all of them.
+///
+/// # Parameters
+///
+/// * _volume_threshold_ - volume of each output bar (greater than 0)
+#[derive(Debug, Clone)]
+pub struct VolumeBars {
+    volume_threshold: Decimal,
+    partial: Option<Partial>,
+    completed: Vec<DataItem>,
+}
+
+#[derive(Debug, Clone)]
+struct Partial {
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: Decimal,
+}
+
+impl Partial {
+    fn new<T: Open + High + Low + Close>(input: &T, volume: Decimal) -> Self {
+        Self {
+            open: input.open(),
+            high: input.high(),
+            low: input.low(),
+            close: input.close(),
+            volume,
+        }
+    }
+
+    fn to_item(&self) -> DataItem {
+        DataItem::builder()
+            .open(self.open)
+            .high(self.high)
+            .low(self.low)
+            .close(self.close)
+            .volume(self.volume.to_f64().unwrap())
+            .build()
+            .unwrap()
+    }
+}
+
+impl VolumeBars {
+    pub fn new(volume_threshold: Decimal) -> Result<Self> {
+        if volume_threshold <= dec!(0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            volume_threshold,
+            partial: None,
+            completed: Vec::new(),
+        })
+    }
+
+    /// Bars completed by the most recent call to `next`, oldest first.
+    pub fn completed(&self) -> &[DataItem] {
+        &self.completed
+    }
+}
+
+impl Next<&DataItem> for VolumeBars {
+    type Output = Option<DataItem>;
+
+    fn next(&mut self, input: &DataItem) -> Self::Output {
+        self.completed.clear();
+        let mut volume = Decimal::from_f64(input.volume()).unwrap();
+
+        let mut partial = match self.partial.take() {
+            None => Partial::new(input, dec!(0)),
+            Some(partial) => Partial {
+                high: partial.high.max(input.high()),
+                low: partial.low.min(input.low()),
+                close: input.close(),
+                ..partial
+            },
+        };
+        loop {
+            let needed = self.volume_threshold - partial.volume;
+            if volume < needed {
+                partial.volume += volume;
+                break;
+            }
+            partial.volume = self.volume_threshold;
+            self.completed.push(partial.to_item());
+            volume -= needed;
+            partial = Partial::new(input, dec!(0));
+        }
+        if !partial.volume.is_zero() {
+            self.partial = Some(partial);
+        }
+
+        self.completed.first().cloned()
+    }
+}
+
+impl Reset for VolumeBars {
+    fn reset(&mut self) {
+        self.partial = None;
+        self.completed.clear();
+    }
+}
+
+impl fmt::Display for VolumeBars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VOLUME_BARS({})", self.volume_threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(open: f64, high: f64, low: f64, close: f64, volume: f64) -> DataItem {
+        DataItem::builder()
+            .open(open)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(volume)
+            .build()
+            .unwrap()
+    }
+
+    fn ohlcv(item: &DataItem) -> (f64, f64, f64, f64, f64) {
+        (item.open(), item.high(), item.low(), item.close(), item.volume())
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeBars::new(dec!(0)).is_err());
+        assert!(VolumeBars::new(dec!(-1)).is_err());
+        assert!(VolumeBars::new(dec!(1000)).is_ok());
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let mut vb = VolumeBars::new(dec!(100)).unwrap();
+        assert!(vb.next(&bar(10.0, 11.0, 9.5, 10.5, 40.0)).is_none());
+        assert!(vb.next(&bar(10.5, 12.0, 10.0, 11.5, 30.0)).is_none());
+        // 30 of this bar's 50 complete the first volume bar, 20 carry over
+        let out = vb.next(&bar(11.5, 11.8, 9.0, 9.5, 50.0)).unwrap();
+        assert_eq!(ohlcv(&out), (10.0, 12.0, 9.0, 9.5, 100.0));
+        assert_eq!(vb.completed().len(), 1);
+
+        let out = vb.next(&bar(9.5, 10.0, 9.2, 9.8, 80.0)).unwrap();
+        assert_eq!(ohlcv(&out), (11.5, 11.8, 9.0, 9.8, 100.0));
+    }
+
+    #[test]
+    fn test_large_input_completes_several_bars() {
+        let mut vb = VolumeBars::new(dec!(100)).unwrap();
+        vb.next(&bar(10.0, 10.5, 9.5, 10.0, 60.0));
+
+        let out = vb.next(&bar(10.0, 13.0, 10.0, 12.0, 290.0)).unwrap();
+        assert_eq!(ohlcv(&out), (10.0, 13.0, 9.5, 12.0, 100.0));
+        let completed: Vec<_> = vb.completed().iter().map(ohlcv).collect();
+        assert_eq!(
+            completed,
+            [
+                (10.0, 13.0, 9.5, 12.0, 100.0),
+                (10.0, 13.0, 10.0, 12.0, 100.0),
+                (10.0, 13.0, 10.0, 12.0, 100.0),
+            ]
+        );
+
+        // the remaining 50 start the next bar
+        assert!(vb.next(&bar(12.0, 12.5, 11.0, 11.5, 40.0)).is_none());
+        assert!(vb.completed().is_empty());
+        let out = vb.next(&bar(11.5, 11.6, 11.2, 11.4, 10.0)).unwrap();
+        assert_eq!(ohlcv(&out), (10.0, 13.0, 10.0, 11.4, 100.0));
+    }
+
+    #[test]
+    fn test_exact_threshold() {
+        let mut vb = VolumeBars::new(dec!(100)).unwrap();
+        assert!(vb.next(&bar(10.0, 11.0, 9.0, 10.5, 100.0)).is_some());
+        // nothing carried over
+        assert!(vb.next(&bar(20.0, 21.0, 19.0, 20.5, 50.0)).is_none());
+        let out = vb.next(&bar(20.5, 22.0, 20.0, 21.0, 50.0)).unwrap();
+        assert_eq!(ohlcv(&out), (20.0, 22.0, 19.0, 21.0, 100.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vb = VolumeBars::new(dec!(100)).unwrap();
+        vb.next(&bar(10.0, 11.0, 9.0, 10.5, 90.0));
+
+        vb.reset();
+        assert!(vb.next(&bar(20.0, 21.0, 19.0, 20.5, 50.0)).is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", VolumeBars::new(dec!(1000)).unwrap()), "VOLUME_BARS(1000)");
+    }
+}
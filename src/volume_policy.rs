@@ -0,0 +1,67 @@
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+
+/// How a volume-based indicator treats a bar whose volume is zero or missing (`NaN` or
+/// infinite).
+///
+/// Pass it to the indicator's `with_volume_policy` constructor. Under `Error` such a bar
+/// makes `try_next` return `TaError::DataItemInvalid` and `next` panic.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VolumePolicy {
+    /// The bar is ignored: the indicator's state doesn't change and it repeats its last
+    /// output.
+    Skip,
+    /// The bar is used with a volume of 0.
+    #[default]
+    TreatAsZero,
+    /// The bar is rejected.
+    Error,
+}
+
+impl VolumePolicy {
+    /// Volume to use for a bar reporting `volume`, or `None` if the bar is to be skipped.
+    ///
+    /// A volume beyond `Decimal`'s range (about 7.9e28) is an error under every policy.
+    pub(crate) fn volume(self, volume: f64) -> Result<Option<Decimal>> {
+        if volume.is_finite() && volume != 0.0 {
+            return Decimal::from_f64(volume).map(Some).ok_or(TaError::InvalidParameter);
+        }
+        match self {
+            VolumePolicy::Skip => Ok(None),
+            VolumePolicy::TreatAsZero => Ok(Some(dec!(0))),
+            VolumePolicy::Error => Err(TaError::DataItemInvalid),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume() {
+        for policy in [VolumePolicy::Skip, VolumePolicy::TreatAsZero, VolumePolicy::Error] {
+            assert_eq!(policy.volume(2.5).unwrap(), Some(dec!(2.5)));
+        }
+        for volume in [0.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(VolumePolicy::Skip.volume(volume).unwrap(), None);
+            assert_eq!(VolumePolicy::TreatAsZero.volume(volume).unwrap(), Some(dec!(0)));
+            assert!(VolumePolicy::Error.volume(volume).is_err());
+        }
+    }
+
+    #[test]
+    fn test_volume_out_of_range() {
+        for policy in [VolumePolicy::Skip, VolumePolicy::TreatAsZero, VolumePolicy::Error] {
+            assert_eq!(policy.volume(1e22).unwrap(), Some(dec!(10000000000000000000000)));
+            assert!(policy.volume(1e30).is_err());
+        }
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(VolumePolicy::default(), VolumePolicy::TreatAsZero);
+    }
+}
@@ -0,0 +1,268 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{High, Low, Next, Reset, Volume};
+
+/// Volume profile.
+///
+/// A histogram of traded volume by price since the last reset. The range from the lowest
+/// low to the highest high seen is split into _num_buckets_ equal buckets, and each bar's
+/// volume is spread evenly over its own high-low range, so a bucket receives the share of
+/// the bar that overlaps it. A bar with no range puts all its volume in one bucket.
+///
+/// When a bar extends the range the buckets are rebuilt over the new bounds, and the volume
+/// of each old bucket moves to the new bucket holding its midpoint. The profile therefore
+/// gets coarser as the range grows, but its total volume is kept.
+///
+/// Each `next` returns the point of control after the bar.
+///
+/// # Parameters
+///
+/// * _num_buckets_ - number of price buckets (integer greater than 0)
+#[doc(alias = "VP")]
+#[derive(Debug, Clone)]
+pub struct VolumeProfile {
+    buckets: Vec<Decimal>,
+    bounds: Option<(Decimal, Decimal)>,
+}
+
+impl VolumeProfile {
+    pub fn new(num_buckets: usize) -> Result<Self> {
+        match num_buckets {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                buckets: vec![dec!(0); num_buckets],
+                bounds: None,
+            }),
+        }
+    }
+
+    /// Point of control: the midpoint of the bucket with the most volume. The lowest such
+    /// bucket wins a tie. `None` before the first bar.
+    pub fn poc(&self) -> Option<Decimal> {
+        self.bounds?;
+        Some(self.midpoint(self.poc_index()))
+    }
+
+    /// Price range around the point of control holding at least `percent` of the volume,
+    /// commonly 70. Starting from the POC bucket, it grows by whichever neighbouring bucket
+    /// holds more volume. `None` before the first bar.
+    pub fn value_area(&self, percent: Decimal) -> Option<(Decimal, Decimal)> {
+        let (low, _) = self.bounds?;
+        let target = self.buckets.iter().sum::<Decimal>() * percent / dec!(100);
+        let (mut first, mut last) = (self.poc_index(), self.poc_index());
+        let mut area = self.buckets[first];
+        while area < target && (first > 0 || last + 1 < self.buckets.len()) {
+            let below = if first > 0 { Some(self.buckets[first - 1]) } else { None };
+            let above = self.buckets.get(last + 1).copied();
+            if above.is_none() || below.is_some_and(|below| below > above.unwrap()) {
+                first -= 1;
+                area += self.buckets[first];
+            } else {
+                last += 1;
+                area += self.buckets[last];
+            }
+        }
+        let width = self.width();
+        Some((low + width * Decimal::from(first), low + width * Decimal::from(last + 1)))
+    }
+
+    fn poc_index(&self) -> usize {
+        let mut poc = 0;
+        for (i, volume) in self.buckets.iter().enumerate() {
+            if *volume > self.buckets[poc] {
+                poc = i;
+            }
+        }
+        poc
+    }
+
+    fn width(&self) -> Decimal {
+        match self.bounds {
+            Some((low, high)) => (high - low) / Decimal::from(self.buckets.len()),
+            None => dec!(0),
+        }
+    }
+
+    fn midpoint(&self, index: usize) -> Decimal {
+        let (low, _) = self.bounds.unwrap();
+        low + self.width() * (Decimal::from(index) + dec!(0.5))
+    }
+
+    fn index_of(&self, price: Decimal) -> usize {
+        let (low, _) = self.bounds.unwrap();
+        let width = self.width();
+        if width.is_zero() {
+            return 0;
+        }
+        ((price - low) / width).floor().to_usize().unwrap().min(self.buckets.len() - 1)
+    }
+
+    fn rebucket(&mut self, low: Decimal, high: Decimal) {
+        let old = self.clone();
+        self.bounds = Some((low, high));
+        self.buckets.iter_mut().for_each(|volume| *volume = dec!(0));
+        for (i, volume) in old.buckets.iter().enumerate() {
+            let index = self.index_of(old.midpoint(i));
+            self.buckets[index] += volume;
+        }
+    }
+}
+
+impl<T: High + Low + Volume> Next<&T> for VolumeProfile {
+    type Output = Decimal;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let bar_high = Decimal::from_f64(input.high()).unwrap();
+        let bar_low = Decimal::from_f64(input.low()).unwrap();
+        let volume = Decimal::from_f64(input.volume()).unwrap();
+
+        match self.bounds {
+            None => self.bounds = Some((bar_low, bar_high)),
+            Some((low, high)) if bar_low < low || bar_high > high => {
+                self.rebucket(low.min(bar_low), high.max(bar_high))
+            }
+            _ => {}
+        }
+
+        let range = bar_high - bar_low;
+        let width = self.width();
+        if range.is_zero() || width.is_zero() {
+            let index = self.index_of(bar_low);
+            self.buckets[index] += volume;
+        } else {
+            let (low, _) = self.bounds.unwrap();
+            for index in self.index_of(bar_low)..=self.index_of(bar_high) {
+                let bucket_low = low + width * Decimal::from(index);
+                let overlap = bar_high.min(bucket_low + width) - bar_low.max(bucket_low);
+                if overlap > dec!(0) {
+                    self.buckets[index] += volume * overlap / range;
+                }
+            }
+        }
+
+        self.midpoint(self.poc_index())
+    }
+}
+
+impl Reset for VolumeProfile {
+    fn reset(&mut self) {
+        self.buckets.iter_mut().for_each(|volume| *volume = dec!(0));
+        self.bounds = None;
+    }
+}
+
+impl Default for VolumeProfile {
+    fn default() -> Self {
+        Self::new(24).unwrap()
+    }
+}
+
+impl fmt::Display for VolumeProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VP({})", self.buckets.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use crate::test_util::assert_full_reset;
+    use super::*;
+
+    fn bar(high: f64, low: f64, volume: f64) -> DataItem {
+        DataItem::builder()
+            .high(high)
+            .low(low)
+            .close(low)
+            .open(low)
+            .volume(volume)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeProfile::new(0).is_err());
+        assert!(VolumeProfile::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_poc() {
+        let mut vp = VolumeProfile::new(10).unwrap();
+        assert_eq!(vp.poc(), None);
+
+        // 10 buckets of width 1 from 100 to 110
+        vp.next(&bar(110.0, 100.0, 10.0));
+        for _ in 0..5 {
+            assert_eq!(vp.next(&bar(104.75, 104.25, 50.0)), dec!(104.5));
+        }
+        vp.next(&bar(108.0, 106.0, 20.0));
+        assert_eq!(vp.poc(), Some(dec!(104.5)));
+        assert_eq!(vp.buckets.iter().sum::<Decimal>(), dec!(280));
+    }
+
+    #[test]
+    fn test_rebucket() {
+        let mut vp = VolumeProfile::new(10).unwrap();
+        vp.next(&bar(110.0, 100.0, 10.0));
+        vp.next(&bar(104.75, 104.25, 100.0));
+
+        // buckets of width 2 from 100 to 120: 104.5 moves into 104..106
+        assert_eq!(vp.next(&bar(120.0, 115.0, 10.0)), dec!(105));
+        assert_eq!(vp.buckets.iter().sum::<Decimal>(), dec!(120));
+        // width 2.2 from 98: 105 moves into 104.6..106.8
+        assert_eq!(vp.next(&bar(100.0, 98.0, 20.0)), dec!(105.7));
+    }
+
+    #[test]
+    fn test_flat_bars() {
+        let mut vp = VolumeProfile::new(4).unwrap();
+        assert_eq!(vp.next(&bar(50.0, 50.0, 10.0)), dec!(50));
+        vp.next(&bar(54.0, 50.0, 4.0));
+        assert_eq!(vp.next(&bar(53.5, 53.5, 20.0)), dec!(53.5));
+    }
+
+    #[test]
+    fn test_value_area() {
+        let mut vp = VolumeProfile::new(10).unwrap();
+        assert_eq!(vp.value_area(dec!(70)), None);
+
+        // one unit per bucket, then 10 in 103..104, 5 in 104..105 and 3 in 102..103
+        vp.next(&bar(110.0, 100.0, 10.0));
+        vp.next(&bar(104.0, 103.0, 10.0));
+        vp.next(&bar(105.0, 104.0, 5.0));
+        vp.next(&bar(103.0, 102.0, 3.0));
+        assert_eq!(vp.poc(), Some(dec!(103.5)));
+
+        // total 28: 11 at the POC, then 6 above, 4 below and 2 at 101..102
+        assert_eq!(vp.value_area(dec!(30)), Some((dec!(103), dec!(104))));
+        assert_eq!(vp.value_area(dec!(60)), Some((dec!(103), dec!(105))));
+        assert_eq!(vp.value_area(dec!(70)), Some((dec!(102), dec!(105))));
+        assert_eq!(vp.value_area(dec!(100)), Some((dec!(100), dec!(110))));
+    }
+
+    #[test]
+    fn test_reset() {
+        let bars = [bar(110.0, 100.0, 10.0), bar(104.75, 104.25, 50.0), bar(120.0, 115.0, 10.0)];
+        let series: Vec<&DataItem> = bars.iter().collect();
+        assert_full_reset(VolumeProfile::new(10).unwrap(), &series);
+
+        let mut vp = VolumeProfile::new(10).unwrap();
+        vp.next(&bars[0]);
+        vp.reset();
+        assert_eq!(vp.poc(), None);
+    }
+
+    #[test]
+    fn test_default() {
+        VolumeProfile::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", VolumeProfile::default()), "VP(24)");
+    }
+}
@@ -0,0 +1,376 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, High, Low, Next, Reset, Volume};
+
+use crate::math::sqrt;
+use crate::model::VwapBandsOutput;
+use crate::precision::VolumeUnit;
+use crate::volume_policy::VolumePolicy;
+
+/// Volume weighted average price (VWAP).
+///
+/// Average typical price of every bar since the last reset, weighted by volume. Call
+/// `reset` at the start of each session.
+///
+/// # Formula
+///
+/// VWAP = Σ(price * volume) / Σvolume
+///
+/// Where _price_ is the typical price (high + low + close) / 3. Returns `None` while no
+/// volume has traded.
+///
+/// A bar without volume adds nothing to either sum, so `VolumePolicy::Skip` and
+/// `VolumePolicy::TreatAsZero` give the same output here.
+///
+/// The sums don't overflow on very large volumes: once they would, volume is counted in a
+/// coarser unit, at the cost of the digits that the smallest volumes carry beyond 12
+/// decimal places of that unit.
+/// A bar that overflows them even in the coarsest unit, 10<sup>24</sup>, is rejected:
+/// `try_next` returns an error and `next` panics.
+#[doc(alias = "VWAP")]
+#[derive(Debug, Clone, Default)]
+pub struct VolumeWeightedAveragePrice {
+    sums: Sums,
+    volume_policy: VolumePolicy,
+}
+
+/// Running sums, with volume counted in `unit`. The ratios read from them don't depend on
+/// the unit, which grows whenever a sum would overflow, see the `precision` module.
+#[derive(Debug, Clone, Default)]
+struct Sums {
+    price_volume: Decimal,
+    price_sq_volume: Decimal,
+    volume: Decimal,
+    unit: VolumeUnit,
+}
+
+impl Sums {
+    /// Adds the bar, unless `policy` skips it. Fails and changes nothing if the sums overflow
+    /// even in the coarsest unit.
+    fn add<T: High + Low + Close + Volume>(&mut self, input: &T, policy: VolumePolicy) -> Result<()> {
+        let Some(volume) = policy.volume(input.volume())? else {
+            return Ok(());
+        };
+        let price = Decimal::from_f64((input.high() + input.low() + input.close()) / 3.0).unwrap();
+        let saved = self.clone();
+        while !self.try_add(price, self.unit.convert(volume)) {
+            if !self.unit.coarsen() {
+                *self = saved;
+                return Err(TaError::InvalidParameter);
+            }
+            self.price_volume = VolumeUnit::rescale(self.price_volume);
+            self.price_sq_volume = VolumeUnit::rescale(self.price_sq_volume);
+            self.volume = VolumeUnit::rescale(self.volume);
+        }
+        Ok(())
+    }
+
+    /// Adds `volume` at `price` and returns true, or returns false and changes nothing if
+    /// a sum would overflow.
+    fn try_add(&mut self, price: Decimal, volume: Decimal) -> bool {
+        let sums = price.checked_mul(volume).and_then(|price_volume| {
+            Some((
+                self.price_volume.checked_add(price_volume)?,
+                self.price_sq_volume.checked_add(price.checked_mul(price_volume)?)?,
+                self.volume.checked_add(volume)?,
+            ))
+        });
+        match sums {
+            Some((price_volume, price_sq_volume, volume)) => {
+                self.price_volume = price_volume;
+                self.price_sq_volume = price_sq_volume;
+                self.volume = volume;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn vwap(&self) -> Option<Decimal> {
+        if self.volume <= dec!(0) {
+            return None;
+        }
+        Some(self.price_volume / self.volume)
+    }
+}
+
+impl VolumeWeightedAveragePrice {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Like `new`, with `volume_policy` deciding how bars with zero or missing volume are
+    /// handled.
+    pub fn with_volume_policy(volume_policy: VolumePolicy) -> Self {
+        Self {
+            sums: Sums::default(),
+            volume_policy,
+        }
+    }
+
+    /// `next` that returns an error instead of panicking on a bar rejected by
+    /// `VolumePolicy::Error` or with a volume beyond `Decimal`'s range.
+    pub fn try_next<T: High + Low + Close + Volume>(&mut self, input: &T) -> Result<Option<Decimal>> {
+        self.sums.add(input, self.volume_policy)?;
+        Ok(self.sums.vwap())
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<&T> for VolumeWeightedAveragePrice {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.try_next(input).expect("invalid volume")
+    }
+}
+
+impl Reset for VolumeWeightedAveragePrice {
+    fn reset(&mut self) {
+        self.sums = Sums::default();
+    }
+}
+
+impl fmt::Display for VolumeWeightedAveragePrice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VWAP")
+    }
+}
+
+/// VWAP with standard deviation bands.
+///
+/// [VolumeWeightedAveragePrice](struct.VolumeWeightedAveragePrice.html) with bands
+/// _multiplier_ volume-weighted standard deviations of the typical price above and below
+/// it. Like the VWAP, everything accumulates until `reset`.
+///
+/// # Formula
+///
+/// SD = sqrt(Σ(price<sup>2</sup> * volume) / Σvolume - VWAP<sup>2</sup>)
+///
+/// Upper = VWAP + multiplier * SD
+///
+/// Lower = VWAP - multiplier * SD
+///
+/// # Parameters
+///
+/// * _multiplier_ - number of standard deviations (greater than 0)
+#[derive(Debug, Clone)]
+pub struct VwapBands {
+    sums: Sums,
+    multiplier: Decimal,
+    volume_policy: VolumePolicy,
+}
+
+impl VwapBands {
+    pub fn new(multiplier: Decimal) -> Result<Self> {
+        Self::with_volume_policy(multiplier, VolumePolicy::default())
+    }
+
+    /// Like `new`, with `volume_policy` deciding how bars with zero or missing volume are
+    /// handled.
+    pub fn with_volume_policy(multiplier: Decimal, volume_policy: VolumePolicy) -> Result<Self> {
+        if multiplier <= dec!(0) {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            sums: Sums::default(),
+            multiplier,
+            volume_policy,
+        })
+    }
+
+    /// `next` that returns an error instead of panicking on a bar rejected by
+    /// `VolumePolicy::Error` or with a volume beyond `Decimal`'s range.
+    pub fn try_next<T: High + Low + Close + Volume>(&mut self, input: &T) -> Result<Option<VwapBandsOutput>> {
+        self.sums.add(input, self.volume_policy)?;
+        let Some(vwap) = self.sums.vwap() else {
+            return Ok(None);
+        };
+        let variance = (self.sums.price_sq_volume / self.sums.volume - vwap * vwap).max(dec!(0));
+        let width = self.multiplier * sqrt(variance);
+        Ok(Some(VwapBandsOutput {
+            vwap,
+            upper: vwap + width,
+            lower: vwap - width,
+        }))
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<&T> for VwapBands {
+    type Output = Option<VwapBandsOutput>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.try_next(input).expect("invalid volume")
+    }
+}
+
+impl Reset for VwapBands {
+    fn reset(&mut self) {
+        self.sums = Sums::default();
+    }
+}
+
+impl Default for VwapBands {
+    fn default() -> Self {
+        Self::new(dec!(2.0)).unwrap()
+    }
+}
+
+impl fmt::Display for VwapBands {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VWAP_BANDS({})", self.multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(price: f64, volume: f64) -> DataItem {
+        DataItem::builder().open(price).high(price).low(price).close(price).volume(volume).build().unwrap()
+    }
+
+    #[test]
+    fn test_vwap() {
+        let mut vwap = VolumeWeightedAveragePrice::new();
+        assert_eq!(vwap.next(&bar(10.0, 0.0)), None);
+        assert_eq!(vwap.next(&bar(10.0, 100.0)), Some(dec!(10)));
+        // (10 * 100 + 13 * 300) / 400
+        assert_eq!(vwap.next(&bar(13.0, 300.0)), Some(dec!(12.25)));
+    }
+
+    #[test]
+    fn test_vwap_typical_price() {
+        let mut vwap = VolumeWeightedAveragePrice::new();
+        let di = DataItem::builder().open(10.0).high(12.0).low(9.0).close(12.0).volume(5.0).build().unwrap();
+        assert_eq!(vwap.next(&di), Some(dec!(11)));
+    }
+
+    #[test]
+    fn test_bands_new() {
+        assert!(VwapBands::new(dec!(0)).is_err());
+        assert!(VwapBands::new(dec!(2)).is_ok());
+    }
+
+    #[test]
+    fn test_bands() {
+        let mut bands = VwapBands::new(dec!(2)).unwrap();
+        let out = bands.next(&bar(10.0, 100.0)).unwrap();
+        assert_eq!(out, VwapBandsOutput { vwap: dec!(10), upper: dec!(10), lower: dec!(10) });
+
+        // prices 10 and 14 with equal volume: VWAP 12, SD 2
+        let out = bands.next(&bar(14.0, 100.0)).unwrap();
+        assert_eq!(out.vwap, dec!(12));
+        assert_eq!(out.upper.round_dp(10), dec!(16));
+        assert_eq!(out.lower.round_dp(10), dec!(8));
+    }
+
+    #[test]
+    fn test_bands_widen_with_dispersion() {
+        let mut bands = VwapBands::default();
+        let mut widths = Vec::new();
+        for (i, price) in [100.0, 101.0, 99.0, 104.0, 95.0, 110.0].iter().enumerate() {
+            let out = bands.next(&bar(*price, 1000.0 + i as f64)).unwrap();
+            widths.push(out.upper - out.lower);
+        }
+        assert!(widths.windows(2).all(|w| w[1] > w[0]));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bands = VwapBands::default();
+        bands.next(&bar(10.0, 100.0));
+        bands.next(&bar(20.0, 100.0));
+
+        bands.reset();
+        let out = bands.next(&bar(30.0, 10.0)).unwrap();
+        assert_eq!(out.vwap, dec!(30));
+        assert_eq!(out.upper, dec!(30));
+
+        let mut vwap = VolumeWeightedAveragePrice::new();
+        vwap.next(&bar(10.0, 100.0));
+        vwap.reset();
+        assert_eq!(vwap.next(&bar(30.0, 10.0)), Some(dec!(30)));
+    }
+
+    #[test]
+    fn test_volume_policy() {
+        let series = [bar(10.0, 100.0), bar(50.0, 0.0), bar(13.0, 300.0)];
+
+        for policy in [VolumePolicy::Skip, VolumePolicy::TreatAsZero] {
+            let mut vwap = VolumeWeightedAveragePrice::with_volume_policy(policy);
+            let outputs: Vec<_> = series.iter().map(|bar| vwap.try_next(bar).unwrap()).collect();
+            assert_eq!(outputs, [Some(dec!(10)), Some(dec!(10)), Some(dec!(12.25))]);
+        }
+
+        let mut vwap = VolumeWeightedAveragePrice::with_volume_policy(VolumePolicy::Error);
+        assert_eq!(vwap.try_next(&series[0]), Ok(Some(dec!(10))));
+        assert_eq!(vwap.try_next(&series[1]), Err(TaError::DataItemInvalid));
+        assert_eq!(vwap.try_next(&series[2]), Ok(Some(dec!(12.25))));
+
+        let mut bands = VwapBands::with_volume_policy(dec!(2), VolumePolicy::Error).unwrap();
+        assert!(bands.try_next(&series[0]).is_ok());
+        assert_eq!(bands.try_next(&series[1]), Err(TaError::DataItemInvalid));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid volume")]
+    fn test_volume_policy_error_panics_in_next() {
+        let mut vwap = VolumeWeightedAveragePrice::with_volume_policy(VolumePolicy::Error);
+        vwap.next(&bar(10.0, 0.0));
+    }
+
+    #[test]
+    fn test_large_volume() {
+        let prices = [1234.5, 1240.25, 1229.75, 1251.0, 1236.5, 1244.0, 1238.25, 1247.75];
+        let mut huge = VwapBands::default();
+        let mut small = VwapBands::default();
+        let mut vwap = VolumeWeightedAveragePrice::new();
+
+        // 1e22 base units a bar: Σ(price^2 * volume) passes Decimal::MAX by the fifth bar
+        for i in 0..40 {
+            let price = prices[i % prices.len()];
+            let weight = 1.0 + (i % 3) as f64;
+            let out = huge.next(&bar(price, weight * 1e22)).unwrap();
+            let expected = small.next(&bar(price, weight)).unwrap();
+            assert_eq!(out.vwap.round_dp(8), expected.vwap.round_dp(8));
+            assert_eq!(out.upper.round_dp(6), expected.upper.round_dp(6));
+            assert!(vwap.next(&bar(price, weight * 1e25)).is_some());
+        }
+    }
+
+    #[test]
+    fn test_overflow_in_coarsest_unit() {
+        let mut vwap = VolumeWeightedAveragePrice::new();
+        vwap.next(&bar(100.0, 1000.0));
+
+        // 1e13 * 7e28 overflows even counted in units of 1e24; the bar is rejected
+        assert_eq!(vwap.try_next(&bar(1e13, 7e28)), Err(TaError::InvalidParameter));
+        assert_eq!(vwap.next(&bar(102.0, 1000.0)), Some(dec!(101)));
+    }
+
+    #[test]
+    fn test_tiny_values() {
+        // prices and volumes below 1e-12 are kept exactly while nothing overflows
+        let mut vwap = VolumeWeightedAveragePrice::new();
+        let out = vwap.next(&bar(2e-13, 1.0)).unwrap();
+        assert_eq!(out.round_dp(20), dec!(0.0000000000002));
+        // (2e-13 + 3 * 1.23456789e-9) / 4
+        let out = vwap.next(&bar(1.23456789e-9, 3.0)).unwrap();
+        assert_eq!(out.round_dp(17), dec!(0.00000000092597592));
+
+        let mut vwap = VolumeWeightedAveragePrice::new();
+        assert_eq!(vwap.next(&bar(100.0, 1e-13)).unwrap().round_dp(8), dec!(100));
+        vwap.next(&bar(1.23456789e-9, 3e-13));
+        assert_eq!(vwap.next(&bar(100.0, 0.0)).unwrap().round_dp(8), dec!(25));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", VolumeWeightedAveragePrice::new()), "VWAP");
+        assert_eq!(format!("{}", VwapBands::default()), "VWAP_BANDS(2.0)");
+    }
+}
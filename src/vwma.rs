@@ -0,0 +1,318 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset, Volume};
+
+use crate::precision::VolumeUnit;
+use crate::volume_policy::VolumePolicy;
+
+/// Volume weighted moving average (VWMA).
+///
+/// The average close of the last _period_ bars, each weighted by its volume. Unlike
+/// [VolumeWeightedAveragePrice](../vwap/struct.VolumeWeightedAveragePrice.html) it looks at
+/// a fixed window instead of accumulating since the last reset.
+///
+/// # Formula
+///
+/// VWMA = Σ(close * volume) / Σvolume
+///
+/// Returns `None` until _period_ bars have been seen, and while the window holds no volume.
+/// Bars without volume are counted with a volume of 0 unless another `VolumePolicy` is set.
+///
+/// Very large volumes don't overflow the sums: once they would, volume is counted in a
+/// coarser unit, at the cost of the digits that the smallest volumes carry beyond 12
+/// decimal places of that unit.
+/// A bar that overflows them even in the coarsest unit, 10<sup>24</sup>, is rejected:
+/// `try_next` returns an error and `next` panics.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[doc(alias = "VWMA")]
+#[derive(Debug, Clone)]
+pub struct VolumeWeightedMovingAverage {
+    period: usize,
+    /// Closes and volumes, the volumes counted in `unit`.
+    window: VecDeque<(Decimal, Decimal)>,
+    sum_price_volume: Decimal,
+    sum_volume: Decimal,
+    unit: VolumeUnit,
+    volume_policy: VolumePolicy,
+}
+
+impl VolumeWeightedMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        Self::with_volume_policy(period, VolumePolicy::default())
+    }
+
+    /// Like `new`, with `volume_policy` deciding how bars with zero or missing volume are
+    /// handled.
+    pub fn with_volume_policy(period: usize, volume_policy: VolumePolicy) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => Ok(Self {
+                period,
+                window: VecDeque::with_capacity(period),
+                sum_price_volume: dec!(0),
+                sum_volume: dec!(0),
+                unit: VolumeUnit::default(),
+                volume_policy,
+            }),
+        }
+    }
+
+    /// `next` that returns an error instead of panicking on a bar rejected by
+    /// `VolumePolicy::Error` or with a volume beyond `Decimal`'s range.
+    pub fn try_next<T: Close + Volume>(&mut self, input: &T) -> Result<Option<Decimal>> {
+        let Some(volume) = self.volume_policy.volume(input.volume())? else {
+            return Ok(self.value());
+        };
+        let close = Decimal::from_f64_retain(input.close()).unwrap();
+        let dropped = if self.window.len() == self.period { self.window.pop_front() } else { None };
+        if let Some((old_close, old_volume)) = dropped {
+            // was added to the sum, so it can't overflow
+            self.sum_price_volume -= old_close * old_volume;
+            self.sum_volume -= old_volume;
+        }
+        let volume = self.unit.convert(volume);
+        self.window.push_back((close, volume));
+
+        let sums = close.checked_mul(volume).and_then(|price_volume| {
+            Some((self.sum_price_volume.checked_add(price_volume)?, self.sum_volume.checked_add(volume)?))
+        });
+        match sums {
+            Some((sum_price_volume, sum_volume)) => {
+                self.sum_price_volume = sum_price_volume;
+                self.sum_volume = sum_volume;
+            }
+            None => {
+                let saved = (self.window.clone(), self.unit);
+                while !self.recompute_sums() {
+                    if !self.unit.coarsen() {
+                        // put back the window the bar arrived to
+                        (self.window, self.unit) = saved;
+                        self.window.pop_back();
+                        if let Some((old_close, old_volume)) = dropped {
+                            self.window.push_front((old_close, old_volume));
+                            self.sum_price_volume += old_close * old_volume;
+                            self.sum_volume += old_volume;
+                        }
+                        return Err(TaError::InvalidParameter);
+                    }
+                    for (_, volume) in self.window.iter_mut() {
+                        *volume = VolumeUnit::rescale(*volume);
+                    }
+                }
+            }
+        }
+        Ok(self.value())
+    }
+
+    /// Recomputes the sums from the window, or returns false if they would overflow.
+    fn recompute_sums(&mut self) -> bool {
+        let mut sum_price_volume = dec!(0);
+        let mut sum_volume = dec!(0);
+        for (close, volume) in self.window.iter() {
+            let Some(sums) = close.checked_mul(*volume).and_then(|price_volume| {
+                Some((sum_price_volume.checked_add(price_volume)?, sum_volume.checked_add(*volume)?))
+            }) else {
+                return false;
+            };
+            (sum_price_volume, sum_volume) = sums;
+        }
+        self.sum_price_volume = sum_price_volume;
+        self.sum_volume = sum_volume;
+        true
+    }
+
+    fn value(&self) -> Option<Decimal> {
+        if self.window.len() < self.period || self.sum_volume <= dec!(0) {
+            return None;
+        }
+        Some(self.sum_price_volume / self.sum_volume)
+    }
+}
+
+impl Period for VolumeWeightedMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl<T: Close + Volume> Next<&T> for VolumeWeightedMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.try_next(input).expect("invalid volume")
+    }
+}
+
+impl Reset for VolumeWeightedMovingAverage {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.sum_price_volume = dec!(0);
+        self.sum_volume = dec!(0);
+        self.unit = VolumeUnit::default();
+    }
+}
+
+impl Default for VolumeWeightedMovingAverage {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for VolumeWeightedMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VWMA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(close: f64, volume: f64) -> DataItem {
+        DataItem::builder()
+            .high(close)
+            .low(close)
+            .close(close)
+            .open(close)
+            .volume(volume)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeWeightedMovingAverage::new(0).is_err());
+        assert!(VolumeWeightedMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut vwma = VolumeWeightedMovingAverage::new(2).unwrap();
+        assert_eq!(vwma.next(&bar(10.0, 100.0)), None);
+        // (10 * 100 + 20 * 300) / 400
+        assert_eq!(vwma.next(&bar(20.0, 300.0)), Some(dec!(17.5)));
+        // (20 * 300 + 5 * 100) / 400
+        assert_eq!(vwma.next(&bar(5.0, 100.0)), Some(dec!(16.25)));
+    }
+
+    #[test]
+    fn test_high_volume_dominates() {
+        let mut vwma = VolumeWeightedMovingAverage::new(4).unwrap();
+        vwma.next(&bar(10.0, 10.0));
+        vwma.next(&bar(10.0, 10.0));
+        vwma.next(&bar(10.0, 10.0));
+        let out = vwma.next(&bar(50.0, 970.0)).unwrap();
+        // the SMA would be 20
+        assert_eq!(out, dec!(48.8));
+    }
+
+    #[test]
+    fn test_zero_volume() {
+        let mut vwma = VolumeWeightedMovingAverage::new(2).unwrap();
+        vwma.next(&bar(10.0, 0.0));
+        assert_eq!(vwma.next(&bar(12.0, 0.0)), None);
+        assert_eq!(vwma.next(&bar(14.0, 10.0)), Some(dec!(14)));
+    }
+
+    #[test]
+    fn test_volume_policy() {
+        let series = [bar(10.0, 100.0), bar(20.0, 300.0), bar(50.0, 0.0), bar(5.0, 100.0)];
+        let run = |policy| {
+            let mut vwma = VolumeWeightedMovingAverage::with_volume_policy(2, policy).unwrap();
+            series.iter().map(|bar| vwma.try_next(bar)).collect::<Vec<_>>()
+        };
+
+        // the zero-volume bar still pushes 10 x 100 out of the window
+        assert_eq!(
+            run(VolumePolicy::TreatAsZero),
+            [Ok(None), Ok(Some(dec!(17.5))), Ok(Some(dec!(20))), Ok(Some(dec!(5)))]
+        );
+        // the window stays 10 x 100, 20 x 300 until the next traded bar
+        assert_eq!(
+            run(VolumePolicy::Skip),
+            [Ok(None), Ok(Some(dec!(17.5))), Ok(Some(dec!(17.5))), Ok(Some(dec!(16.25)))]
+        );
+        assert_eq!(
+            run(VolumePolicy::Error),
+            [Ok(None), Ok(Some(dec!(17.5))), Err(TaError::DataItemInvalid), Ok(Some(dec!(16.25)))]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid volume")]
+    fn test_volume_policy_error_panics_in_next() {
+        let mut vwma = VolumeWeightedMovingAverage::with_volume_policy(2, VolumePolicy::Error).unwrap();
+        vwma.next(&bar(10.0, 0.0));
+    }
+
+    #[test]
+    fn test_large_volume() {
+        let mut huge = VolumeWeightedMovingAverage::new(5).unwrap();
+        let mut small = VolumeWeightedMovingAverage::new(5).unwrap();
+        for i in 0..30 {
+            let close = 1234.5 + (i % 7) as f64 * 3.25;
+            let weight = 1.0 + (i % 4) as f64;
+            // Σ(close * volume) passes Decimal::MAX within the first window
+            let out = huge.next(&bar(close, weight * 1e25));
+            let expected = small.next(&bar(close, weight));
+            assert_eq!(out.map(|out| out.round_dp(8)), expected.map(|expected| expected.round_dp(8)));
+        }
+    }
+
+    #[test]
+    fn test_overflow_in_coarsest_unit() {
+        let mut vwma = VolumeWeightedMovingAverage::new(2).unwrap();
+        vwma.next(&bar(100.0, 1.0));
+        assert_eq!(vwma.next(&bar(200.0, 3.0)), Some(dec!(175)));
+
+        // 1e25 * 7e28 overflows even counted in units of 1e24; the bar is rejected
+        assert_eq!(vwma.try_next(&bar(1e25, 7e28)), Err(TaError::InvalidParameter));
+        // (200 * 3 + 100) / 4
+        assert_eq!(vwma.next(&bar(100.0, 1.0)), Some(dec!(175)));
+    }
+
+    #[test]
+    fn test_tiny_values() {
+        // prices and volumes below 1e-12 are kept exactly while nothing overflows
+        let mut vwma = VolumeWeightedMovingAverage::new(2).unwrap();
+        vwma.next(&bar(2e-13, 1.0));
+        assert_eq!(vwma.next(&bar(2e-13, 3.0)).unwrap().round_dp(20), dec!(0.0000000000002));
+
+        let mut vwma = VolumeWeightedMovingAverage::new(2).unwrap();
+        vwma.next(&bar(1.23456789e-9, 1.0));
+        let out = vwma.next(&bar(1.23456789e-9, 3.0)).unwrap();
+        assert_eq!(out.round_dp(17), dec!(0.00000000123456789));
+
+        let mut vwma = VolumeWeightedMovingAverage::new(2).unwrap();
+        vwma.next(&bar(100.0, 1e-13));
+        assert_eq!(vwma.next(&bar(200.0, 3e-13)).unwrap().round_dp(8), dec!(175));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vwma = VolumeWeightedMovingAverage::new(2).unwrap();
+        vwma.next(&bar(10.0, 100.0));
+        assert!(vwma.next(&bar(20.0, 300.0)).is_some());
+
+        vwma.reset();
+        assert_eq!(vwma.next(&bar(30.0, 100.0)), None);
+        assert_eq!(vwma.next(&bar(10.0, 100.0)), Some(dec!(20)));
+    }
+
+    #[test]
+    fn test_default() {
+        VolumeWeightedMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", VolumeWeightedMovingAverage::default()), "VWMA(20)");
+    }
+}
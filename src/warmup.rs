@@ -0,0 +1,279 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::{Close, Next, Period, Reset};
+
+/// Output of an indicator that can be viewed as "maybe a value".
+///
+/// Infallible outputs are always a value, `Option` outputs are passed through as is.
+pub trait WarmupOutput {
+    type Value;
+
+    fn into_option(self) -> Option<Self::Value>;
+}
+
+impl WarmupOutput for Decimal {
+    type Value = Decimal;
+
+    fn into_option(self) -> Option<Decimal> {
+        Some(self)
+    }
+}
+
+impl<T> WarmupOutput for Option<T> {
+    type Value = T;
+
+    fn into_option(self) -> Option<T> {
+        self
+    }
+}
+
+/// Adapter that hides an indicator's warmup outputs.
+///
+/// The first `skip` outputs of the wrapped indicator are swallowed and returned as `None`.
+/// For indicators that already return `Option`, any leading `None`s past that point are
+/// swallowed too, so the adapter stays silent until the first real value. From then on
+/// every output is passed through unchanged.
+#[derive(Debug, Clone)]
+pub struct SkipWarmup<I> {
+    indicator: I,
+    skip: usize,
+    count: usize,
+    warmup_len: Option<usize>,
+}
+
+impl<I> SkipWarmup<I> {
+    pub fn new(indicator: I, skip: usize) -> Self {
+        Self {
+            indicator,
+            skip,
+            count: 0,
+            warmup_len: None,
+        }
+    }
+
+    /// Number of inputs swallowed before the first value was passed through, or `None`
+    /// while still warming up.
+    pub fn warmup_len(&self) -> Option<usize> {
+        self.warmup_len
+    }
+
+    pub fn inner(&self) -> &I {
+        &self.indicator
+    }
+}
+
+impl<I, In> Next<In> for SkipWarmup<I>
+where
+    I: Next<In>,
+    I::Output: WarmupOutput,
+{
+    type Output = Option<<I::Output as WarmupOutput>::Value>;
+
+    fn next(&mut self, input: In) -> Self::Output {
+        let output = self.indicator.next(input).into_option();
+        if self.warmup_len.is_none() {
+            if self.count < self.skip || output.is_none() {
+                self.count += 1;
+                return None;
+            }
+            self.warmup_len = Some(self.count);
+        }
+        output
+    }
+}
+
+impl<I: Reset> Reset for SkipWarmup<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.count = 0;
+        self.warmup_len = None;
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for SkipWarmup<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.indicator.fmt(f)
+    }
+}
+
+/// What an indicator outputs before it has seen _period_ inputs.
+///
+/// The core averages each have their own native behavior, kept for back-compat:
+///
+/// * [SimpleMovingAverage](../simple_moving_average/struct.SimpleMovingAverage.html) - `Partial`
+/// * [ExponentialMovingAverage](../ema/struct.ExponentialMovingAverage.html) - `Emit`
+/// * [RollingMovingAverage](../rolling_moving_average/struct.RollingMovingAverage.html) and
+///   [WeightedMovingAverage](../weighted_moving_average/struct.WeightedMovingAverage.html) - `None`
+///
+/// Their `warmup_policy` builder wraps them in a [WithWarmupPolicy](struct.WithWarmupPolicy.html)
+/// that applies the chosen policy instead.
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum WarmupPolicy {
+    /// Whatever the indicator computes from the inputs so far, `None` included.
+    Emit,
+    /// `None`.
+    None,
+    /// Mean of the inputs seen so far.
+    Partial,
+}
+
+/// An indicator with its warmup output chosen by a [WarmupPolicy](enum.WarmupPolicy.html).
+///
+/// Once _period_ inputs have been seen, the wrapped indicator's output is passed through
+/// whatever the policy. Created with the `warmup_policy` builder of the core averages.
+#[derive(Debug, Clone)]
+pub struct WithWarmupPolicy<I> {
+    indicator: I,
+    policy: WarmupPolicy,
+    count: usize,
+    sum: Decimal,
+}
+
+impl<I> WithWarmupPolicy<I> {
+    pub fn new(indicator: I, policy: WarmupPolicy) -> Self {
+        Self {
+            indicator,
+            policy,
+            count: 0,
+            sum: dec!(0),
+        }
+    }
+
+    pub fn policy(&self) -> WarmupPolicy {
+        self.policy
+    }
+
+    pub fn inner(&self) -> &I {
+        &self.indicator
+    }
+}
+
+impl<I: Period> Period for WithWarmupPolicy<I> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I> Next<Decimal> for WithWarmupPolicy<I>
+where
+    I: Next<Decimal> + Period,
+    I::Output: WarmupOutput<Value = Decimal>,
+{
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let output = self.indicator.next(input).into_option();
+        if self.count >= self.indicator.period() {
+            return output;
+        }
+
+        self.count += 1;
+        self.sum += input;
+        if self.count == self.indicator.period() {
+            return output;
+        }
+        match self.policy {
+            WarmupPolicy::Emit => output,
+            WarmupPolicy::None => None,
+            WarmupPolicy::Partial => Some(self.sum / Decimal::from(self.count)),
+        }
+    }
+}
+
+impl<I, T: Close> Next<&T> for WithWarmupPolicy<I>
+where
+    I: Next<Decimal> + Period,
+    I::Output: WarmupOutput<Value = Decimal>,
+{
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl<I: Reset> Reset for WithWarmupPolicy<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.count = 0;
+        self.sum = dec!(0);
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for WithWarmupPolicy<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.indicator.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+    use crate::rolling_moving_average::RollingMovingAverage;
+    use crate::simple_moving_average::SimpleMovingAverage;
+
+    #[test]
+    fn test_skip_infallible() {
+        let mut sma = SkipWarmup::new(SimpleMovingAverage::new(3).unwrap(), 3);
+
+        assert_eq!(sma.next(dec!(4)), None);
+        assert_eq!(sma.next(dec!(5)), None);
+        assert_eq!(sma.warmup_len(), None);
+        assert_eq!(sma.next(dec!(6)), None);
+        assert_eq!(sma.next(dec!(7)), Some(dec!(6)));
+        assert_eq!(sma.warmup_len(), Some(3));
+        assert_eq!(sma.next(dec!(8)), Some(dec!(7)));
+    }
+
+    #[test]
+    fn test_skip_optional() {
+        let mut rma = SkipWarmup::new(RollingMovingAverage::new(3).unwrap(), 0);
+
+        assert_eq!(rma.next(dec!(3)), None);
+        assert_eq!(rma.next(dec!(6)), None);
+        assert_eq!(rma.next(dec!(9)), Some(dec!(6)));
+        assert_eq!(rma.warmup_len(), Some(2));
+    }
+
+    #[test]
+    fn test_skip_optional_past_warmup() {
+        let mut rma = SkipWarmup::new(RollingMovingAverage::new(2).unwrap(), 2);
+
+        assert_eq!(rma.next(dec!(2)), None);
+        assert_eq!(rma.next(dec!(4)), None);
+        assert_eq!(rma.next(dec!(6)), Some(dec!(4.5)));
+        assert_eq!(rma.warmup_len(), Some(2));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sma = SkipWarmup::new(SimpleMovingAverage::new(2).unwrap(), 1);
+        sma.next(dec!(4));
+        assert!(sma.next(dec!(6)).is_some());
+
+        sma.reset();
+        assert_eq!(sma.warmup_len(), None);
+        assert_eq!(sma.next(dec!(10)), None);
+        assert_eq!(sma.next(dec!(20)), Some(dec!(15)));
+    }
+
+    #[test]
+    fn test_policy_reset() {
+        let mut sma = SimpleMovingAverage::new(2).unwrap().warmup_policy(WarmupPolicy::None);
+        sma.next(dec!(4));
+        assert!(sma.next(dec!(6)).is_some());
+
+        sma.reset();
+        assert_eq!(sma.next(dec!(10)), None);
+        assert_eq!(sma.next(dec!(20)), Some(dec!(15)));
+        assert_eq!(format!("{}", sma), "SMA(2)");
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = SkipWarmup::new(SimpleMovingAverage::new(5).unwrap(), 5);
+        assert_eq!(format!("{}", sma), "SMA(5)");
+    }
+}
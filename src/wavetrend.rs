@@ -0,0 +1,181 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, High, Low, Next, Period, Reset};
+
+use crate::ema::ExponentialMovingAverage;
+use crate::model::WaveTrendOutput;
+use crate::simple_moving_average::SimpleMovingAverage;
+
+/// WaveTrend oscillator (WT), as popularised by LazyBear.
+///
+/// A CCI-like channel index of the typical price, smoothed by a second EMA. Readings above
+/// 60 or below -60 are commonly read as overbought or oversold, and crosses of `wt1` over
+/// `wt2` as entry signals.
+///
+/// # Formula
+///
+/// ESA = EMA<sub>channel</sub>(ap), where ap = (high + low + close) / 3
+///
+/// D = EMA<sub>channel</sub>(|ap - ESA|)
+///
+/// CI = (ap - ESA) / (0.015 * D), or 0 when D is 0
+///
+/// WT1 = EMA<sub>average</sub>(CI)
+///
+/// WT2 = SMA<sub>4</sub>(WT1)
+///
+/// Returns `None` until _channel_period_ + _average_period_ bars have been seen.
+///
+/// # Parameters
+///
+/// * _channel_period_ - period of the ESA and D EMAs (integer greater than 0)
+/// * _average_period_ - period of the EMA of CI (integer greater than 0)
+#[doc(alias = "WT")]
+#[derive(Debug, Clone)]
+pub struct WaveTrend {
+    esa: ExponentialMovingAverage,
+    deviation: ExponentialMovingAverage,
+    tci: ExponentialMovingAverage,
+    signal: SimpleMovingAverage,
+    count: usize,
+}
+
+impl WaveTrend {
+    pub fn new(channel_period: usize, average_period: usize) -> Result<Self> {
+        if channel_period == 0 || average_period == 0 {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            esa: ExponentialMovingAverage::new(channel_period)?,
+            deviation: ExponentialMovingAverage::new(channel_period)?,
+            tci: ExponentialMovingAverage::new(average_period)?,
+            signal: SimpleMovingAverage::new(4)?,
+            count: 0,
+        })
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for WaveTrend {
+    type Output = Option<WaveTrendOutput>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let high = Decimal::from_f64(input.high()).unwrap();
+        let low = Decimal::from_f64(input.low()).unwrap();
+        let close = Decimal::from_f64_retain(input.close()).unwrap();
+        let ap = (high + low + close) / dec!(3);
+
+        let esa = self.esa.next(ap);
+        let d = self.deviation.next((ap - esa).abs());
+        let ci = if d.is_zero() {
+            dec!(0)
+        } else {
+            (ap - esa) / (dec!(0.015) * d)
+        };
+        let wt1 = self.tci.next(ci);
+        let wt2 = self.signal.next(wt1);
+
+        self.count += 1;
+        if self.count < self.esa.period() + self.tci.period() {
+            return None;
+        }
+        Some(WaveTrendOutput { wt1, wt2 })
+    }
+}
+
+impl Reset for WaveTrend {
+    fn reset(&mut self) {
+        self.esa.reset();
+        self.deviation.reset();
+        self.tci.reset();
+        self.signal.reset();
+        self.count = 0;
+    }
+}
+
+impl Default for WaveTrend {
+    fn default() -> Self {
+        Self::new(10, 21).unwrap()
+    }
+}
+
+impl fmt::Display for WaveTrend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WT({}, {})", self.esa.period(), self.tci.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder()
+            .high(high)
+            .low(low)
+            .close(close)
+            .open(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(WaveTrend::new(0, 21).is_err());
+        assert!(WaveTrend::new(10, 0).is_err());
+        assert!(WaveTrend::new(10, 21).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut wt = WaveTrend::new(3, 1).unwrap();
+        // ap = 3, 6, 3, 9
+        assert_eq!(wt.next(&bar(4.0, 2.0, 3.0)), None);
+        assert_eq!(wt.next(&bar(7.0, 5.0, 6.0)), None);
+        assert_eq!(wt.next(&bar(4.0, 2.0, 3.0)), None);
+
+        // ESA = 3, 4.5, 3.75, 6.375; D = 0, 0.75, 0.75, 1.6875
+        // CI = (9 - 6.375) / (0.015 * 1.6875) = 103.7037...
+        let out = wt.next(&bar(10.0, 8.0, 9.0)).unwrap();
+        assert_eq!(out.wt1.round_dp(4), dec!(103.7037));
+        // WT1 so far: 0, 133.33.., -66.66.., 103.70..
+        assert_eq!(out.wt2.round_dp(4), dec!(42.5926));
+    }
+
+    #[test]
+    fn test_flat_prices() {
+        let mut wt = WaveTrend::new(3, 2).unwrap();
+        for _ in 0..10 {
+            if let Some(out) = wt.next(&bar(11.0, 9.0, 10.0)) {
+                assert_eq!(out.wt1, dec!(0));
+                assert_eq!(out.wt2, dec!(0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut wt = WaveTrend::new(1, 1).unwrap();
+        assert_eq!(wt.next(&bar(4.0, 2.0, 3.0)), None);
+        assert!(wt.next(&bar(7.0, 5.0, 6.0)).is_some());
+
+        wt.reset();
+        assert_eq!(wt.next(&bar(4.0, 2.0, 3.0)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        let wt = WaveTrend::default();
+        assert_eq!(wt.esa.period(), 10);
+        assert_eq!(wt.tci.period(), 21);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", WaveTrend::new(10, 21).unwrap()), "WT(10, 21)");
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::errors::{Result, TaError};
+use ta::{Close, Next, Period, Reset};
+
+use crate::warmup::{WarmupPolicy, WithWarmupPolicy};
+
+/// Weighted moving average (WMA).
+///
+/// A linearly weighted average of the last _period_ values. By default the most recent
+/// value has weight _period_, the one before _period - 1_, down to weight 1 for the oldest.
+///
+/// `WeightedMovingAverage::centered` uses triangular weights instead, peaking at the middle
+/// of the window (TRIMA-style). Since the heaviest weight sits about _(period - 1) / 2_
+/// bars in the past, the centered average lags price by roughly that many bars.
+///
+/// # Formula
+///
+/// WMA = Σ(w<sub>i</sub> * p<sub>i</sub>) / Σw<sub>i</sub>
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal_macros::dec;
+/// use ta::Next;
+/// use technical_analysis::weighted_moving_average::WeightedMovingAverage;
+///
+/// let mut wma = WeightedMovingAverage::new(3).unwrap();
+/// assert_eq!(wma.next(dec!(1)), None);
+/// assert_eq!(wma.next(dec!(2)), None);
+/// assert_eq!(wma.next(dec!(3)).unwrap().round_dp(4), dec!(2.3333));
+/// ```
+#[doc(alias = "WMA")]
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct WeightedMovingAverage {
+    period: usize,
+    centered: bool,
+    weights: Vec<Decimal>,
+    weight_sum: Decimal,
+    window: VecDeque<Decimal>,
+}
+
+impl WeightedMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        Self::with_weights(period, false, |i| i + 1)
+    }
+
+    pub fn centered(period: usize) -> Result<Self> {
+        Self::with_weights(period, true, |i| (i + 1).min(period - i))
+    }
+
+    fn with_weights(period: usize, centered: bool, weight: impl Fn(usize) -> usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::InvalidParameter),
+            _ => {
+                // weights are indexed oldest first
+                let weights: Vec<Decimal> = (0..period).map(|i| Decimal::from(weight(i))).collect();
+                Ok(Self {
+                    period,
+                    centered,
+                    weight_sum: weights.iter().sum(),
+                    weights,
+                    window: VecDeque::with_capacity(period),
+                })
+            }
+        }
+    }
+
+    /// Wraps the WMA to pick its warmup output. Unwrapped, it returns `None` until the
+    /// window is full, which is `WarmupPolicy::None`; `Emit` keeps that behavior.
+    pub fn warmup_policy(self, policy: WarmupPolicy) -> WithWarmupPolicy<Self> {
+        WithWarmupPolicy::new(self, policy)
+    }
+}
+
+impl Period for WeightedMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl Next<Decimal> for WeightedMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(input);
+        if self.window.len() < self.period {
+            return None;
+        }
+
+        let weighted: Decimal = self
+            .window
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(value, weight)| value * weight)
+            .sum();
+        Some(weighted / self.weight_sum)
+    }
+}
+
+impl<T: Close> Next<&T> for WeightedMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for WeightedMovingAverage {
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+impl Default for WeightedMovingAverage {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for WeightedMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.centered {
+            write!(f, "TRIMA({})", self.period)
+        } else {
+            write!(f, "WMA({})", self.period)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    fn impulse_response(wma: &mut WeightedMovingAverage) -> Vec<Decimal> {
+        let period = wma.period();
+        let mut inputs = vec![dec!(0); period - 1];
+        inputs.push(dec!(1));
+        inputs.extend(vec![dec!(0); period - 1]);
+        inputs.into_iter().filter_map(|input| wma.next(input)).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(WeightedMovingAverage::new(0).is_err());
+        assert!(WeightedMovingAverage::new(1).is_ok());
+        assert!(WeightedMovingAverage::centered(0).is_err());
+        assert!(WeightedMovingAverage::centered(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut wma = WeightedMovingAverage::new(3).unwrap();
+        assert_eq!(wma.next(dec!(6)), None);
+        assert_eq!(wma.next(dec!(12)), None);
+        // (6 * 1 + 12 * 2 + 18 * 3) / 6
+        assert_eq!(wma.next(dec!(18)), Some(dec!(14)));
+        // (12 * 1 + 18 * 2 + 6 * 3) / 6
+        assert_eq!(wma.next(dec!(6)), Some(dec!(11)));
+    }
+
+    #[test]
+    fn test_next_centered() {
+        let mut wma = WeightedMovingAverage::centered(3).unwrap();
+        assert_eq!(wma.next(dec!(6)), None);
+        assert_eq!(wma.next(dec!(12)), None);
+        // (6 * 1 + 12 * 2 + 18 * 1) / 4
+        assert_eq!(wma.next(dec!(18)), Some(dec!(12)));
+    }
+
+    #[test]
+    fn test_centered_impulse_is_symmetric() {
+        let response = impulse_response(&mut WeightedMovingAverage::centered(5).unwrap());
+        let reversed: Vec<Decimal> = response.iter().rev().copied().collect();
+        assert_eq!(response.len(), 5);
+        assert_eq!(response, reversed);
+        assert_eq!(response.iter().copied().max(), Some(response[2]));
+
+        let response = impulse_response(&mut WeightedMovingAverage::new(5).unwrap());
+        let reversed: Vec<Decimal> = response.iter().rev().copied().collect();
+        assert_ne!(response, reversed);
+        assert_eq!(response.iter().copied().max(), Some(response[0]));
+    }
+
+    #[test]
+    fn test_warmup_policy() {
+        let outputs = |policy| {
+            let mut wma = WeightedMovingAverage::new(3).unwrap().warmup_policy(policy);
+            [dec!(3), dec!(6), dec!(9), dec!(3)].map(|input| wma.next(input))
+        };
+        assert_eq!(outputs(WarmupPolicy::Emit), [None, None, Some(dec!(7)), Some(dec!(5.5))]);
+        assert_eq!(outputs(WarmupPolicy::None), [None, None, Some(dec!(7)), Some(dec!(5.5))]);
+        assert_eq!(outputs(WarmupPolicy::Partial), [Some(dec!(3)), Some(dec!(4.5)), Some(dec!(7)), Some(dec!(5.5))]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut wma = WeightedMovingAverage::new(2).unwrap();
+        wma.next(dec!(4));
+        assert!(wma.next(dec!(6)).is_some());
+
+        wma.reset();
+        assert_eq!(wma.next(dec!(99)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        WeightedMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", WeightedMovingAverage::new(9).unwrap()), "WMA(9)");
+        assert_eq!(format!("{}", WeightedMovingAverage::centered(9).unwrap()), "TRIMA(9)");
+    }
+}
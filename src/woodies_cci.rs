@@ -0,0 +1,209 @@
+use std::collections::VecDeque;
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::{Result, TaError};
+use ta::{Close, High, Low, Next, Period, Reset};
+
+use crate::cci::CommodityChannelIndex;
+use crate::model::{WoodiesOutput, WoodiesTrend};
+
+/// Consecutive CCI readings on one side of the zero line that confirm a trend.
+const TREND_BARS: usize = 6;
+
+/// Woodie's CCI.
+///
+/// Ken Wood's trading system around a pair of
+/// [CCIs](../cci/struct.CommodityChannelIndex.html): the main CCI and a faster "turbo" CCI
+/// for early entries. The trend follows Woodie's zero-line rule: it turns up once the
+/// main CCI has stayed above zero for six consecutive bars, reaching +100 along the way,
+/// and down once it has stayed below zero for six bars, reaching -100. It then holds until
+/// the opposite side is confirmed the same way.
+///
+/// Returns `None` until _period_ bars have been seen.
+///
+/// # Parameters
+///
+/// * _period_ - period of the main CCI (greater than _turbo_period_)
+/// * _turbo_period_ - period of the turbo CCI (integer greater than 0)
+#[derive(Debug, Clone)]
+pub struct WoodiesCci {
+    cci: CommodityChannelIndex,
+    turbo: CommodityChannelIndex,
+    /// The last `TREND_BARS` main CCI readings.
+    recent: VecDeque<Decimal>,
+    trend: WoodiesTrend,
+}
+
+impl WoodiesCci {
+    pub fn new(period: usize, turbo_period: usize) -> Result<Self> {
+        if turbo_period >= period {
+            return Err(TaError::InvalidParameter);
+        }
+        Ok(Self {
+            cci: CommodityChannelIndex::new(period)?,
+            turbo: CommodityChannelIndex::new(turbo_period)?,
+            recent: VecDeque::with_capacity(TREND_BARS),
+            trend: WoodiesTrend::Neutral,
+        })
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for WoodiesCci {
+    type Output = Option<WoodiesOutput>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let turbo_cci = self.turbo.next(input);
+        let cci = self.cci.next(input)?;
+
+        if self.recent.len() == TREND_BARS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(cci);
+        if self.recent.len() == TREND_BARS {
+            if self.recent.iter().all(|cci| *cci > dec!(0)) && self.recent.iter().any(|cci| *cci >= dec!(100)) {
+                self.trend = WoodiesTrend::Up;
+            } else if self.recent.iter().all(|cci| *cci < dec!(0))
+                && self.recent.iter().any(|cci| *cci <= dec!(-100))
+            {
+                self.trend = WoodiesTrend::Down;
+            }
+        }
+
+        Some(WoodiesOutput {
+            cci,
+            turbo_cci: turbo_cci?,
+            trend: self.trend,
+        })
+    }
+}
+
+impl Reset for WoodiesCci {
+    fn reset(&mut self) {
+        self.cci.reset();
+        self.turbo.reset();
+        self.recent.clear();
+        self.trend = WoodiesTrend::Neutral;
+    }
+}
+
+impl Default for WoodiesCci {
+    fn default() -> Self {
+        Self::new(14, 6).unwrap()
+    }
+}
+
+impl fmt::Display for WoodiesCci {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WOODIES_CCI({}, {})", self.cci.period(), self.turbo.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ta::DataItem;
+    use super::*;
+
+    fn bar(close: f64) -> DataItem {
+        DataItem::builder()
+            .high(close)
+            .low(close)
+            .close(close)
+            .open(close)
+            .volume(1.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(WoodiesCci::new(14, 14).is_err());
+        assert!(WoodiesCci::new(6, 14).is_err());
+        assert!(WoodiesCci::new(14, 0).is_err());
+        assert!(WoodiesCci::new(14, 6).is_ok());
+    }
+
+    #[test]
+    fn test_matches_ccis() {
+        let mut woodies = WoodiesCci::new(5, 3).unwrap();
+        let mut cci = CommodityChannelIndex::new(5).unwrap();
+        let mut turbo = CommodityChannelIndex::new(3).unwrap();
+        for i in 0..20 {
+            let input = bar(100.0 + ((i * 7) % 5) as f64);
+            let (cci, turbo) = (cci.next(&input), turbo.next(&input));
+            let out = woodies.next(&input);
+            assert_eq!(out.is_some(), i >= 4);
+            if let Some(out) = out {
+                assert_eq!(Some(out.cci), cci);
+                assert_eq!(Some(out.turbo_cci), turbo);
+            }
+        }
+    }
+
+    #[test]
+    fn test_trend_flips_after_confirmation() {
+        let mut woodies = WoodiesCci::new(5, 3).unwrap();
+        let trends = |woodies: &mut WoodiesCci, closes: &[f64]| -> Vec<WoodiesTrend> {
+            closes.iter().filter_map(|close| woodies.next(&bar(*close))).map(|out| out.trend).collect()
+        };
+
+        // a steady rise keeps the CCI above +100
+        let rising: Vec<f64> = (0..10).map(|i| 100.0 + i as f64).collect();
+        let out = trends(&mut woodies, &rising);
+        // six readings from the fifth bar on: the sixth confirms
+        assert_eq!(out.len(), 6);
+        assert!(out[..5].iter().all(|trend| *trend == WoodiesTrend::Neutral));
+        assert_eq!(out[5], WoodiesTrend::Up);
+
+        // a steady fall: the CCI turns negative within a bar or two, and the trend holds
+        // until six negative readings are in
+        let falling: Vec<f64> = (1..=12).map(|i| 109.0 - 2.0 * i as f64).collect();
+        let out = trends(&mut woodies, &falling);
+        let flip = out.iter().position(|trend| *trend == WoodiesTrend::Down).unwrap();
+        assert!(flip >= TREND_BARS - 1);
+        assert!(out[..flip].iter().all(|trend| *trend == WoodiesTrend::Up));
+        assert!(out[flip..].iter().all(|trend| *trend == WoodiesTrend::Down));
+    }
+
+    #[test]
+    fn test_trend_holds_without_confirmation() {
+        let mut woodies = WoodiesCci::new(3, 2).unwrap();
+        let mut last = None;
+        for i in 0..8 {
+            last = woodies.next(&bar(100.0 + i as f64));
+        }
+        assert_eq!(last.as_ref().unwrap().trend, WoodiesTrend::Up);
+
+        // a flat market pins the CCI at 0, which confirms neither side
+        for _ in 0..10 {
+            last = woodies.next(&bar(107.0));
+        }
+        let last = last.unwrap();
+        assert_eq!(last.cci, dec!(0));
+        assert_eq!(last.trend, WoodiesTrend::Up);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut woodies = WoodiesCci::new(3, 2).unwrap();
+        for i in 0..10 {
+            woodies.next(&bar(100.0 + i as f64));
+        }
+        assert_eq!(woodies.next(&bar(111.0)).unwrap().trend, WoodiesTrend::Up);
+
+        woodies.reset();
+        woodies.next(&bar(100.0));
+        woodies.next(&bar(101.0));
+        assert_eq!(woodies.next(&bar(102.0)).unwrap().trend, WoodiesTrend::Neutral);
+    }
+
+    #[test]
+    fn test_default() {
+        WoodiesCci::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", WoodiesCci::default()), "WOODIES_CCI(14, 6)");
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use ta::errors::Result;
+use ta::{Close, Next, Period, Reset};
+
+use crate::ema::ExponentialMovingAverage;
+use crate::lag::Lag;
+
+/// Zero-lag exponential moving average (ZLEMA).
+///
+/// Removes most of an EMA's lag by feeding it the input extrapolated by its own change over
+/// the lag the EMA would otherwise introduce.
+///
+/// # Formula
+///
+/// ZLEMA = EMA(period) of (2 * value - value<sub>lag bars ago</sub>)
+///
+/// Where _lag_ = (period - 1) / 2, rounded down. Returns `None` until _lag_ + 1 values have
+/// been seen. With a _lag_ of 0 it is a plain EMA.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[doc(alias = "ZLEMA")]
+#[derive(Debug, Clone)]
+pub struct ZeroLagExponentialMovingAverage {
+    ema: ExponentialMovingAverage,
+    lag: Option<Lag>,
+}
+
+impl ZeroLagExponentialMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        let ema = ExponentialMovingAverage::new(period)?;
+        let lag = match (period - 1) / 2 {
+            0 => None,
+            lag => Some(Lag::new(lag)?),
+        };
+        Ok(Self { ema, lag })
+    }
+}
+
+impl Period for ZeroLagExponentialMovingAverage {
+    fn period(&self) -> usize {
+        self.ema.period()
+    }
+}
+
+impl Next<Decimal> for ZeroLagExponentialMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let delagged = match &mut self.lag {
+            Some(lag) => dec!(2) * input - lag.next(input)?,
+            None => input,
+        };
+        Some(self.ema.next(delagged))
+    }
+}
+
+impl<T: Close> Next<&T> for ZeroLagExponentialMovingAverage {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for ZeroLagExponentialMovingAverage {
+    fn reset(&mut self) {
+        self.ema.reset();
+        if let Some(lag) = &mut self.lag {
+            lag.reset();
+        }
+    }
+}
+
+impl Default for ZeroLagExponentialMovingAverage {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for ZeroLagExponentialMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZLEMA({})", self.ema.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(ZeroLagExponentialMovingAverage::new(0).is_err());
+        assert!(ZeroLagExponentialMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        // lag 1, k = 0.5
+        let mut zlema = ZeroLagExponentialMovingAverage::new(3).unwrap();
+        assert_eq!(zlema.next(dec!(10)), None);
+        assert_eq!(zlema.next(dec!(12)), Some(dec!(14)));
+        assert_eq!(zlema.next(dec!(12)), Some(dec!(13)));
+        assert_eq!(zlema.next(dec!(16)), Some(dec!(16.5)));
+    }
+
+    #[test]
+    fn test_no_lag_is_ema() {
+        let mut zlema = ZeroLagExponentialMovingAverage::new(2).unwrap();
+        let mut ema = ExponentialMovingAverage::new(2).unwrap();
+        for input in [dec!(4), dec!(7), dec!(3), dec!(9)] {
+            assert_eq!(zlema.next(input), Some(ema.next(input)));
+        }
+    }
+
+    #[test]
+    fn test_less_lag_than_ema() {
+        let mut zlema = ZeroLagExponentialMovingAverage::new(9).unwrap();
+        let mut ema = ExponentialMovingAverage::new(9).unwrap();
+        for i in 0..30 {
+            let input = Decimal::from(i);
+            let (fast, slow) = (zlema.next(input), ema.next(input));
+            if let Some(fast) = fast {
+                assert!(input - fast < input - slow || i < 5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut zlema = ZeroLagExponentialMovingAverage::new(3).unwrap();
+        zlema.next(dec!(10));
+        zlema.next(dec!(12));
+
+        zlema.reset();
+        assert_eq!(zlema.next(dec!(12)), None);
+        assert_eq!(zlema.next(dec!(12)), Some(dec!(12)));
+    }
+
+    #[test]
+    fn test_default() {
+        ZeroLagExponentialMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", ZeroLagExponentialMovingAverage::new(9).unwrap()), "ZLEMA(9)");
+    }
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+use rust_decimal::Decimal;
+use ta::errors::Result;
+use ta::{Close, Next, Period, Reset};
+
+use crate::simple_moving_average::{MinPeriodsSimpleMovingAverage, SimpleMovingAverage};
+use crate::standard_deviation::StandardDeviation;
+
+/// Rolling z-score (ZSCORE).
+///
+/// How many standard deviations the latest value sits from the mean of the last _period_
+/// values, the current one included. Often used for mean-reversion entries.
+///
+/// # Formula
+///
+/// ZSCORE = (value - SMA(period)) / SD(period)
+///
+/// Returns `None` until _period_ values have been seen, and when the window has no
+/// dispersion.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0)
+#[doc(alias = "ZSCORE")]
+#[derive(Debug, Clone)]
+pub struct ZScore {
+    sma: MinPeriodsSimpleMovingAverage,
+    sd: StandardDeviation,
+}
+
+impl ZScore {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            sma: SimpleMovingAverage::with_min_periods(period, period)?,
+            sd: StandardDeviation::new(period)?,
+        })
+    }
+}
+
+impl Period for ZScore {
+    fn period(&self) -> usize {
+        self.sd.period()
+    }
+}
+
+impl Next<Decimal> for ZScore {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        let mean = self.sma.next(input);
+        let sd = self.sd.next(input)?;
+        if sd.is_zero() {
+            return None;
+        }
+        Some((input - mean?) / sd)
+    }
+}
+
+impl<T: Close> Next<&T> for ZScore {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(Decimal::from_f64_retain(input.close()).unwrap())
+    }
+}
+
+impl Reset for ZScore {
+    fn reset(&mut self) {
+        self.sma.reset();
+        self.sd.reset();
+    }
+}
+
+impl Default for ZScore {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for ZScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZSCORE({})", self.sd.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(ZScore::new(0).is_err());
+        assert!(ZScore::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut zscore = ZScore::new(8).unwrap();
+        for value in [2, 4, 4, 4, 5, 5, 7] {
+            assert_eq!(zscore.next(Decimal::from(value)), None);
+        }
+        // mean 5, SD 2
+        assert_eq!(zscore.next(dec!(9)).unwrap().round_dp(10), dec!(2));
+        // window 4, 4, 4, 5, 5, 7, 9, 1: mean 4.875
+        assert!(zscore.next(dec!(1)).unwrap() < dec!(-1));
+    }
+
+    #[test]
+    fn test_zero_dispersion() {
+        let mut zscore = ZScore::new(2).unwrap();
+        zscore.next(dec!(3));
+        assert_eq!(zscore.next(dec!(3)), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut zscore = ZScore::new(2).unwrap();
+        zscore.next(dec!(1));
+        assert!(zscore.next(dec!(3)).is_some());
+
+        zscore.reset();
+        assert_eq!(zscore.next(dec!(1)), None);
+    }
+
+    #[test]
+    fn test_default() {
+        ZScore::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", ZScore::default()), "ZSCORE(20)");
+    }
+}